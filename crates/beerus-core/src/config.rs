@@ -10,6 +10,10 @@ use shellexpand;
 #[cfg(feature = "std")]
 use std::{env, fs, net::SocketAddr, path::PathBuf, str::FromStr};
 
+use crate::lightclient::{
+    backfill::DEFAULT_BACKFILL_CONCURRENCY,
+    block_cache::{BlockRetention, DEFAULT_BLOCK_CACHE_MAX_BLOCKS},
+};
 use crate::stdlib::string::{String, ToString};
 
 pub const STARKNET_MAINNET_CC_ADDRESS: &str = "0xc662c410C0ECf747543f5bA90660f6ABeBD9C8c4";
@@ -37,6 +41,16 @@ pub struct Config {
     #[cfg(feature = "std")]
     pub helios_rpc_address: Option<u16>,
     pub ethereum_checkpoint: Option<String>,
+    /// Number of state diff entries checked against proofs by verified state updates,
+    /// all of them if `None`.
+    pub state_diff_sample_size: Option<usize>,
+    /// Maximum number of StarkNet blocks kept in the block cache.
+    pub block_cache_max_blocks: Option<usize>,
+    /// Maximum size in bytes of the StarkNet blocks kept in the block cache, unbounded if `None`.
+    pub block_cache_max_bytes: Option<usize>,
+    /// Number of StarkNet blocks fetched concurrently when filling blocks skipped by the sync
+    /// loop.
+    pub backfill_concurrency: Option<usize>,
 }
 
 impl Config {
@@ -90,6 +104,22 @@ impl Config {
             config.ethereum_checkpoint = None;
         }
 
+        if let Ok(sample_size) = std::env::var("STATE_DIFF_SAMPLE_SIZE") {
+            config.state_diff_sample_size = Some(sample_size.parse().unwrap());
+        }
+
+        if let Ok(max_blocks) = std::env::var("BLOCK_CACHE_MAX_BLOCKS") {
+            config.block_cache_max_blocks = Some(max_blocks.parse().unwrap());
+        }
+
+        if let Ok(max_bytes) = std::env::var("BLOCK_CACHE_MAX_BYTES") {
+            config.block_cache_max_bytes = Some(max_bytes.parse().unwrap());
+        }
+
+        if let Ok(concurrency) = std::env::var("BACKFILL_CONCURRENCY") {
+            config.backfill_concurrency = Some(concurrency.parse().unwrap());
+        }
+
         config
     }
 
@@ -152,6 +182,10 @@ impl Config {
             starknet_rpc: starknet_rpc.to_string(),
             starknet_core_contract_address: starknet_cc,
             poll_interval_secs: Some(DEFAULT_POLL_INTERVAL_SECS),
+            state_diff_sample_size: None,
+            block_cache_max_blocks: Some(DEFAULT_BLOCK_CACHE_MAX_BLOCKS),
+            block_cache_max_bytes: None,
+            backfill_concurrency: Some(DEFAULT_BACKFILL_CONCURRENCY),
         }
     }
 
@@ -171,6 +205,22 @@ impl Config {
         }
     }
 
+    /// Return the retention window of the block cache.
+    pub fn get_block_retention(&self) -> BlockRetention {
+        BlockRetention {
+            max_blocks: self
+                .block_cache_max_blocks
+                .unwrap_or(DEFAULT_BLOCK_CACHE_MAX_BLOCKS),
+            max_bytes: self.block_cache_max_bytes,
+        }
+    }
+
+    /// Return the number of StarkNet blocks fetched concurrently by the backfill.
+    pub fn get_backfill_concurrency(&self) -> usize {
+        self.backfill_concurrency
+            .unwrap_or(DEFAULT_BACKFILL_CONCURRENCY)
+    }
+
     // Return the current checkpoint given the network.
     pub async fn get_checkpoint(&self) -> eyre::Result<String> {
         let cf = checkpoints::CheckpointFallback::new()
@@ -202,6 +252,10 @@ impl Config {
         env::remove_var("DATA_DIR");
         env::remove_var("BEERUS_RPC_ADDR");
         env::remove_var("HELIOS_RPC_ADDR");
+        env::remove_var("STATE_DIFF_SAMPLE_SIZE");
+        env::remove_var("BLOCK_CACHE_MAX_BLOCKS");
+        env::remove_var("BLOCK_CACHE_MAX_BYTES");
+        env::remove_var("BACKFILL_CONCURRENCY");
     }
 }
 
@@ -272,6 +326,10 @@ impl Default for Config {
             helios_rpc_address: Some(DEFAULT_HELIOS_RPC_ADDR),
             #[cfg(feature = "std")]
             ethereum_checkpoint: None,
+            state_diff_sample_size: None,
+            block_cache_max_blocks: Some(DEFAULT_BLOCK_CACHE_MAX_BLOCKS),
+            block_cache_max_bytes: None,
+            backfill_concurrency: Some(DEFAULT_BACKFILL_CONCURRENCY),
         }
     }
 }
@@ -0,0 +1,413 @@
+use crate::stdlib::{string::ToString, vec::Vec};
+
+use eyre::{eyre, Result};
+use futures::stream::{self, StreamExt};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{BlockId, BlockWithTxs, FieldElement, MaybePendingBlockWithTxs};
+use starknet::providers::jsonrpc::JsonRpcError;
+use tokio::sync::RwLock;
+
+use super::{
+    beerus::{NodeData, SyncBlock},
+    block_cache::BlockCache,
+    starknet::StarkNetLightClient,
+};
+
+/// Default number of blocks fetched concurrently when filling a gap in the block cache.
+pub const DEFAULT_BACKFILL_CONCURRENCY: usize = 4;
+/// Number of reorganizations buffered for each subscriber before the oldest ones are dropped.
+pub const REORG_CHANNEL_CAPACITY: usize = 16;
+
+/// A cached block whose parent hash does not match the hash of the cached block before it:
+/// the chain was reorganized between the two fetches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reorg {
+    pub block_number: u64,
+    /// Parent hash of the block.
+    pub parent_hash: FieldElement,
+    /// Hash of the cached block at `block_number - 1`.
+    pub expected_parent_hash: FieldElement,
+}
+
+/// A reorganization of the StarkNet chain, rolled back from the block cache.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorg {
+    /// Last cached block shared by the orphaned and the canonical chains, `None` if the fork
+    /// is older than the cached blocks.
+    pub fork_point: Option<SyncBlock>,
+    /// Blocks rolled back from the block cache, by ascending block number.
+    pub orphaned: Vec<SyncBlock>,
+    /// Tip of the canonical chain.
+    pub tip: SyncBlock,
+}
+
+/// Outcome of tracking a new StarkNet tip.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// Blocks skipped since the last tracked block, fetched and inserted in the block cache.
+    pub filled: Vec<u64>,
+    /// Skipped blocks that could not be fetched. Tracking stops before the first one, which is
+    /// fetched again on the next round.
+    pub failed: Vec<u64>,
+    /// Reorganization rolled back from the block cache.
+    pub rollback: Option<ChainReorg>,
+    /// Breaks in the parent hash linkage of the new blocks that could not be resolved.
+    pub reorgs: Vec<Reorg>,
+}
+
+/// Track a new StarkNet tip: fetch the blocks skipped since the last tracked block, insert them
+/// and the tip in the block cache, then check the parent hash linkage of the new blocks.
+///
+/// If the new blocks do not descend from the last tracked block, or if the tip replaces a cached
+/// block at or below the last tracked height, the cached chain is rolled back to the fork point
+/// and replaced by the canonical chain, the blocks above a shorter tip being dropped. Breaks left between the new blocks, e.g. backfilled from different
+/// branches, are resolved the same way from the top, the most recent block being canonical.
+/// The orphaned blocks are notified to the `NodeData::reorg_notifier` subscribers.
+///
+/// The skipped blocks are fetched `concurrency` at a time, without holding the node lock. If
+/// one of them cannot be fetched, only the blocks below it are tracked, so that no block is
+/// tracked without its parent. Nothing happens if the tip is not newer than the last tracked
+/// block and matches the cached block at its height, or is not cached, or if the fork point
+/// cannot be fetched, in which case the tip is tracked again on the next round.
+pub async fn track_tip(
+    starknet_lightclient: &dyn StarkNetLightClient,
+    node: &RwLock<NodeData>,
+    tip: BlockWithTxs,
+    concurrency: usize,
+) -> BackfillReport {
+    let mut report = BackfillReport::default();
+    let (last_block_number, cached_hash, missing) = {
+        let data = node.read().await;
+        let missing = missing_blocks(&data.payload, data.block_number, tip.block_number);
+        let cached_hash = data
+            .payload
+            .get(tip.block_number)
+            .map(|block| block.block_hash);
+        (data.block_number, cached_hash, missing)
+    };
+    let replaces_cached = tip.block_number <= last_block_number
+        && matches!(cached_hash, Some(hash) if hash != tip.block_hash);
+    if (tip.block_number <= last_block_number && !replaces_cached) || tip.block_number == 0 {
+        return report;
+    }
+
+    if !missing.is_empty() {
+        info!(
+            "Backfilling {} blocks skipped before block {}",
+            missing.len(),
+            tip.block_number
+        );
+    }
+    let mut blocks = Vec::new();
+    for (block_number, result) in fetch_blocks(starknet_lightclient, missing, concurrency).await {
+        match result {
+            Ok(MaybePendingBlockWithTxs::Block(block)) => {
+                if report.failed.is_empty() {
+                    report.filled.push(block_number);
+                    blocks.push(block);
+                }
+            }
+            Ok(MaybePendingBlockWithTxs::PendingBlock(_)) => {
+                warn!("Error backfilling block {}: block is pending", block_number);
+                report.failed.push(block_number);
+            }
+            Err(err) => {
+                warn!("Error backfilling block {}: {}", block_number, err.message);
+                report.failed.push(block_number);
+            }
+        }
+    }
+    if report.failed.is_empty() {
+        blocks.push(tip);
+    }
+    let (mut tip_block, tip_root) = match blocks.last() {
+        Some(block) => (sync_block(block), block.new_root),
+        None => return report,
+    };
+
+    // The first new block must be a child of the cached block before it, unless the chain was
+    // reorganized since the previous round.
+    let mut first_orphaned = None;
+    let mut canonical = Vec::new();
+    let first = &blocks[0];
+    if first.block_number <= last_block_number + 1 {
+        let parent_hash = node
+            .read()
+            .await
+            .payload
+            .get(first.block_number - 1)
+            .map(|block| block.block_hash);
+        if matches!(parent_hash, Some(hash) if hash != first.parent_hash) {
+            match find_fork(
+                starknet_lightclient,
+                node,
+                first.block_number - 1,
+                first.parent_hash,
+            )
+            .await
+            {
+                Ok((block_number, replaced)) => {
+                    first_orphaned = Some(block_number);
+                    canonical = replaced;
+                }
+                Err(err) => {
+                    warn!(
+                        "Error looking for the fork point of block {}: {}",
+                        tip_block.block_number, err
+                    );
+                    return BackfillReport::default();
+                }
+            }
+        } else if first.block_number <= last_block_number {
+            first_orphaned = Some(first.block_number);
+        }
+    }
+    canonical.extend(blocks);
+
+    let mut orphaned = {
+        let mut data = node.write().await;
+        let orphaned = match first_orphaned {
+            Some(block_number) => data.payload.truncate(block_number),
+            None => Vec::new(),
+        };
+        for block in canonical {
+            let block_number = block.block_number;
+            if let Err(err) = data.payload.insert(block) {
+                warn!("Error caching block {}: {}", block_number, err);
+            }
+        }
+        data.block_number = tip_block.block_number;
+        data.state_root = tip_root.to_string();
+        orphaned
+    };
+
+    // Resolve the breaks left between the new blocks from the top, each one at most once.
+    let mut below = tip_block.block_number + 1;
+    loop {
+        let reorg = check_continuity(
+            &node.read().await.payload,
+            last_block_number + 1,
+            tip_block.block_number,
+        )
+        .into_iter()
+        .rev()
+        .find(|reorg| reorg.block_number < below);
+        let reorg = match reorg {
+            Some(reorg) => reorg,
+            None => break,
+        };
+        below = reorg.block_number;
+        warn!(
+            "Reorg detected at block {}: parent hash {} does not match cached block hash {}",
+            reorg.block_number, reorg.parent_hash, reorg.expected_parent_hash
+        );
+
+        match find_fork(
+            starknet_lightclient,
+            node,
+            reorg.block_number - 1,
+            reorg.parent_hash,
+        )
+        .await
+        {
+            Ok((block_number, replaced)) => {
+                let mut data = node.write().await;
+                let orphaned_count = orphaned.len();
+                for block in replaced {
+                    let block_number = block.block_number;
+                    match data.payload.get(block_number) {
+                        Some(stale) if stale.block_hash != block.block_hash => {
+                            orphaned.push(stale.clone())
+                        }
+                        _ => {}
+                    }
+                    if let Err(err) = data.payload.insert(block) {
+                        warn!("Error caching block {}: {}", block_number, err);
+                    }
+                }
+                if orphaned.len() > orphaned_count {
+                    first_orphaned =
+                        Some(first_orphaned.map_or(block_number, |first| first.min(block_number)));
+                }
+            }
+            Err(err) => {
+                warn!(
+                    "Error looking for the fork point of block {}: {}",
+                    reorg.block_number, err
+                );
+                // The blocks above the break are fetched again on the next round.
+                let mut data = node.write().await;
+                data.payload.truncate(reorg.block_number);
+                if let Some(block) = data.payload.get(reorg.block_number - 1) {
+                    tip_block = sync_block(block);
+                    let state_root = block.new_root.to_string();
+                    data.state_root = state_root;
+                }
+                data.block_number = reorg.block_number - 1;
+                report.reorgs.push(reorg);
+                break;
+            }
+        }
+    }
+
+    let mut data = node.write().await;
+    info!("New Block Added to Payload:");
+    info!("Block Number {:?}", &data.block_number);
+    info!("Block Root {:?}", &data.state_root);
+
+    if let Some(first_orphaned) = first_orphaned {
+        // Blocks rolled back and inserted again, e.g. fetched again by `find_fork`, are not
+        // orphaned.
+        orphaned.retain(|block| {
+            data.payload
+                .get(block.block_number)
+                .map_or(true, |canonical| canonical.block_hash != block.block_hash)
+        });
+        orphaned.sort_by_key(|block| block.block_number);
+        let reorg = ChainReorg {
+            fork_point: first_orphaned
+                .checked_sub(1)
+                .and_then(|block_number| data.payload.get(block_number))
+                .map(sync_block),
+            orphaned: orphaned.iter().map(sync_block).collect(),
+            tip: tip_block,
+        };
+        warn!(
+            "Reorg detected: {} blocks orphaned from block {}",
+            reorg.orphaned.len(),
+            first_orphaned
+        );
+        data.sync.record_reorg(&reorg);
+        // Sending only fails if nobody is subscribed.
+        let _ = data.reorg_notifier.send(reorg.clone());
+        report.rollback = Some(reorg);
+    }
+
+    report.reorgs.extend(check_continuity(
+        &data.payload,
+        last_block_number + 1,
+        data.block_number,
+    ));
+    report
+}
+
+/// Walk the cached chain down from `block_number` until a cached block hashes to
+/// `parent_hash`, the parent of the canonical chain above it, fetching the canonical blocks on
+/// the way.
+///
+/// The walk stops at the first block missing from the cache, the fork is then older than the
+/// cached chain.
+///
+/// # Returns
+///
+/// Returns the number of the first orphaned block and the canonical blocks replacing the
+/// orphaned ones by ascending block number, or an `Err(eyre::Report)` if a canonical block
+/// cannot be fetched.
+async fn find_fork(
+    starknet_lightclient: &dyn StarkNetLightClient,
+    node: &RwLock<NodeData>,
+    mut block_number: u64,
+    mut parent_hash: FieldElement,
+) -> Result<(u64, Vec<BlockWithTxs>)> {
+    let mut canonical = Vec::new();
+    let first_orphaned = loop {
+        let cached_hash = node
+            .read()
+            .await
+            .payload
+            .get(block_number)
+            .map(|block| block.block_hash);
+        match cached_hash {
+            Some(hash) if hash != parent_hash => {}
+            _ => break block_number + 1,
+        }
+
+        let block = match starknet_lightclient
+            .get_block_with_txs(&BlockId::Number(block_number))
+            .await
+            .map_err(|err| eyre!(err.message))?
+        {
+            MaybePendingBlockWithTxs::Block(block) => block,
+            MaybePendingBlockWithTxs::PendingBlock(_) => {
+                return Err(eyre!("Block {block_number} is pending"))
+            }
+        };
+        parent_hash = block.parent_hash;
+        canonical.push(block);
+        if block_number == 0 {
+            break 0;
+        }
+        block_number -= 1;
+    };
+
+    canonical.reverse();
+    Ok((first_orphaned, canonical))
+}
+
+fn sync_block(block: &BlockWithTxs) -> SyncBlock {
+    SyncBlock {
+        block_number: block.block_number,
+        block_hash: block.block_hash,
+    }
+}
+
+/// Return the blocks skipped between the last tracked block and the tip and missing from the
+/// block cache, by ascending block number.
+///
+/// Nothing is skipped before the first tracked block, and only the blocks the cache retains
+/// alongside the tip are returned.
+pub fn missing_blocks(
+    payload: &BlockCache,
+    last_block_number: u64,
+    tip_block_number: u64,
+) -> Vec<u64> {
+    if last_block_number == 0 {
+        return Vec::new();
+    }
+    let max_blocks = payload.retention().max_blocks as u64;
+    let from = (last_block_number + 1).max((tip_block_number + 1).saturating_sub(max_blocks));
+    (from..tip_block_number)
+        .filter(|block_number| payload.get(*block_number).is_none())
+        .collect()
+}
+
+/// Fetch blocks by number from the full node, at most `concurrency` at a time.
+///
+/// The results are returned in the order of `block_numbers`.
+pub async fn fetch_blocks(
+    starknet_lightclient: &dyn StarkNetLightClient,
+    block_numbers: Vec<u64>,
+    concurrency: usize,
+) -> Vec<(u64, Result<MaybePendingBlockWithTxs, JsonRpcError>)> {
+    stream::iter(block_numbers)
+        .map(|block_number| async move {
+            let block = starknet_lightclient
+                .get_block_with_txs(&BlockId::Number(block_number))
+                .await;
+            (block_number, block)
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Return the cached blocks in `from..=to` whose parent hash does not match the hash of the
+/// cached block before them. Blocks whose parent is not cached are not checked.
+pub fn check_continuity(payload: &BlockCache, from: u64, to: u64) -> Vec<Reorg> {
+    payload
+        .values()
+        .filter(|block| block.block_number > 0 && (from..=to).contains(&block.block_number))
+        .filter_map(|block| {
+            let parent = payload.get(block.block_number - 1)?;
+            if block.parent_hash == parent.block_hash {
+                return None;
+            }
+            Some(Reorg {
+                block_number: block.block_number,
+                parent_hash: block.parent_hash,
+                expected_parent_hash: parent.block_hash,
+            })
+        })
+        .collect()
+}
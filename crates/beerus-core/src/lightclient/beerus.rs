@@ -1,12 +1,12 @@
 #[cfg(feature = "std")]
-use std::{str::FromStr, thread, time};
+use std::{str::FromStr, time::Duration};
 
 #[cfg(not(feature = "std"))]
 use gloo_timers::callback::Interval;
 #[cfg(not(feature = "std"))]
 use wasm_bindgen_futures::spawn_local;
 
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, OnceCell, RwLock};
 
 #[cfg(not(feature = "std"))]
 use core::str::FromStr;
@@ -17,41 +17,314 @@ use crate::stdlib::vec::Vec;
 use crate::stdlib::{collections::BTreeMap, sync::Arc};
 
 use super::{ethereum::EthereumLightClient, starknet::StarkNetLightClient};
+#[cfg(not(feature = "std"))]
+use crate::lightclient::backfill::track_tip;
+#[cfg(feature = "std")]
+use crate::lightclient::{
+    block_cache::BLOCK_CACHE_DIR,
+    sync::{self, SyncContext, SyncHandle},
+};
 use crate::{
     config::Config,
     ethers_helper,
     lightclient::{
-        ethereum::helios_lightclient::HeliosLightClient, starknet::StarkNetLightClientImpl,
+        backfill::{ChainReorg, REORG_CHANNEL_CAPACITY},
+        block_cache::BlockCache,
+        ethereum::helios_lightclient::HeliosLightClient,
+        starknet::{
+            class_hash::calculate_class_hash,
+            commitment::{
+                calculate_block_hash, has_legacy_block_hash, receipt_block_number, receipt_events,
+                transaction_hash, GOERLI_FIRST_0_7_BLOCK, MAINNET_FIRST_0_7_BLOCK,
+            },
+            events::{block_events_matching, is_complete_block_slice},
+            storage_key::{storage_slots, storage_var_address, StorageValue},
+            storage_proof::{ContractData, GetProofOutput, Membership},
+            transaction_hash::{calculate_transaction_hash, may_have_legacy_hash},
+            StarkNetLightClientImpl,
+        },
+        state_history::{fetch_state_updates, StateHistory},
     },
 };
 use ethabi::Uint as U256;
-use ethers::{abi::Abi, types::H160};
+use ethers::{
+    abi::Abi,
+    types::{SyncingStatus, H160},
+};
 use eyre::Result as EyreResult;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use helios::types::{BlockTag, CallOpts};
 #[cfg(feature = "std")]
-use log::{debug, error, info, warn};
+use log::{info, warn};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use starknet::core::types::{
-    BlockHashAndNumber, BlockId, BlockStatus, BlockTag as StarknetBlockTag, BlockWithTxHashes,
-    BlockWithTxs, BroadcastedTransaction, DeclareTransaction, DeployAccountTransaction,
-    DeployTransaction, FeeEstimate, FieldElement, FunctionCall, InvokeTransaction,
-    L1HandlerTransaction, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
-    MaybePendingTransactionReceipt, Transaction,
+    BlockHashAndNumber, BlockId, BlockTag as StarknetBlockTag, BlockWithTxHashes, BlockWithTxs,
+    BroadcastedTransaction, ContractClass, EventFilter, EventsPage, FeeEstimate, FieldElement,
+    FunctionCall, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate,
+    MaybePendingTransactionReceipt, StateDiff, StateUpdate, SyncStatus as StarknetSyncStatus,
+    SyncStatusType, Transaction,
 };
 use starknet::providers::jsonrpc::JsonRpcError;
 
+/// Maximum number of storage keys of a single `pathfinder_getProof` request.
+pub const MAX_STORAGE_KEYS_PER_PROOF: usize = 100;
+/// Maximum number of transaction receipts fetched concurrently to verify a block.
+pub const MAX_CONCURRENT_RECEIPT_REQUESTS: usize = 16;
+/// Maximum number of StarkNet blocks checked to verify a single page of events.
+pub const MAX_EVENTS_BLOCK_RANGE: u64 = 100;
+
+/// Storage variable of the balances of the OpenZeppelin ERC-20 contract.
+pub const ERC20_BALANCES_VAR: &str = "ERC20_balances";
+/// Storage variable of the allowances of the OpenZeppelin ERC-20 contract.
+pub const ERC20_ALLOWANCES_VAR: &str = "ERC20_allowances";
+
+/// Error code of the StarkNet `CONTRACT_NOT_FOUND` error.
+const CONTRACT_NOT_FOUND_CODE: i64 = 20;
+/// Error code of the StarkNet `INVALID_TXN_INDEX` error.
+const INVALID_TXN_INDEX_CODE: i64 = 27;
+
 /// Enum representing the different synchronization status of the light client.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyncStatus {
+    /// The light client is not started.
+    #[default]
     NotSynced,
+    /// The light client is started but has not yet observed a state proven on L1 or the
+    /// StarkNet tip.
     Syncing,
+    /// Ethereum is synced, a state proven on L1 and the StarkNet tip have been fetched.
     Synced,
 }
 
+/// A StarkNet block observed during the synchronization.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncBlock {
+    pub block_number: u64,
+    pub block_hash: FieldElement,
+}
+
+/// Progress of the light client synchronization with Ethereum and StarkNet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub status: SyncStatus,
+    /// `true` if the Ethereum light client reports its consensus sync as complete.
+    pub l1_synced: bool,
+    /// Ethereum block the last proven state was read at.
+    pub l1_block: Option<u64>,
+    /// First StarkNet block proven on L1 observed by the light client.
+    pub starting_block: Option<SyncBlock>,
+    /// Last StarkNet block proven on L1.
+    pub proven_block: Option<SyncBlock>,
+    /// Latest StarkNet block fetched from the full node.
+    pub l2_tip: Option<SyncBlock>,
+    /// Number of StarkNet blocks between the L2 tip and the last proven block.
+    pub l2_lag: Option<u64>,
+    /// Number of consecutive failed synchronization rounds.
+    pub consecutive_failures: u32,
+    /// Error of the last failed synchronization round, cleared by a successful round.
+    pub last_error: Option<String>,
+    /// Number of StarkNet reorganizations rolled back from the block cache.
+    pub reorg_count: u64,
+    /// Last StarkNet reorganization rolled back from the block cache.
+    pub last_reorg: Option<ChainReorg>,
+}
+
+impl SyncState {
+    /// Mark the light client as started. It stays `Syncing` until the sync loop reports
+    /// Ethereum as synced and fetches both a proven state and the StarkNet tip.
+    pub fn start(&mut self) {
+        self.status = SyncStatus::Syncing;
+        self.update_status();
+    }
+
+    /// Mark the light client as stopped, keeping the progress made so far.
+    pub fn stop(&mut self) {
+        self.status = SyncStatus::NotSynced;
+    }
+
+    /// Record the Ethereum light client consensus sync status.
+    pub fn record_l1_synced(&mut self, l1_synced: bool) {
+        self.l1_synced = l1_synced;
+        self.update_status();
+    }
+
+    /// Record a state proven on L1.
+    pub fn record_proven_state(&mut self, proven_state: &ProvenState) {
+        let block = SyncBlock {
+            block_number: proven_state.block_number,
+            block_hash: proven_state.block_hash,
+        };
+        if self.starting_block.is_none() {
+            self.starting_block = Some(block.clone());
+        }
+        self.proven_block = Some(block);
+        self.l1_block = Some(proven_state.l1_block);
+        self.update_status();
+    }
+
+    /// Record the latest StarkNet block fetched from the full node.
+    pub fn record_l2_tip(&mut self, block_number: u64, block_hash: FieldElement) {
+        self.l2_tip = Some(SyncBlock {
+            block_number,
+            block_hash,
+        });
+        self.update_status();
+    }
+
+    /// Record a StarkNet reorganization rolled back from the block cache.
+    pub fn record_reorg(&mut self, reorg: &ChainReorg) {
+        self.reorg_count = self.reorg_count.saturating_add(1);
+        self.last_reorg = Some(reorg.clone());
+    }
+
+    /// Record the outcome of a synchronization round.
+    pub fn record_round(&mut self, result: &EyreResult<()>) {
+        match result {
+            Ok(()) => {
+                self.consecutive_failures = 0;
+                self.last_error = None;
+            }
+            Err(err) => {
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                self.last_error = Some(err.to_string());
+            }
+        }
+    }
+
+    fn update_status(&mut self) {
+        self.l2_lag = match (&self.l2_tip, &self.proven_block) {
+            (Some(tip), Some(proven)) => Some(tip.block_number.saturating_sub(proven.block_number)),
+            _ => None,
+        };
+        if self.status == SyncStatus::NotSynced {
+            return;
+        }
+        self.status = if self.l1_synced && self.proven_block.is_some() && self.l2_tip.is_some() {
+            SyncStatus::Synced
+        } else {
+            SyncStatus::Syncing
+        };
+    }
+}
+
+/// StarkNet state proven on L1, read from the StarkNet core contract at a single Ethereum block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenState {
+    /// Last StarkNet block number proven on L1.
+    pub block_number: u64,
+    /// StarkNet global state root after `block_number`.
+    pub state_root: FieldElement,
+    /// StarkNet block hash of `block_number`.
+    pub block_hash: FieldElement,
+    /// Ethereum block the snapshot was read at.
+    pub l1_block: u64,
+}
+
+/// A block returned by the StarkNet full node along with its verification status.
+#[derive(Clone, Debug)]
+pub struct VerifiedBlock<B> {
+    pub block: B,
+    /// `true` if the block hash matches the one committed on L1, `false` if the block is not
+    /// proven yet and was only checked against its own header, or if it predates StarkNet
+    /// v0.7.0 and was not checked at all.
+    pub verified: bool,
+}
+
+/// A page of events returned by the StarkNet full node along with its verification status.
+#[derive(Clone, Debug)]
+pub struct VerifiedEvents {
+    pub page: EventsPage,
+    /// `true` if every block of the page matches the block hash committed on L1, `false` if
+    /// one of them is not proven yet or if the page is an empty page between two others.
+    pub verified: bool,
+}
+
+/// Storage values of a contract along with the proof they were verified against.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedStorage {
+    pub contract_address: FieldElement,
+    /// StarkNet block the values were read at.
+    pub block_number: u64,
+    /// StarkNet state root committed on L1 for `block_number`.
+    pub state_root: FieldElement,
+    /// Ethereum block the state root was read at.
+    pub l1_block: u64,
+    pub storage_keys: Vec<FieldElement>,
+    pub storage_values: Vec<FieldElement>,
+    pub proof: GetProofOutput,
+    /// Membership of every storage key in the contract storage tree.
+    pub memberships: Vec<Membership>,
+}
+
+/// A state update returned by the StarkNet full node along with its verification status.
+#[derive(Clone, Debug)]
+pub struct VerifiedStateUpdate {
+    pub state_update: MaybePendingStateUpdate,
+    /// `true` if the state diff was checked against proofs at the state root committed on L1,
+    /// `false` if the block is pending or not proven yet.
+    pub verified: bool,
+}
+
+/// A single entry of a state diff, checked against a storage or contract proof.
+#[derive(Clone, Debug)]
+enum StateDiffCheck {
+    Storage {
+        contract_address: FieldElement,
+        key: FieldElement,
+        value: FieldElement,
+    },
+    Nonce {
+        contract_address: FieldElement,
+        nonce: FieldElement,
+    },
+    ClassHash {
+        contract_address: FieldElement,
+        class_hash: FieldElement,
+    },
+    CompiledClassHash {
+        class_hash: FieldElement,
+        compiled_class_hash: FieldElement,
+    },
+}
+
+/// A transaction or transaction receipt returned by the StarkNet full node along with its
+/// inclusion status.
+#[derive(Clone, Debug)]
+pub struct VerifiedTransaction<T> {
+    pub transaction: T,
+    /// `true` if the transaction is included in a block whose hash matches the one committed
+    /// on L1, `false` if it is pending, if its block is not proven yet or if its hash predates
+    /// StarkNet v0.8.0 and cannot be recomputed.
+    pub verified: bool,
+}
+
+/// A transaction located in a block verified against its block hash.
+struct TransactionInclusion {
+    block: BlockWithTxs,
+    receipts: Vec<MaybePendingTransactionReceipt>,
+    index: usize,
+    verified: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct NodeData {
     pub block_number: u64,
     pub state_root: String,
-    pub payload: BTreeMap<u64, BlockWithTxs>,
+    /// StarkNet blocks fetched from the full node, served to block and transaction reads.
+    pub payload: BlockCache,
+    /// Last StarkNet state proven on L1, used to verify reads.
+    pub proven_state: Option<ProvenState>,
+    /// StarkNet states previously proven on L1, used to verify reads at older blocks.
+    pub state_history: StateHistory,
+    /// Progress of the synchronization.
+    pub sync: SyncState,
+    /// Notifies the reorganizations rolled back from the block cache.
+    ///
+    /// Subscribers must run in the same process as the light client. Clients of the RPC server
+    /// poll `beerus_syncStatus` instead, whose `last_reorg` and `reorg_count` track the same
+    /// reorganizations.
+    pub reorg_notifier: broadcast::Sender<ChainReorg>,
 }
 
 impl NodeData {
@@ -59,8 +332,23 @@ impl NodeData {
         NodeData {
             block_number: 0,
             state_root: "".to_string(),
-            payload: BTreeMap::new(),
+            payload: BlockCache::default(),
+            proven_state: None,
+            state_history: StateHistory::new(),
+            sync: SyncState::default(),
+            reorg_notifier: broadcast::channel(REORG_CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl NodeData {
+    /// Replace the block cache, resuming from its most recent block.
+    pub fn set_payload(&mut self, payload: BlockCache) {
+        if let Some(block) = payload.latest() {
+            self.block_number = block.block_number;
+            self.state_root = block.new_root.to_string();
         }
+        self.payload = payload;
     }
 }
 
@@ -78,14 +366,17 @@ pub struct BeerusLightClient {
     pub ethereum_lightclient: Arc<Mutex<Box<dyn EthereumLightClient>>>,
     /// StarkNet light client.
     pub starknet_lightclient: Arc<Box<dyn StarkNetLightClient>>,
-    /// Sync status.
-    pub sync_status: SyncStatus,
     /// StarkNet core ABI.
     pub starknet_core_abi: Abi,
     /// StarkNet core contract address.
     pub starknet_core_contract_address: H160,
     /// Payload data
     pub node: Arc<RwLock<NodeData>>,
+    /// StarkNet chain id, read from the StarkNet full node on first use.
+    chain_id: OnceCell<FieldElement>,
+    /// Handle of the sync loop, set once the light client is started.
+    #[cfg(feature = "std")]
+    sync_handle: Option<SyncHandle>,
 }
 
 impl BeerusLightClient {
@@ -102,6 +393,16 @@ impl BeerusLightClient {
             Box::new(ethereum_lightclient_raw),
             Box::new(starknet_lightclient_raw),
         );
+
+        #[cfg(feature = "std")]
+        {
+            info!("opening block cache...");
+            let payload = BlockCache::open(
+                config.data_dir.join(BLOCK_CACHE_DIR),
+                config.get_block_retention(),
+            )?;
+            beerus.node.write().await.set_payload(payload);
+        }
         Ok(beerus)
     }
 
@@ -122,173 +423,136 @@ impl BeerusLightClient {
         // For now we assume that the ABI is valid and that the deserialization will never fail.
         let starknet_core_abi: Abi = serde_json::from_str(starknet_core_abi).unwrap();
         let starknet_core_contract_address = config.starknet_core_contract_address;
-        let node_raw = NodeData::new();
+        let mut node_raw = NodeData::new();
+        node_raw.payload = BlockCache::new(config.get_block_retention());
         let node = Arc::new(RwLock::new(node_raw));
 
         Self {
             config,
             ethereum_lightclient,
             starknet_lightclient,
-            sync_status: SyncStatus::NotSynced,
             starknet_core_abi,
             starknet_core_contract_address,
             node,
+            chain_id: OnceCell::new(),
+            #[cfg(feature = "std")]
+            sync_handle: None,
         }
     }
 
     /// Start Beerus light client and synchronize with Ethereum and StarkNet.
+    ///
+    /// The synchronization runs in a background task every `poll_interval_secs` until
+    /// [`BeerusLightClient::stop`] is called.
     #[cfg(feature = "std")]
-
     pub async fn start(&mut self) -> EyreResult<()> {
-        if let SyncStatus::NotSynced = self.sync_status {
+        if let SyncStatus::NotSynced = self.sync_status().await {
             // Start the Ethereum light client.
             self.ethereum_lightclient.lock().await.start().await?;
             // Start the StarkNet light client.
             self.starknet_lightclient.start().await?;
-            self.sync_status = SyncStatus::Synced;
-
-            let ethereum_clone = self.ethereum_lightclient.clone();
-            let starknet_clone = self.starknet_lightclient.clone();
-            let node_clone = self.node.clone();
-            let poll_interval_secs = self.config.get_poll_interval();
-
-            // Define function that will loop
-            let task = async move {
-                loop {
-                    let state_root = ethereum_clone
-                        .lock()
-                        .await
-                        .starknet_state_root()
-                        .await
-                        .unwrap();
-
-                    let last_proven_block = ethereum_clone
-                        .lock()
-                        .await
-                        .starknet_last_proven_block()
-                        .await
-                        .unwrap();
-
-                    // TODO: these logs don't get caught by the main thread
-                    info!("State Root: {state_root}");
-                    info!("Block Number: {last_proven_block}");
-
-                    match starknet_clone
-                        .get_block_with_txs(&BlockId::Tag(StarknetBlockTag::Latest))
-                        .await
-                    {
-                        Ok(block) => {
-                            debug!("block: {block:?}");
-                            let mut data = node_clone.write().await;
-                            match block {
-                                MaybePendingBlockWithTxs::Block(block) => {
-                                    // if block.block_number > data.block_number && block.block_number == last_proven_block
-                                    if block.block_number > data.block_number
-                                        && 0 < block.block_number
-                                    {
-                                        data.block_number = block.block_number;
-                                        data.state_root = block.new_root.to_string();
-                                        data.payload.insert(block.block_number, block);
-
-                                        info!("New Block Added to Payload:");
-                                        info!("Block Number {:?}", &data.block_number);
-                                        info!("Block Root {:?}", &data.state_root);
-                                    }
-                                }
-                                MaybePendingBlockWithTxs::PendingBlock(_) => {
-                                    warn!("Pending Block");
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            error!("Error getting block: {}", err);
-                        }
-                    }
-                    thread::sleep(time::Duration::from_secs(poll_interval_secs));
-                }
-            };
-            // Spawn loop function
-            #[cfg(feature = "std")]
-            tokio::spawn(task);
+            self.node.write().await.sync.start();
+
+            self.sync_handle = Some(sync::spawn(SyncContext {
+                ethereum_lightclient: self.ethereum_lightclient.clone(),
+                starknet_lightclient: self.starknet_lightclient.clone(),
+                node: self.node.clone(),
+                starknet_core_abi: self.starknet_core_abi.clone(),
+                starknet_core_contract_address: self.starknet_core_contract_address,
+                poll_interval: Duration::from_secs(self.config.get_poll_interval()),
+                backfill_concurrency: self.config.get_backfill_concurrency(),
+            }));
         };
         Ok(())
     }
 
+    /// Stop the sync loop started by [`BeerusLightClient::start`] and wait for its current
+    /// round to complete.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the loop was stopped or never started, or an `Err(eyre::Report)` if
+    /// the sync task panicked.
+    #[cfg(feature = "std")]
+    pub async fn stop(&mut self) -> EyreResult<()> {
+        if let Some(sync_handle) = self.sync_handle.take() {
+            sync_handle.stop().await?;
+        }
+        self.node.write().await.sync.stop();
+        Ok(())
+    }
+
+    /// Take the handle of the sync loop, so that it can still be stopped once the light client
+    /// has been moved, e.g. into the RPC server.
+    #[cfg(feature = "std")]
+    pub fn take_sync_handle(&mut self) -> Option<SyncHandle> {
+        self.sync_handle.take()
+    }
+
     #[cfg(not(feature = "std"))]
     pub async fn start(&mut self) -> Result<()> {
-        if let SyncStatus::NotSynced = self.sync_status {
+        if let SyncStatus::NotSynced = self.sync_status().await {
             // Start the Ethereum light client.
             //TODO: Change unwrap
             self.ethereum_lightclient.write().await.start().await?;
             // Start the StarkNet light client.
             //TODO: Change unwrap
             self.starknet_lightclient.start().await?;
-            self.sync_status = SyncStatus::Synced;
+            self.node.write().await.sync.start();
 
             let ethereum_clone = self.ethereum_lightclient.clone();
             let starknet_clone = self.starknet_lightclient.clone();
             let node_clone = self.node.clone();
+            let backfill_concurrency = self.config.get_backfill_concurrency();
 
             Interval::new(12000, move || {
                 let ethereum_clone = ethereum_clone.clone();
                 let starknet_clone = starknet_clone.clone();
                 let node_clone = node_clone.clone();
 
+                // Each tick runs a single round, the interval drives the loop.
                 spawn_local(async move {
-                    loop {
-                        //TODO:Fix starknet_state_root and last_proven_block call. (Helios calls are working fine, but these 2 functions arent)
-                        // let state_root = ethereum_clone
-                        //     .read()
-                        //     .await
-                        //     .starknet_state_root()
-                        //     .await
-                        //     .unwrap();
-                        // let last_proven_block = ethereum_clone
-                        //     .read()
-                        //     .await
-                        //     .starknet_last_proven_block()
-                        //     .await
-                        //     .unwrap();
-
-                        //TODO:Remove this once starknet_state_root and last_proven_block call(This is just to valdiate that Helios Fetch are working fine within the thread)
-                        let block_number = ethereum_clone
-                            .read()
-                            .await
-                            .get_block_number()
-                            .await
-                            .unwrap();
-                        // log::info!("Loop State Root, {state_root}");
-                        // log::info!("Loop Block Number, {last_proven_block}");
-                        log::info!("Ethereum Block Number, {block_number}");
-
-                        match starknet_clone
-                            .get_block_with_txs(&BlockId::Tag(StarknetBlockTag::Latest))
-                            .await
-                        {
-                            Ok(block) => {
-                                let mut data = node_clone.write().await;
-                                match block {
-                                    MaybePendingBlockWithTxs::Block(block) => {
-                                        // TODO: change "0 < block.block_number" to "block.block_number == last_proven_block"
-                                        if block.block_number > data.block_number
-                                            && 0 < block.block_number
-                                        {
-                                            data.block_number = block.block_number;
-                                            data.state_root = block.new_root.to_string();
-                                            data.payload.insert(block.block_number, block);
-                                            log::info!("New Block Added to Payload");
-                                            log::info!("Block Number {:?}", &data.block_number);
-                                            log::info!("Block Root {:?}", &data.state_root);
-                                        }
-                                    }
-                                    MaybePendingBlockWithTxs::PendingBlock(_) => {
-                                        log::info!("Pending Block");
-                                    }
-                                }
-                            }
-                            Err(err) => {
-                                log::info!("Error getting block: {err:?}");
-                            }
+                    //TODO:Fix starknet_state_root and last_proven_block call. (Helios calls are working fine, but these 2 functions arent)
+                    // let state_root = ethereum_clone
+                    //     .read()
+                    //     .await
+                    //     .starknet_state_root()
+                    //     .await
+                    //     .unwrap();
+                    // let last_proven_block = ethereum_clone
+                    //     .read()
+                    //     .await
+                    //     .starknet_last_proven_block()
+                    //     .await
+                    //     .unwrap();
+
+                    //TODO:Remove this once starknet_state_root and last_proven_block call(This is just to valdiate that Helios Fetch are working fine within the thread)
+                    // log::info!("Loop State Root, {state_root}");
+                    // log::info!("Loop Block Number, {last_proven_block}");
+                    match ethereum_clone.read().await.get_block_number().await {
+                        Ok(block_number) => log::info!("Ethereum Block Number, {block_number}"),
+                        Err(err) => log::info!("Error getting Ethereum block number: {err:?}"),
+                    }
+
+                    match starknet_clone
+                        .get_block_with_txs(&BlockId::Tag(StarknetBlockTag::Latest))
+                        .await
+                    {
+                        Ok(MaybePendingBlockWithTxs::Block(block)) => {
+                            node_clone
+                                .write()
+                                .await
+                                .sync
+                                .record_l2_tip(block.block_number, block.block_hash);
+                            // TODO: only track blocks up to the last proven block
+                            track_tip(&**starknet_clone, &node_clone, block, backfill_concurrency)
+                                .await;
+                        }
+                        Ok(MaybePendingBlockWithTxs::PendingBlock(_)) => {
+                            log::info!("Pending Block");
+                        }
+                        Err(err) => {
+                            log::info!("Error getting block: {err:?}");
                         }
                     }
                 });
@@ -299,12 +563,83 @@ impl BeerusLightClient {
     }
 
     /// Return the current synchronization status.
-    pub fn sync_status(&self) -> &SyncStatus {
-        &self.sync_status
+    pub async fn sync_status(&self) -> SyncStatus {
+        self.node.read().await.sync.status.clone()
+    }
+
+    /// Return the synchronization progress, including the lag of the StarkNet tip behind the
+    /// last block proven on L1.
+    pub async fn sync_state(&self) -> SyncState {
+        self.node.read().await.sync.clone()
+    }
+
+    /// Subscribe to the StarkNet reorganizations detected by the sync loop. Every notification
+    /// lists the blocks orphaned by a reorganization and rolled back from the block cache.
+    ///
+    /// A subscriber lagging more than `REORG_CHANNEL_CAPACITY` notifications behind misses the
+    /// oldest ones.
+    pub async fn subscribe_reorgs(&self) -> broadcast::Receiver<ChainReorg> {
+        self.node.read().await.reorg_notifier.subscribe()
+    }
+
+    /// Return the Ethereum light client sync status, recording it in the sync progress.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SyncingStatus)` if the operation was successful, or an `Err(eyre::Report)`
+    /// if the Ethereum light client failed.
+    pub async fn eth_syncing(&self) -> EyreResult<SyncingStatus> {
+        let status = self.ethereum_lightclient.lock().await.syncing().await?;
+        self.node
+            .write()
+            .await
+            .sync
+            .record_l1_synced(matches!(status, SyncingStatus::IsFalse));
+        Ok(status)
+    }
+
+    /// Return the StarkNet sync status of the light client.
+    ///
+    /// While the light client is not synced, its own progress is reported: from the first
+    /// block proven on L1 it observed, to the last proven block, up to the StarkNet tip.
+    /// Once synced, the status of the StarkNet full node is returned, since the light client
+    /// cannot be ahead of it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SyncStatusType)` if the operation was successful, or an `Err(JsonRpcError)`
+    /// if the StarkNet full node failed.
+    pub async fn starknet_syncing(&self) -> Result<SyncStatusType, JsonRpcError> {
+        let sync = self.sync_state().await;
+        if sync.status == SyncStatus::Synced {
+            return self.starknet_lightclient.syncing().await;
+        }
+
+        let block_or_zero = |block: Option<&SyncBlock>| {
+            block.map_or((FieldElement::ZERO, 0), |block| {
+                (block.block_hash, block.block_number)
+            })
+        };
+        let (starting_block_hash, starting_block_num) = block_or_zero(sync.starting_block.as_ref());
+        let (current_block_hash, current_block_num) = block_or_zero(sync.proven_block.as_ref());
+        let (highest_block_hash, highest_block_num) =
+            block_or_zero(sync.l2_tip.as_ref().or(sync.proven_block.as_ref()));
+
+        Ok(SyncStatusType::Syncing(StarknetSyncStatus {
+            starting_block_hash,
+            starting_block_num,
+            current_block_hash,
+            current_block_num,
+            highest_block_hash,
+            highest_block_num,
+        }))
     }
 
     /// Get the storage at a given address/key.
     ///
+    /// The value returned by the StarkNet RPC is checked against a `pathfinder_getProof`
+    /// storage proof anchored to the StarkNet state root committed on L1.
+    ///
     /// # Arguments
     ///
     /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
@@ -317,167 +652,873 @@ impl BeerusLightClient {
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
+    /// This method can return a `JsonRpcError` in case of failure, or if the proof
+    /// returned by the StarkNet RPC does not match the value against the L1 state root.
+    /// A `CONTRACT_NOT_FOUND` error is only returned if the contract proof shows that the
+    /// contract is not deployed.
     pub async fn starknet_get_storage_at(
         &self,
         contract_address: FieldElement,
         storage_key: FieldElement,
         block_id: &BlockId,
     ) -> Result<FieldElement, JsonRpcError> {
-        let last_proven_block = self
-            .ethereum_lightclient
-            .lock()
-            .await
-            .starknet_last_proven_block()
+        let proven_state = self.proven_state_for_block(block_id).await?;
+        self.verified_storage_at(&proven_state, contract_address, storage_key)
             .await
-            .map_err(|e| rpc_unknown_error(e.to_string()))?
-            .as_u64();
+    }
 
-        if let BlockId::Number(block_number) = block_id {
-            if block_number <= &last_proven_block {
-                return self
-                    .starknet_lightclient
-                    .get_storage_at(contract_address, storage_key, block_id)
-                    .await;
-            }
-        } else if let MaybePendingBlockWithTxHashes::Block(block) = self
+    /// Fetch a storage value and verify its storage proof against the given proven state.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the storage proof is invalid.
+    async fn verified_storage_at(
+        &self,
+        proven_state: &ProvenState,
+        contract_address: FieldElement,
+        storage_key: FieldElement,
+    ) -> Result<FieldElement, JsonRpcError> {
+        let storage_value = self
+            .storage_value_at(proven_state, contract_address, storage_key)
+            .await?;
+
+        self.verify_storage_values_at(
+            proven_state,
+            contract_address,
+            &[storage_key],
+            &[storage_value],
+        )
+        .await?;
+        Ok(storage_value)
+    }
+
+    /// Fetch a storage value from the StarkNet full node at the block of the given proven state.
+    ///
+    /// If the full node reports the contract as not found, its `CONTRACT_NOT_FOUND` error is
+    /// only returned once the contract proof shows that the contract is not deployed.
+    async fn storage_value_at(
+        &self,
+        proven_state: &ProvenState,
+        contract_address: FieldElement,
+        storage_key: FieldElement,
+    ) -> Result<FieldElement, JsonRpcError> {
+        match self
             .starknet_lightclient
-            .get_block_with_tx_hashes(block_id)
-            .await?
+            .get_storage_at(
+                contract_address,
+                storage_key,
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await
         {
-            if block.block_number <= last_proven_block {
-                return self
-                    .starknet_lightclient
-                    .get_storage_at(contract_address, storage_key, block_id)
-                    .await;
+            Err(err) if err.code == CONTRACT_NOT_FOUND_CODE => {
+                self.verify_contract_not_found(proven_state, contract_address)
+                    .await?;
+                Err(err)
             }
+            result => result,
         }
-        Err(rpc_unknown_error("BlockId is not proven yet".to_string()))
     }
 
-    /// Call a view function of a StarkNet contract.
-    ///
-    /// # Arguments
-    ///
-    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
-    /// * `entry_point_selector` - The entry point selector as a `FieldElement`.
-    /// * `calldata` - The calldata as a vector of `FieldElement`.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` containing the result of the function call as a vector of `FieldElement`
-    /// if the operation was successful, or an `Err` containing a `JsonRpcError` if the operation failed.
+    /// Fetch a contract proof and verify that the contract is not deployed in the given proven
+    /// state.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_call_contract(
+    /// This method returns a `JsonRpcError` if the StarkNet light client fails, if the contract
+    /// proof is invalid or if it shows that the contract is deployed.
+    async fn verify_contract_not_found(
         &self,
+        proven_state: &ProvenState,
         contract_address: FieldElement,
-        entry_point_selector: FieldElement,
-        calldata: Vec<FieldElement>,
-    ) -> Result<Vec<FieldElement>, JsonRpcError> {
-        let opts = FunctionCall {
-            contract_address,
-            entry_point_selector,
-            calldata,
-        };
+    ) -> Result<(), JsonRpcError> {
+        let proof = self
+            .starknet_lightclient
+            .get_contract_storage_proof(
+                contract_address,
+                vec![],
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await?;
 
-        let last_block = self
-            .ethereum_lightclient
-            .lock()
-            .await
-            .starknet_last_proven_block()
-            .await
-            .map_err(|e| rpc_unknown_error(e.to_string()))?
-            .as_u64();
+        let report = proof
+            .verify(proven_state.state_root, contract_address, &[], &[])
+            .map_err(|e| {
+                rpc_proof_verification_failed(format!(
+                    "Invalid proof for contract 0x{contract_address:x}: {e}"
+                ))
+            })?;
+        if report.contract == Membership::Member {
+            return Err(rpc_proof_verification_failed(format!(
+                "Contract 0x{contract_address:x} was reported as not found but is deployed"
+            )));
+        }
+        Ok(())
+    }
 
-        self.starknet_lightclient
-            .call(opts, &BlockId::Number(last_block))
-            .await
+    /// Fetch a single storage proof for several keys of a contract and verify the given values
+    /// against it.
+    pub(crate) async fn verify_storage_values_at(
+        &self,
+        proven_state: &ProvenState,
+        contract_address: FieldElement,
+        storage_keys: &[FieldElement],
+        storage_values: &[FieldElement],
+    ) -> Result<(), JsonRpcError> {
+        let proof = self
+            .starknet_lightclient
+            .get_contract_storage_proof(
+                contract_address,
+                storage_keys.to_vec(),
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await?;
+
+        verify_storage_proof(
+            &proof,
+            proven_state,
+            contract_address,
+            storage_keys,
+            storage_values,
+        )?;
+        Ok(())
     }
 
-    /// Estimate the fee for a given StarkNet transaction.
+    /// Get the value of a Cairo storage variable, verified against a storage proof.
+    ///
+    /// The storage address is derived from the variable name and its key arguments, see
+    /// `storage_var_address`. Values spanning several slots, such as `Uint256`, are read with a
+    /// single proof.
     ///
     /// # Arguments
     ///
-    /// * `request` - The broadcasted transaction as a `BroadcastedTransaction`.
-    /// * `block_id` - The block identifier indicating the block for fee estimation.
+    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
+    /// * `name` - The name of the storage variable.
+    /// * `keys` - The key arguments of the storage variable, empty for a plain variable.
+    /// * `block_id` - The block identifier.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the fee estimate as a `Vec<FeeEstimate>` if the operation was successful,
+    /// Returns a `Result` containing the decoded value if the operation was successful,
     /// or an `Err` containing a `JsonRpcError` if the operation failed.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_estimate_fee(
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails, if the
+    /// storage proof is invalid or if the storage slots do not hold a valid `T`.
+    pub async fn get_storage_var<T: StorageValue>(
         &self,
-        request: BroadcastedTransaction,
+        contract_address: FieldElement,
+        name: &str,
+        keys: &[FieldElement],
         block_id: &BlockId,
-    ) -> Result<FeeEstimate, JsonRpcError> {
-        self.starknet_lightclient
-            .estimate_fee_single(request, block_id)
-            .await
+    ) -> Result<T, JsonRpcError> {
+        let address = storage_var_address(name, keys);
+        let values = self
+            .get_storage_many(contract_address, storage_slots::<T>(address), block_id)
+            .await?;
+        T::from_felts(&values).map_err(|e| rpc_unknown_error(e.to_string()))
     }
 
-    /// Get the nonce at a given address.
+    /// Get the ERC-20 balance of an account, verified against a storage proof.
+    ///
+    /// The balance is read from the `ERC20_balances` storage variable of the OpenZeppelin
+    /// ERC-20 layout, as a `Uint256` stored in two consecutive slots.
     ///
     /// # Arguments
     ///
-    /// * `address` - The StarkNet contract address as a `FieldElement`.
-    /// * `block_id` - The block identifier indicating the block to retrieve the nonce from.
+    /// * `token` - The ERC-20 contract address as a `FieldElement`.
+    /// * `owner` - The account address as a `FieldElement`.
+    /// * `block_id` - The block identifier.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the nonce as a `FieldElement` if the operation was successful,
+    /// Returns a `Result` containing the balance as a `U256` if the operation was successful,
     /// or an `Err` containing a `JsonRpcError` if the operation failed.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_get_nonce(
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or if
+    /// the storage proof is invalid.
+    pub async fn erc20_balance_of(
         &self,
-        address: FieldElement,
+        token: FieldElement,
+        owner: FieldElement,
         block_id: &BlockId,
-    ) -> Result<FieldElement, JsonRpcError> {
-        self.starknet_lightclient.get_nonce(block_id, address).await
+    ) -> Result<U256, JsonRpcError> {
+        self.get_storage_var(token, ERC20_BALANCES_VAR, &[owner], block_id)
+            .await
     }
 
-    /// Get the timestamp at the time `cancelL1ToL2Message` was called with a message matching `msg_hash`,
-    /// or 0 if `cancelL1ToL2Message` was never called.
+    /// Get the ERC-20 allowance of a spender over an account, verified against a storage proof.
+    ///
+    /// The allowance is read from the `ERC20_allowances` storage variable of the OpenZeppelin
+    /// ERC-20 layout, as a `Uint256` stored in two consecutive slots.
     ///
     /// # Arguments
     ///
-    /// * `msg_hash` - The message hash as a `U256`.
+    /// * `token` - The ERC-20 contract address as a `FieldElement`.
+    /// * `owner` - The account address as a `FieldElement`.
+    /// * `spender` - The spender address as a `FieldElement`.
+    /// * `block_id` - The block identifier.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the timestamp as a `U256` if the operation was successful and there is a matching message hash,
-    /// or `Ok(U256::zero())` if the operation was successful but there is no matching message hash,
+    /// Returns a `Result` containing the allowance as a `U256` if the operation was successful,
     /// or an `Err` containing a `JsonRpcError` if the operation failed.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_l1_to_l2_message_cancellations(
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or if
+    /// the storage proof is invalid.
+    pub async fn allowance(
         &self,
-        msg_hash: U256,
+        token: FieldElement,
+        owner: FieldElement,
+        spender: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<U256, JsonRpcError> {
+        self.get_storage_var(token, ERC20_ALLOWANCES_VAR, &[owner, spender], block_id)
+            .await
+    }
+
+    /// Get several storage values of a contract, verified against a single storage proof.
+    ///
+    /// One `pathfinder_getProof` request is made for all the keys instead of one per key.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
+    /// * `storage_keys` - The storage keys as `FieldElement`s, at most
+    ///   `MAX_STORAGE_KEYS_PER_PROOF`.
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the storage values, in the order of `storage_keys`, if the
+    /// operation was successful, or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if too many keys are requested, if one of the
+    /// underlying light clients fails, if the block is not proven or if the storage proof is
+    /// invalid.
+    pub async fn get_storage_many(
+        &self,
+        contract_address: FieldElement,
+        storage_keys: Vec<FieldElement>,
+        block_id: &BlockId,
+    ) -> Result<Vec<FieldElement>, JsonRpcError> {
+        Ok(self
+            .get_verified_storage(contract_address, storage_keys, block_id)
+            .await?
+            .storage_values)
+    }
+
+    /// Get storage values of a contract along with the storage proof they were verified against.
+    ///
+    /// The returned `VerifiedStorage` holds everything needed to re-verify the values
+    /// independently: the proof, the L1 state root and L1 block it is anchored to, and the
+    /// membership of every key. Values are read from the leaves of the proof, so a single
+    /// request is made to the full node.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
+    /// * `storage_keys` - The storage keys as `FieldElement`s.
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedStorage` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if more than `MAX_STORAGE_KEYS_PER_PROOF` keys are
+    /// requested, if one of the underlying light clients fails, if the block is not proven or if
+    /// the storage proof is invalid.
+    pub async fn get_verified_storage(
+        &self,
+        contract_address: FieldElement,
+        storage_keys: Vec<FieldElement>,
+        block_id: &BlockId,
+    ) -> Result<VerifiedStorage, JsonRpcError> {
+        if storage_keys.len() > MAX_STORAGE_KEYS_PER_PROOF {
+            return Err(rpc_proof_limit_exceeded(storage_keys.len()));
+        }
+        let proven_state = self.proven_state_for_block(block_id).await?;
+
+        let proof = self
+            .starknet_lightclient
+            .get_contract_storage_proof(
+                contract_address,
+                storage_keys.clone(),
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await?;
+        let storage_values = proof.storage_values(&storage_keys);
+        let memberships = verify_storage_proof(
+            &proof,
+            &proven_state,
+            contract_address,
+            &storage_keys,
+            &storage_values,
+        )?;
+
+        Ok(VerifiedStorage {
+            contract_address,
+            block_number: proven_state.block_number,
+            state_root: proven_state.state_root,
+            l1_block: proven_state.l1_block,
+            storage_keys,
+            storage_values,
+            proof,
+            memberships,
+        })
+    }
+
+    /// Return the last StarkNet state proven on L1.
+    ///
+    /// The snapshot maintained by the sync loop is used when available, otherwise it is
+    /// fetched from the StarkNet core contract and stored in the node data.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `ProvenState` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn proven_state(&self) -> Result<ProvenState, JsonRpcError> {
+        if let Some(proven_state) = self.node.read().await.proven_state.clone() {
+            return Ok(proven_state);
+        }
+        self.refresh_proven_state().await
+    }
+
+    /// Fetch the last StarkNet state proven on L1 and store it in the node data.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `ProvenState` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn refresh_proven_state(&self) -> Result<ProvenState, JsonRpcError> {
+        let proven_state = {
+            let ethereum_lightclient = self.ethereum_lightclient.lock().await;
+            fetch_proven_state(
+                &**ethereum_lightclient,
+                self.starknet_core_contract_address,
+                &self.starknet_core_abi,
+            )
+            .await
+            .map_err(|e| rpc_unknown_error(e.to_string()))?
+        };
+
+        let mut data = self.node.write().await;
+        data.state_history.insert(proven_state.clone());
+        data.proven_state = Some(proven_state.clone());
+        Ok(proven_state)
+    }
+
+    /// Index the `LogStateUpdate` events emitted by the StarkNet core contract up to the
+    /// L1 block of the last proven state, so that older proven blocks can be verified.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the operation was successful, or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn sync_state_history(&self) -> Result<(), JsonRpcError> {
+        let proven_state = self.proven_state().await?;
+        index_state_history(
+            &self.ethereum_lightclient,
+            &self.node,
+            self.starknet_core_contract_address,
+            &self.starknet_core_abi,
+            proven_state.l1_block,
+        )
+        .await
+        .map_err(|e| rpc_unknown_error(e.to_string()))
+    }
+
+    /// Return the proven state a read at `block_id` can be verified against.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if the block is not proven yet, if no L1 state
+    /// root is known for it or if one of the underlying light clients fails.
+    async fn proven_state_for_block(
+        &self,
+        block_id: &BlockId,
+    ) -> Result<ProvenState, JsonRpcError> {
+        let proven_state = self.proven_state().await?;
+
+        let block_number = match block_id {
+            BlockId::Number(block_number) => *block_number,
+            _ => match self
+                .starknet_lightclient
+                .get_block_with_tx_hashes(block_id)
+                .await?
+            {
+                MaybePendingBlockWithTxHashes::Block(block) => block.block_number,
+                MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+                    return Err(rpc_unknown_error("BlockId is not proven yet".to_string()))
+                }
+            },
+        };
+
+        if block_number > proven_state.block_number {
+            return Err(rpc_unknown_error("BlockId is not proven yet".to_string()));
+        }
+        if block_number < proven_state.block_number {
+            return self
+                .node
+                .read()
+                .await
+                .state_history
+                .get(block_number)
+                .cloned()
+                .ok_or_else(|| {
+                    rpc_unknown_error(format!("No L1 state root known for block {block_number}"))
+                });
+        }
+        Ok(proven_state)
+    }
+
+    /// Call a view function of a StarkNet contract.
+    ///
+    /// The call is executed by the full node at the last proven block, its result is not
+    /// verified. Verifying it would require executing the entry point locally in a Cairo VM.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
+    /// * `entry_point_selector` - The entry point selector as a `FieldElement`.
+    /// * `calldata` - The calldata as a vector of `FieldElement`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the result of the function call as a vector of `FieldElement`
+    /// if the operation was successful, or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_call_contract(
+        &self,
+        contract_address: FieldElement,
+        entry_point_selector: FieldElement,
+        calldata: Vec<FieldElement>,
+    ) -> Result<Vec<FieldElement>, JsonRpcError> {
+        let opts = FunctionCall {
+            contract_address,
+            entry_point_selector,
+            calldata,
+        };
+
+        let proven_state = self.proven_state().await?;
+
+        self.starknet_lightclient
+            .call(opts, &BlockId::Number(proven_state.block_number))
+            .await
+    }
+
+    /// Estimate the fee for a given StarkNet transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The broadcasted transaction as a `BroadcastedTransaction`.
+    /// * `block_id` - The block identifier indicating the block for fee estimation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the fee estimate as a `Vec<FeeEstimate>` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_estimate_fee(
+        &self,
+        request: BroadcastedTransaction,
+        block_id: &BlockId,
+    ) -> Result<FeeEstimate, JsonRpcError> {
+        self.starknet_lightclient
+            .estimate_fee_single(request, block_id)
+            .await
+    }
+
+    /// Get the nonce at a given address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The StarkNet contract address as a `FieldElement`.
+    /// * `block_id` - The block identifier indicating the block to retrieve the nonce from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the nonce as a `FieldElement` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_get_nonce(
+        &self,
+        address: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<FieldElement, JsonRpcError> {
+        self.starknet_lightclient.get_nonce(block_id, address).await
+    }
+
+    /// Get the nonce at a given address, verified against a contract proof.
+    ///
+    /// The nonce is read from the contract data bound to the contract state hash,
+    /// which is itself proven against the StarkNet state root committed on L1.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The StarkNet contract address as a `FieldElement`.
+    /// * `block_id` - The block identifier indicating the block to retrieve the nonce from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the nonce as a `FieldElement` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure, or if the contract proof
+    /// cannot be verified.
+    pub async fn starknet_get_verified_nonce(
+        &self,
+        address: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<FieldElement, JsonRpcError> {
+        let contract_data = self.verified_contract_data(address, block_id).await?;
+        Ok(contract_data.nonce)
+    }
+
+    /// Get the class hash of the contract deployed at a given address, verified against a
+    /// contract proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The StarkNet contract address as a `FieldElement`.
+    /// * `block_id` - The block identifier indicating the block to retrieve the class hash from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the class hash as a `FieldElement` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure, or if the contract proof
+    /// cannot be verified.
+    pub async fn starknet_get_verified_class_hash_at(
+        &self,
+        address: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<FieldElement, JsonRpcError> {
+        let contract_data = self.verified_contract_data(address, block_id).await?;
+        Ok(contract_data.class_hash)
+    }
+
+    /// Get a contract class by its hash, verified to hash to the requested class hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    /// * `class_hash` - The class hash as a `FieldElement`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `ContractClass` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if the StarkNet light client fails or if the class
+    /// does not hash to `class_hash`.
+    pub async fn get_verified_class(
+        &self,
+        block_id: &BlockId,
+        class_hash: FieldElement,
+    ) -> Result<ContractClass, JsonRpcError> {
+        let class = self
+            .starknet_lightclient
+            .get_class(block_id, class_hash)
+            .await?;
+        verify_class_hash(&class, class_hash)?;
+        Ok(class)
+    }
+
+    /// Get the contract class of a contract, verified to hash to the class hash proven by the
+    /// contract proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    /// * `contract_address` - The StarkNet contract address as a `FieldElement`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `ContractClass` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails, if the
+    /// contract proof cannot be verified or if the class does not hash to the proven class hash.
+    pub async fn get_verified_class_at(
+        &self,
+        block_id: &BlockId,
+        contract_address: FieldElement,
+    ) -> Result<ContractClass, JsonRpcError> {
+        let class_hash = self
+            .verified_contract_data(contract_address, block_id)
+            .await?
+            .class_hash;
+        let class = self
+            .starknet_lightclient
+            .get_class_at(block_id, contract_address)
+            .await?;
+        verify_class_hash(&class, class_hash)?;
+        Ok(class)
+    }
+
+    /// Fetch the contract data of a contract and verify its contract proof against the
+    /// StarkNet state root proven on L1.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if the block is not proven yet, if one of the
+    /// underlying light clients fails or if the contract proof is invalid.
+    async fn verified_contract_data(
+        &self,
+        contract_address: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<ContractData, JsonRpcError> {
+        let proven_state = self.proven_state_for_block(block_id).await?;
+        self.verified_contract_data_at(&proven_state, contract_address)
+            .await
+    }
+
+    /// Fetch the contract data of a contract and verify its contract proof against the given
+    /// proven state.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the contract proof is invalid, and a `CONTRACT_NOT_FOUND` error if the contract proof
+    /// shows that the contract is not deployed.
+    async fn verified_contract_data_at(
+        &self,
+        proven_state: &ProvenState,
+        contract_address: FieldElement,
+    ) -> Result<ContractData, JsonRpcError> {
+        let proof = self
+            .starknet_lightclient
+            .get_contract_storage_proof(
+                contract_address,
+                vec![],
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await?;
+
+        let report = proof
+            .verify(proven_state.state_root, contract_address, &[], &[])
+            .map_err(|e| {
+                rpc_proof_verification_failed(format!(
+                    "Invalid proof for contract 0x{contract_address:x}: {e}"
+                ))
+            })?;
+        if report.contract == Membership::NonMember {
+            return Err(rpc_contract_not_found(contract_address));
+        }
+
+        // `verify` only succeeds if the contract data is present and bound to the proof.
+        proof.contract_data.ok_or_else(|| {
+            rpc_proof_verification_failed(format!(
+                "Missing contract data for contract 0x{contract_address:x}"
+            ))
+        })
+    }
+
+    /// Verify that a class is declared with the given compiled class hash, against a class
+    /// proof anchored to the StarkNet state root proven on L1.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    /// * `class_hash` - The class hash as a `FieldElement`.
+    /// * `compiled_class_hash` - The expected compiled class hash as a `FieldElement`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if the block is not proven yet, if one of the
+    /// underlying light clients fails, if a proof is invalid or if the class is not declared
+    /// with `compiled_class_hash`.
+    pub async fn verify_compiled_class_hash(
+        &self,
+        block_id: &BlockId,
+        class_hash: FieldElement,
+        compiled_class_hash: FieldElement,
+    ) -> Result<(), JsonRpcError> {
+        let proven_state = self.proven_state_for_block(block_id).await?;
+        let classes_tree_root = self.verified_classes_tree_root(&proven_state).await?;
+        self.verify_compiled_class_hash_at(
+            &proven_state,
+            classes_tree_root,
+            class_hash,
+            compiled_class_hash,
+        )
+        .await
+    }
+
+    /// Return the class tree root committed to by the given proven state.
+    ///
+    /// The class tree root is returned along any contract proof, and is bound to the state root
+    /// by the global state commitment.
+    async fn verified_classes_tree_root(
+        &self,
+        proven_state: &ProvenState,
+    ) -> Result<FieldElement, JsonRpcError> {
+        let proof = self
+            .starknet_lightclient
+            .get_contract_storage_proof(
+                FieldElement::ZERO,
+                vec![],
+                &BlockId::Number(proven_state.block_number),
+            )
+            .await?;
+
+        proof
+            .verify_global_root(proven_state.state_root)
+            .map_err(|e| rpc_proof_verification_failed(format!("Invalid global root: {e}")))?;
+        Ok(proof.classes_tree_root.unwrap_or(FieldElement::ZERO))
+    }
+
+    /// Fetch the class proof of a class and verify that it is declared with the given compiled
+    /// class hash in the given class tree.
+    async fn verify_compiled_class_hash_at(
+        &self,
+        proven_state: &ProvenState,
+        classes_tree_root: FieldElement,
+        class_hash: FieldElement,
+        compiled_class_hash: FieldElement,
+    ) -> Result<(), JsonRpcError> {
+        let proof = self
+            .starknet_lightclient
+            .get_class_proof(class_hash, &BlockId::Number(proven_state.block_number))
+            .await?;
+
+        match proof.verify(classes_tree_root, class_hash, compiled_class_hash) {
+            Ok(Membership::Member) => Ok(()),
+            Ok(Membership::NonMember) => Err(rpc_proof_verification_failed(format!(
+                "Class 0x{class_hash:x} is not declared"
+            ))),
+            Err(e) => Err(rpc_proof_verification_failed(format!(
+                "Invalid class proof for class 0x{class_hash:x}: {e}"
+            ))),
+        }
+    }
+
+    /// Get the timestamp at the time `cancelL1ToL2Message` was called with a message matching `msg_hash`,
+    /// or 0 if `cancelL1ToL2Message` was never called.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg_hash` - The message hash as a `U256`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the timestamp as a `U256` if the operation was successful and there is a matching message hash,
+    /// or `Ok(U256::zero())` if the operation was successful but there is no matching message hash,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_l1_to_l2_message_cancellations(
+        &self,
+        msg_hash: U256,
     ) -> Result<U256, JsonRpcError> {
         // Convert the message hash to bytes32.
         let msg_hash_bytes32 = ethers_helper::u256_to_bytes32_type(msg_hash);
-        // Encode the function data.
+        // Encode the function data.
+        let data = ethers_helper::encode_function_data(
+            msg_hash_bytes32,
+            self.starknet_core_abi.clone(),
+            "l1ToL2MessageCancellations",
+        )
+        .map_err(|e| rpc_unknown_error(e.to_string()))?;
+
+        let data = data.to_vec();
+
+        // Build the call options.
+        let call_opts = CallOpts {
+            from: None,
+            to: Some(self.starknet_core_contract_address),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(data),
+        };
+
+        // Call the StarkNet core contract.
+        let call_response = self
+            .ethereum_lightclient
+            .lock()
+            .await
+            .call(&call_opts, BlockTag::Latest)
+            .await
+            .map_err(|e| rpc_unknown_error(e.to_string()))?;
+
+        Ok(U256::from_big_endian(&call_response))
+    }
+
+    /// Get the `msg_fee + 1` from the `L1ToL2Message` hash', or 0 if there is no matching `msg_hash`.
+    /// The function returns 0 if `L1ToL2Message` was never called.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg_hash` - The message hash as a `U256`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `msg_fee + 1` as a `U256` if the operation was successful and there is a matching message hash,
+    /// or `Ok(U256::zero())` if the operation was successful but there is no matching message hash,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_l1_to_l2_messages(&self, msg_hash: U256) -> Result<U256, JsonRpcError> {
+        let msg_hash_bytes32 = ethers_helper::u256_to_bytes32_type(msg_hash);
         let data = ethers_helper::encode_function_data(
             msg_hash_bytes32,
             self.starknet_core_abi.clone(),
-            "l1ToL2MessageCancellations",
+            "l1ToL2Messages",
         )
         .map_err(|e| rpc_unknown_error(e.to_string()))?;
 
         let data = data.to_vec();
 
-        // Build the call options.
         let call_opts = CallOpts {
             from: None,
             to: Some(self.starknet_core_contract_address),
@@ -487,7 +1528,6 @@ impl BeerusLightClient {
             data: Some(data),
         };
 
-        // Call the StarkNet core contract.
         let call_response = self
             .ethereum_lightclient
             .lock()
@@ -499,8 +1539,8 @@ impl BeerusLightClient {
         Ok(U256::from_big_endian(&call_response))
     }
 
-    /// Get the `msg_fee + 1` from the `L1ToL2Message` hash', or 0 if there is no matching `msg_hash`.
-    /// The function returns 0 if `L1ToL2Message` was never called.
+    /// Get the msg_fee + 1 for the message with the given `msg_hash`, or 0 if no message with such a hash is pending.
+    /// The function returns 0 if `L2ToL1Message` was never called.
     ///
     /// # Arguments
     ///
@@ -515,12 +1555,12 @@ impl BeerusLightClient {
     /// # Errors
     ///
     /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_l1_to_l2_messages(&self, msg_hash: U256) -> Result<U256, JsonRpcError> {
+    pub async fn starknet_l2_to_l1_messages(&self, msg_hash: U256) -> Result<U256, JsonRpcError> {
         let msg_hash_bytes32 = ethers_helper::u256_to_bytes32_type(msg_hash);
         let data = ethers_helper::encode_function_data(
             msg_hash_bytes32,
             self.starknet_core_abi.clone(),
-            "l1ToL2Messages",
+            "l2ToL1Messages",
         )
         .map_err(|e| rpc_unknown_error(e.to_string()))?;
 
@@ -535,6 +1575,7 @@ impl BeerusLightClient {
             data: Some(data),
         };
 
+        // Call the StarkNet core contract.
         let call_response = self
             .ethereum_lightclient
             .lock()
@@ -546,95 +1587,679 @@ impl BeerusLightClient {
         Ok(U256::from_big_endian(&call_response))
     }
 
-    /// Get the msg_fee + 1 for the message with the given `msg_hash`, or 0 if no message with such a hash is pending.
-    /// The function returns 0 if `L2ToL1Message` was never called.
+    /// Get the nonce for the L1-to-L2 message in the StarkNet Core contract.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the nonce as a `U256` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn starknet_l1_to_l2_message_nonce(&self) -> Result<U256, JsonRpcError> {
+        let data = ethers_helper::encode_function_data(
+            (),
+            self.starknet_core_abi.clone(),
+            "l1ToL2MessageNonce",
+        )
+        .map_err(|e| rpc_unknown_error(e.to_string()))?;
+
+        let data = data.to_vec();
+
+        let call_opts = CallOpts {
+            from: None,
+            to: Some(self.starknet_core_contract_address),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(data),
+        };
+
+        let call_response = self
+            .ethereum_lightclient
+            .lock()
+            .await
+            .call(&call_opts, BlockTag::Latest)
+            .await
+            .map_err(|e| rpc_unknown_error(e.to_string()))?;
+
+        Ok(U256::from_big_endian(&call_response))
+    }
+
+    /// Get the block with transactions for the specified block identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `MaybePendingBlockWithTxs` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn get_block_with_txs(
+        &self,
+        block_id: &BlockId,
+    ) -> Result<MaybePendingBlockWithTxs, JsonRpcError> {
+        if let Some(block) = self.cached_block(block_id).await {
+            return Ok(MaybePendingBlockWithTxs::Block(block));
+        }
+
+        self.starknet_lightclient.get_block_with_txs(block_id).await
+    }
+
+    /// Return a block from the block cache, `None` if it is not cached or pending.
+    ///
+    /// The latest block is the last one fetched by the sync loop.
+    async fn cached_block(&self, block_id: &BlockId) -> Option<BlockWithTxs> {
+        let node = self.node.read().await;
+        let block = match block_id {
+            BlockId::Number(block_number) => node.payload.get(*block_number),
+            BlockId::Hash(block_hash) => node.payload.get_by_hash(block_hash),
+            BlockId::Tag(StarknetBlockTag::Latest) => node.payload.get(node.block_number),
+            BlockId::Tag(StarknetBlockTag::Pending) => None,
+        };
+        block.cloned()
+    }
+
+    /// Get the block hash and number of the current block.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `BlockHashAndNumber` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    pub async fn get_block_hash_and_number(&self) -> Result<BlockHashAndNumber, JsonRpcError> {
+        let node = self.node.read().await;
+
+        match node.payload.get(node.block_number) {
+            Some(block) => Ok(BlockHashAndNumber {
+                block_hash: block.block_hash,
+                block_number: block.block_number,
+            }),
+            _ => Err(JsonRpcError {
+                code: 24,
+                message: "Block not found".to_string(),
+            }),
+        }
+    }
+
+    /// Get the block with transactions for the specified block identifier, verified against
+    /// its block hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedBlock` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the block does not match its block hash. Pending blocks are returned unverified.
+    pub async fn get_verified_block_with_txs(
+        &self,
+        block_id: &BlockId,
+    ) -> Result<VerifiedBlock<MaybePendingBlockWithTxs>, JsonRpcError> {
+        let block = self.get_block_with_txs(block_id).await?;
+        let verified = match &block {
+            MaybePendingBlockWithTxs::Block(block) => self.verify_block(block).await?,
+            MaybePendingBlockWithTxs::PendingBlock(_) => false,
+        };
+        Ok(VerifiedBlock { block, verified })
+    }
+
+    /// Get the block with transaction hashes for a given block identifier, verified against
+    /// its block hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The block identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedBlock` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the block does not match its block hash.
+    pub async fn get_verified_block_with_tx_hashes(
+        &self,
+        block_id: &BlockId,
+    ) -> Result<VerifiedBlock<MaybePendingBlockWithTxHashes>, JsonRpcError> {
+        let block = self.get_block_with_tx_hashes(block_id).await?;
+        let verified = match &block {
+            MaybePendingBlockWithTxHashes::Block(block) => {
+                // Signatures and receipts are needed to recompute the block hash.
+                let block_with_txs = match self
+                    .get_block_with_txs(&BlockId::Number(block.block_number))
+                    .await?
+                {
+                    MaybePendingBlockWithTxs::Block(block_with_txs) => block_with_txs,
+                    MaybePendingBlockWithTxs::PendingBlock(_) => {
+                        return Err(rpc_proof_verification_failed(format!(
+                            "Block {} is pending",
+                            block.block_number
+                        )))
+                    }
+                };
+
+                let tx_hashes: Vec<FieldElement> = block_with_txs
+                    .transactions
+                    .iter()
+                    .map(transaction_hash)
+                    .collect();
+                if block_with_txs.block_hash != block.block_hash || tx_hashes != block.transactions
+                {
+                    return Err(rpc_proof_verification_failed(format!(
+                        "Inconsistent transactions for block {}",
+                        block.block_number
+                    )));
+                }
+
+                self.verify_block(&block_with_txs).await?
+            }
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => false,
+        };
+        Ok(VerifiedBlock { block, verified })
+    }
+
+    /// Get the block hash and number of the current block, verified against its block hash.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedBlock` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the block does not match its block hash.
+    pub async fn get_verified_block_hash_and_number(
+        &self,
+    ) -> Result<VerifiedBlock<BlockHashAndNumber>, JsonRpcError> {
+        let block_hash_and_number = self.get_block_hash_and_number().await?;
+        let verified_block = self
+            .get_verified_block_with_txs(&BlockId::Number(block_hash_and_number.block_number))
+            .await?;
+
+        match verified_block.block {
+            MaybePendingBlockWithTxs::Block(block)
+                if block.block_hash == block_hash_and_number.block_hash =>
+            {
+                Ok(VerifiedBlock {
+                    block: block_hash_and_number,
+                    verified: verified_block.verified,
+                })
+            }
+            _ => Err(rpc_proof_verification_failed(format!(
+                "Inconsistent block hash for block {}",
+                block_hash_and_number.block_number
+            ))),
+        }
+    }
+
+    /// Verify a block by recomputing its block hash from its header, its transactions and the
+    /// events of their receipts.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block to verify.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if the block hash matches the one committed on L1, `Ok(false)` if the
+    /// block is not proven yet and only matches its own header, or if it predates StarkNet
+    /// v0.7.0 and its block hash cannot be recomputed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the recomputed block hash does not match the returned or the L1 one.
+    pub async fn verify_block(&self, block: &BlockWithTxs) -> Result<bool, JsonRpcError> {
+        Ok(self.verify_block_with_receipts(block).await?.0)
+    }
+
+    /// Verify a block like `verify_block` and return the receipts its events were read from,
+    /// in transaction order.
+    ///
+    /// The receipts of a cached block are cached along with it once its block hash is
+    /// recomputed, otherwise they are fetched `MAX_CONCURRENT_RECEIPT_REQUESTS` at a time.
+    async fn verify_block_with_receipts(
+        &self,
+        block: &BlockWithTxs,
+    ) -> Result<(bool, Vec<MaybePendingTransactionReceipt>), JsonRpcError> {
+        let tx_hashes: Vec<FieldElement> =
+            block.transactions.iter().map(transaction_hash).collect();
+        let cached_receipts = self
+            .node
+            .read()
+            .await
+            .payload
+            .receipts(&block.block_hash)
+            .map(<[_]>::to_vec);
+        let cached = cached_receipts.is_some();
+        let receipts: Vec<MaybePendingTransactionReceipt> = match cached_receipts {
+            Some(receipts) => receipts,
+            None => {
+                stream::iter(tx_hashes.iter())
+                    .map(|tx_hash| self.starknet_lightclient.get_transaction_receipt(*tx_hash))
+                    .buffered(MAX_CONCURRENT_RECEIPT_REQUESTS)
+                    .try_collect()
+                    .await?
+            }
+        };
+
+        let mut events = Vec::new();
+        for (tx_hash, receipt) in tx_hashes.iter().zip(receipts.iter()) {
+            let receipt_events = receipt_events(receipt).ok_or_else(|| {
+                rpc_proof_verification_failed(format!(
+                    "Pending receipt for transaction 0x{tx_hash:x} of block {}",
+                    block.block_number
+                ))
+            })?;
+            events.extend_from_slice(receipt_events);
+        }
+
+        let block_hash = calculate_block_hash(block, &events);
+        if block_hash != block.block_hash && self.has_legacy_block_hash(block.block_number).await? {
+            return Ok((false, receipts));
+        }
+        if block_hash != block.block_hash {
+            return Err(rpc_proof_verification_failed(format!(
+                "Block hash mismatch for block {}: computed 0x{block_hash:x}, got 0x{:x}",
+                block.block_number, block.block_hash
+            )));
+        }
+        if !cached {
+            if let Err(err) = self
+                .node
+                .write()
+                .await
+                .payload
+                .insert_receipts(&block_hash, receipts.clone())
+            {
+                warn!(
+                    "Error caching receipts of block {}: {}",
+                    block.block_number, err
+                );
+            }
+        }
+
+        match self.l1_block_hash(block.block_number).await? {
+            Some(l1_block_hash) if l1_block_hash == block_hash => Ok((true, receipts)),
+            Some(l1_block_hash) => Err(rpc_proof_verification_failed(format!(
+                "Block hash mismatch for block {}: committed 0x{l1_block_hash:x} on L1, got 0x{block_hash:x}",
+                block.block_number
+            ))),
+            None => Ok((false, receipts)),
+        }
+    }
+
+    /// Get a page of events, verified to be authentic and complete.
+    ///
+    /// For every block spanned by the page, the block receipts are fetched, the event commitment
+    /// is recomputed and checked against the block hash, and the events of the page are checked
+    /// to be exactly the events of the block matching the filter. A continuation token allows
+    /// the page to start or end in the middle of a block.
+    ///
+    /// The first page, without continuation token, is checked from `filter.from_block` and the
+    /// last page, without continuation token in the response, through `filter.to_block`: the
+    /// blocks of the range without any matching event are checked to be empty. An empty page is
+    /// proven the same way. Blocks between two pages are covered by the pages themselves, the
+    /// continuation token being opaque.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The event filter.
+    /// * `continuation_token` - The continuation token of the previous page, if any.
+    /// * `chunk_size` - The maximum number of events of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedEvents` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails, if a
+    /// block does not match its block hash, if a matching event is missing from the page or if
+    /// the page spans more than `MAX_EVENTS_BLOCK_RANGE` blocks.
+    pub async fn get_verified_events(
+        &self,
+        filter: EventFilter,
+        continuation_token: Option<String>,
+        chunk_size: u64,
+    ) -> Result<VerifiedEvents, JsonRpcError> {
+        let page = self
+            .starknet_lightclient
+            .get_events(filter.clone(), continuation_token.clone(), chunk_size)
+            .await?;
+
+        if page
+            .events
+            .windows(2)
+            .any(|events| events[0].block_number > events[1].block_number)
+        {
+            return Err(rpc_proof_verification_failed(
+                "Events are not sorted by block number".to_string(),
+            ));
+        }
+
+        let first_block = match (&continuation_token, page.events.first()) {
+            (None, _) => match &filter.from_block {
+                Some(block_id) => self.filter_block_number(block_id).await?,
+                None => 0,
+            },
+            (Some(_), Some(first)) => first.block_number,
+            // An empty page in the middle of the results does not tell where it starts.
+            (Some(_), None) => {
+                return Ok(VerifiedEvents {
+                    page,
+                    verified: false,
+                })
+            }
+        };
+        let last_block = match (&page.continuation_token, page.events.last()) {
+            (None, _) => match &filter.to_block {
+                Some(block_id) => self.filter_block_number(block_id).await?,
+                None => self.starknet_lightclient.block_number().await?,
+            },
+            (Some(_), Some(last)) => last.block_number,
+            (Some(_), None) => {
+                return Ok(VerifiedEvents {
+                    page,
+                    verified: false,
+                })
+            }
+        };
+        if page
+            .events
+            .iter()
+            .any(|event| event.block_number < first_block || event.block_number > last_block)
+        {
+            return Err(rpc_proof_verification_failed(format!(
+                "Events outside of blocks {first_block} to {last_block}"
+            )));
+        }
+        if last_block.saturating_sub(first_block) >= MAX_EVENTS_BLOCK_RANGE {
+            return Err(rpc_events_limit_exceeded(first_block, last_block));
+        }
+
+        let mut verified = true;
+        for block_number in first_block..=last_block {
+            let block = match self
+                .get_block_with_txs(&BlockId::Number(block_number))
+                .await?
+            {
+                MaybePendingBlockWithTxs::Block(block) => block,
+                MaybePendingBlockWithTxs::PendingBlock(_) => {
+                    return Err(rpc_proof_verification_failed(format!(
+                        "Block {block_number} is pending"
+                    )))
+                }
+            };
+            let (block_verified, receipts) = self.verify_block_with_receipts(&block).await?;
+
+            let expected = block_events_matching(&block, &receipts, &filter);
+            let start = page
+                .events
+                .partition_point(|event| event.block_number < block_number);
+            let end = page
+                .events
+                .partition_point(|event| event.block_number <= block_number);
+            let starts_block = block_number != first_block || continuation_token.is_none();
+            let ends_block = block_number != last_block || page.continuation_token.is_none();
+            if !is_complete_block_slice(
+                &expected,
+                &page.events[start..end],
+                starts_block,
+                ends_block,
+            ) {
+                return Err(rpc_proof_verification_failed(format!(
+                    "Events of block {block_number} do not match its event commitment"
+                )));
+            }
+
+            verified &= block_verified;
+        }
+
+        Ok(VerifiedEvents { page, verified })
+    }
+
+    /// Return the number of the block an event filter bound refers to, the latest block for
+    /// the pending block.
+    async fn filter_block_number(&self, block_id: &BlockId) -> Result<u64, JsonRpcError> {
+        match block_id {
+            BlockId::Number(block_number) => Ok(*block_number),
+            BlockId::Tag(_) => self.starknet_lightclient.block_number().await,
+            BlockId::Hash(block_hash) => match self.get_block_with_tx_hashes(block_id).await? {
+                MaybePendingBlockWithTxHashes::Block(block) => Ok(block.block_number),
+                MaybePendingBlockWithTxHashes::PendingBlock(_) => Err(
+                    rpc_proof_verification_failed(format!("Block 0x{block_hash:x} is pending")),
+                ),
+            },
+        }
+    }
+
+    /// Get a transaction by its hash, verified to be included in its block.
+    ///
+    /// The transaction hash is recomputed from the transaction fields and the transaction is
+    /// located in its block, itself verified against its block hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_hash` - The transaction hash as a string.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `VerifiedTransaction` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the transaction is not included in its block. Pending transactions are returned
+    /// unverified.
+    pub async fn get_verified_transaction_by_hash(
+        &self,
+        tx_hash: String,
+    ) -> Result<VerifiedTransaction<Transaction>, JsonRpcError> {
+        let hash = FieldElement::from_str(&tx_hash).map_err(|_| invalid_call_data("hash"))?;
+
+        match self.transaction_inclusion(hash).await? {
+            Some(mut inclusion) => Ok(VerifiedTransaction {
+                transaction: inclusion.block.transactions.swap_remove(inclusion.index),
+                verified: inclusion.verified,
+            }),
+            None => Ok(VerifiedTransaction {
+                transaction: self
+                    .starknet_lightclient
+                    .get_transaction_by_hash(hash)
+                    .await?,
+                verified: false,
+            }),
+        }
+    }
+
+    /// Get the receipt of a transaction, verified to be included in its block.
+    ///
+    /// The events of the returned receipt are the ones committed in the block event commitment.
     ///
     /// # Arguments
     ///
-    /// * `msg_hash` - The message hash as a `U256`.
+    /// * `tx_hash` - The transaction hash as a string.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the `msg_fee + 1` as a `U256` if the operation was successful and there is a matching message hash,
-    /// or `Ok(U256::zero())` if the operation was successful but there is no matching message hash,
+    /// Returns a `Result` containing the `VerifiedTransaction` if the operation was successful,
     /// or an `Err` containing a `JsonRpcError` if the operation failed.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_l2_to_l1_messages(&self, msg_hash: U256) -> Result<U256, JsonRpcError> {
-        let msg_hash_bytes32 = ethers_helper::u256_to_bytes32_type(msg_hash);
-        let data = ethers_helper::encode_function_data(
-            msg_hash_bytes32,
-            self.starknet_core_abi.clone(),
-            "l2ToL1Messages",
-        )
-        .map_err(|e| rpc_unknown_error(e.to_string()))?;
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or
+    /// if the transaction is not included in its block. Pending receipts are returned
+    /// unverified.
+    pub async fn get_verified_transaction_receipt(
+        &self,
+        tx_hash: String,
+    ) -> Result<VerifiedTransaction<MaybePendingTransactionReceipt>, JsonRpcError> {
+        let hash = FieldElement::from_str(&tx_hash).map_err(|_| invalid_call_data("hash"))?;
 
-        let data = data.to_vec();
+        match self.transaction_inclusion(hash).await? {
+            Some(mut inclusion) => Ok(VerifiedTransaction {
+                transaction: inclusion.receipts.swap_remove(inclusion.index),
+                verified: inclusion.verified,
+            }),
+            None => Ok(VerifiedTransaction {
+                transaction: self
+                    .starknet_lightclient
+                    .get_transaction_receipt(hash)
+                    .await?,
+                verified: false,
+            }),
+        }
+    }
 
-        let call_opts = CallOpts {
-            from: None,
-            to: Some(self.starknet_core_contract_address),
-            gas: None,
-            gas_price: None,
-            value: None,
-            data: Some(data),
+    /// Locate a transaction in its block and verify the block, `None` if the transaction is
+    /// still pending.
+    async fn transaction_inclusion(
+        &self,
+        tx_hash: FieldElement,
+    ) -> Result<Option<TransactionInclusion>, JsonRpcError> {
+        let receipt = self
+            .starknet_lightclient
+            .get_transaction_receipt(tx_hash)
+            .await?;
+        let block_number = match receipt_block_number(&receipt) {
+            Some(block_number) => block_number,
+            None => return Ok(None),
         };
 
-        // Call the StarkNet core contract.
-        let call_response = self
-            .ethereum_lightclient
-            .lock()
-            .await
-            .call(&call_opts, BlockTag::Latest)
-            .await
-            .map_err(|e| rpc_unknown_error(e.to_string()))?;
+        let block = match self
+            .get_block_with_txs(&BlockId::Number(block_number))
+            .await?
+        {
+            MaybePendingBlockWithTxs::Block(block) => block,
+            MaybePendingBlockWithTxs::PendingBlock(_) => {
+                return Err(rpc_proof_verification_failed(format!(
+                    "Block {block_number} is pending"
+                )))
+            }
+        };
 
-        Ok(U256::from_big_endian(&call_response))
+        let index = block
+            .transactions
+            .iter()
+            .position(|transaction| transaction_hash(transaction) == tx_hash)
+            .ok_or_else(|| {
+                rpc_proof_verification_failed(format!(
+                    "Transaction 0x{tx_hash:x} not found in block {block_number}"
+                ))
+            })?;
+
+        let transaction = &block.transactions[index];
+        let computed_tx_hash = calculate_transaction_hash(transaction, self.chain_id().await?);
+        // Transactions hashed before StarkNet v0.8.0 cannot be checked against their hash.
+        let legacy_hash = computed_tx_hash != tx_hash && may_have_legacy_hash(transaction);
+        if computed_tx_hash != tx_hash && !legacy_hash {
+            return Err(rpc_proof_verification_failed(format!(
+                "Transaction hash mismatch: computed 0x{computed_tx_hash:x}, got 0x{tx_hash:x}"
+            )));
+        }
+
+        let (verified, receipts) = self.verify_block_with_receipts(&block).await?;
+        Ok(Some(TransactionInclusion {
+            block,
+            receipts,
+            index,
+            verified: verified && !legacy_hash,
+        }))
     }
 
-    /// Get the nonce for the L1-to-L2 message in the StarkNet Core contract.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` containing the nonce as a `U256` if the operation was successful,
-    /// or an `Err` containing a `JsonRpcError` if the operation failed.
-    ///
-    /// # Errors
+    /// StarkNet chain id of the full node, fetched once and reused afterwards.
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn starknet_l1_to_l2_message_nonce(&self) -> Result<U256, JsonRpcError> {
-        let data = ethers_helper::encode_function_data(
-            (),
-            self.starknet_core_abi.clone(),
-            "l1ToL2MessageNonce",
-        )
-        .map_err(|e| rpc_unknown_error(e.to_string()))?;
+    /// A full node lying about its chain id cannot get a transaction verified: the
+    /// transaction hashes it returns would not match the ones recomputed here.
+    async fn chain_id(&self) -> Result<FieldElement, JsonRpcError> {
+        self.chain_id
+            .get_or_try_init(|| self.starknet_lightclient.chain_id())
+            .await
+            .copied()
+    }
 
-        let data = data.to_vec();
+    /// Return `true` if the block was hashed with a formula older than StarkNet v0.7.0.
+    ///
+    /// The chain id is only fetched for blocks old enough to be concerned.
+    async fn has_legacy_block_hash(&self, block_number: u64) -> Result<bool, JsonRpcError> {
+        if block_number >= MAINNET_FIRST_0_7_BLOCK.max(GOERLI_FIRST_0_7_BLOCK) {
+            return Ok(false);
+        }
+        Ok(has_legacy_block_hash(self.chain_id().await?, block_number))
+    }
 
-        let call_opts = CallOpts {
-            from: None,
-            to: Some(self.starknet_core_contract_address),
-            gas: None,
-            gas_price: None,
-            value: None,
-            data: Some(data),
-        };
+    /// Return the block hash committed on L1 for a StarkNet block, `None` if it is unknown.
+    async fn l1_block_hash(&self, block_number: u64) -> Result<Option<FieldElement>, JsonRpcError> {
+        Ok(self
+            .l1_state(block_number)
+            .await?
+            .map(|proven_state| proven_state.block_hash))
+    }
 
-        let call_response = self
-            .ethereum_lightclient
-            .lock()
-            .await
-            .call(&call_opts, BlockTag::Latest)
+    /// Return the state committed on L1 for a StarkNet block, `None` if it is unknown.
+    async fn l1_state(&self, block_number: u64) -> Result<Option<ProvenState>, JsonRpcError> {
+        let proven_state = self.proven_state().await?;
+        if proven_state.block_number == block_number {
+            return Ok(Some(proven_state));
+        }
+        Ok(self
+            .node
+            .read()
             .await
-            .map_err(|e| rpc_unknown_error(e.to_string()))?;
+            .state_history
+            .get(block_number)
+            .cloned())
+    }
 
-        Ok(U256::from_big_endian(&call_response))
+    /// Return the state committed on L1 for the StarkNet block with the given block hash,
+    /// `None` if it is unknown.
+    async fn l1_state_for_block_hash(
+        &self,
+        block_hash: FieldElement,
+    ) -> Result<Option<ProvenState>, JsonRpcError> {
+        let proven_state = self.proven_state().await?;
+        if proven_state.block_hash == block_hash {
+            return Ok(Some(proven_state));
+        }
+        Ok(self
+            .node
+            .read()
+            .await
+            .state_history
+            .find_by_block_hash(block_hash)
+            .cloned())
     }
 
-    /// Get the block with transactions for the specified block identifier.
+    /// Get the state update of a block, verified against the state committed on L1.
+    ///
+    /// For a proven block, the new root is checked against the L1 state root, and the old root
+    /// against the L1 state root of the parent block when the parent is in the state history.
+    /// The storage diffs, nonce updates, deployed contracts and replaced classes are checked
+    /// against storage and contract proofs at the new root, at most `MAX_STORAGE_KEYS_PER_PROOF`
+    /// keys per proof. When `Config::state_diff_sample_size` is set, only a random sample of
+    /// these entries is checked.
     ///
     /// # Arguments
     ///
@@ -642,56 +2267,155 @@ impl BeerusLightClient {
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the `MaybePendingBlockWithTxs` if the operation was successful,
+    /// Returns a `Result` containing the `VerifiedStateUpdate` if the operation was successful,
     /// or an `Err` containing a `JsonRpcError` if the operation failed.
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn get_block_with_txs(
+    /// This method returns a `JsonRpcError` if one of the underlying light clients fails or if
+    /// the state update of a proven block is inconsistent with the L1 state. State updates of
+    /// pending or unproven blocks are returned unverified.
+    pub async fn get_verified_state_update(
         &self,
         block_id: &BlockId,
-    ) -> Result<MaybePendingBlockWithTxs, JsonRpcError> {
-        let block_number = match block_id {
-            BlockId::Number(number) => *number,
-            BlockId::Tag(_) => self.starknet_lightclient.block_number().await.unwrap(),
-            BlockId::Hash(_) => self.starknet_lightclient.block_number().await.unwrap(),
+    ) -> Result<VerifiedStateUpdate, JsonRpcError> {
+        let state_update = self.starknet_lightclient.get_state_update(block_id).await?;
+
+        let proven_state = match &state_update {
+            MaybePendingStateUpdate::Update(update) => {
+                self.l1_state_for_block_hash(update.block_hash).await?
+            }
+            MaybePendingStateUpdate::PendingUpdate(_) => None,
+        };
+        let (update, proven_state) = match (&state_update, proven_state) {
+            (MaybePendingStateUpdate::Update(update), Some(proven_state)) => (update, proven_state),
+            _ => {
+                return Ok(VerifiedStateUpdate {
+                    state_update,
+                    verified: false,
+                })
+            }
         };
-        let node_data = self.node.read().await.clone();
 
-        if block_number <= node_data.block_number {
-            let payload_block = node_data.payload.get(&block_number).unwrap();
-            Ok(MaybePendingBlockWithTxs::Block(payload_block.clone()))
-        } else {
-            self.starknet_lightclient.get_block_with_txs(block_id).await
-        }
+        self.verify_state_update(&proven_state, update).await?;
+        Ok(VerifiedStateUpdate {
+            state_update,
+            verified: true,
+        })
     }
 
-    /// Get the block hash and number of the current block.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` containing the `BlockHashAndNumber` if the operation was successful,
-    /// or an `Err` containing a `JsonRpcError` if the operation failed.
-    ///
-    /// # Errors
-    ///
-    /// This method can return a `JsonRpcError` in case of failure.
-    pub async fn get_block_hash_and_number(&self) -> Result<BlockHashAndNumber, JsonRpcError> {
-        let cloned_node = self.node.read().await;
-        let payload = cloned_node.payload.clone();
+    /// Verify a state update against the state committed on L1 for its block.
+    async fn verify_state_update(
+        &self,
+        proven_state: &ProvenState,
+        update: &StateUpdate,
+    ) -> Result<(), JsonRpcError> {
+        if update.new_root != proven_state.state_root {
+            return Err(rpc_proof_verification_failed(format!(
+                "New root mismatch for block {}: committed 0x{:x} on L1, got 0x{:x}",
+                proven_state.block_number, proven_state.state_root, update.new_root
+            )));
+        }
+        let previous_state = self
+            .node
+            .read()
+            .await
+            .state_history
+            .previous(proven_state.block_number)
+            .cloned();
+        // The old root is only committed on L1 when the parent block was itself proven.
+        if let Some(previous_state) =
+            previous_state.filter(|state| state.block_number + 1 == proven_state.block_number)
+        {
+            if update.old_root != previous_state.state_root {
+                return Err(rpc_proof_verification_failed(format!(
+                    "Old root mismatch for block {}: committed 0x{:x} on L1, got 0x{:x}",
+                    proven_state.block_number, previous_state.state_root, update.old_root
+                )));
+            }
+        }
 
-        let block = payload.get(&cloned_node.block_number);
-        match block {
-            Some(block) => Ok(BlockHashAndNumber {
-                block_hash: block.block_hash,
-                block_number: block.block_number,
-            }),
-            _ => Err(JsonRpcError {
-                code: 24,
-                message: "Block not found".to_string(),
-            }),
+        let mut checks = state_diff_checks(&update.state_diff);
+        if let Some(sample_size) = self.config.state_diff_sample_size {
+            if sample_size < checks.len() {
+                checks = checks
+                    .choose_multiple(&mut rand::thread_rng(), sample_size)
+                    .cloned()
+                    .collect();
+            }
+        }
+
+        let mut storage: BTreeMap<FieldElement, (Vec<FieldElement>, Vec<FieldElement>)> =
+            BTreeMap::new();
+        let mut contracts: BTreeMap<FieldElement, Vec<StateDiffCheck>> = BTreeMap::new();
+        let mut classes = Vec::new();
+        for check in checks {
+            match check {
+                StateDiffCheck::Storage {
+                    contract_address,
+                    key,
+                    value,
+                } => {
+                    let (keys, values) = storage.entry(contract_address).or_default();
+                    keys.push(key);
+                    values.push(value);
+                }
+                StateDiffCheck::Nonce {
+                    contract_address, ..
+                }
+                | StateDiffCheck::ClassHash {
+                    contract_address, ..
+                } => contracts.entry(contract_address).or_default().push(check),
+                StateDiffCheck::CompiledClassHash {
+                    class_hash,
+                    compiled_class_hash,
+                } => classes.push((class_hash, compiled_class_hash)),
+            }
+        }
+
+        for (contract_address, (keys, values)) in storage {
+            for (keys, values) in keys
+                .chunks(MAX_STORAGE_KEYS_PER_PROOF)
+                .zip(values.chunks(MAX_STORAGE_KEYS_PER_PROOF))
+            {
+                self.verify_storage_values_at(proven_state, contract_address, keys, values)
+                    .await?;
+            }
+        }
+        for (contract_address, checks) in contracts {
+            let contract_data = self
+                .verified_contract_data_at(proven_state, contract_address)
+                .await?;
+            for check in checks {
+                let consistent = match check {
+                    StateDiffCheck::Nonce { nonce, .. } => contract_data.nonce == nonce,
+                    StateDiffCheck::ClassHash { class_hash, .. } => {
+                        contract_data.class_hash == class_hash
+                    }
+                    StateDiffCheck::Storage { .. } | StateDiffCheck::CompiledClassHash { .. } => {
+                        true
+                    }
+                };
+                if !consistent {
+                    return Err(rpc_proof_verification_failed(format!(
+                        "State diff of contract 0x{contract_address:x} does not match its contract proof"
+                    )));
+                }
+            }
         }
+        if !classes.is_empty() {
+            let classes_tree_root = self.verified_classes_tree_root(proven_state).await?;
+            for (class_hash, compiled_class_hash) in classes {
+                self.verify_compiled_class_hash_at(
+                    proven_state,
+                    classes_tree_root,
+                    class_hash,
+                    compiled_class_hash,
+                )
+                .await?;
+            }
+        }
+        Ok(())
     }
 
     /// Return the transaction receipt of a transaction.
@@ -737,6 +2461,9 @@ impl BeerusLightClient {
 
     /// Get the block with transaction hashes for a given block identifier.
     ///
+    /// The block is served from the block cache when possible, from the StarkNet full node
+    /// otherwise.
+    ///
     /// # Arguments
     ///
     /// * `block_id` - The block identifier.
@@ -747,85 +2474,14 @@ impl BeerusLightClient {
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure. Possible error codes include:
-    ///
-    /// - `24`: Block not found.
+    /// This method can return a `JsonRpcError` in case of failure.
     pub async fn get_block_with_tx_hashes(
         &self,
         block_id: &BlockId,
     ) -> Result<MaybePendingBlockWithTxHashes, JsonRpcError> {
-        let cloned_node = self.node.read().await;
-        let payload = cloned_node.payload.clone();
-
-        let block = match block_id {
-            BlockId::Number(block_number) => payload.get(block_number),
-            BlockId::Hash(block_hash) => {
-                let block = payload
-                    .values()
-                    .find(|block| block.block_hash == *block_hash);
-                match block {
-                    Some(block) => Some(block),
-                    None => {
-                        // TODO: Select a correct error code for "Block with hash {} not found in the payload.", now its BLOCK_NOT_FOUND
-                        return Err(JsonRpcError {
-                            code: 24,
-                            message: format!(
-                                "Block with hash {} not found in the payload.",
-                                block_hash
-                            ),
-                        });
-                    }
-                }
-            }
-            BlockId::Tag(tag) => match tag {
-                StarknetBlockTag::Latest => payload.get(&cloned_node.block_number),
-                StarknetBlockTag::Pending => {
-                    let block = payload
-                        .values()
-                        .find(|block| block.status == BlockStatus::Pending);
-                    match block {
-                        Some(block) => Some(block),
-                        None => {
-                            // TODO: Select a correct error code for "Block with pending status not found in the payload.", now its BLOCK NOT FOUND
-                            return Err(JsonRpcError {
-                                code: 24,
-                                message: "Block with pending status not found in the payload."
-                                    .to_string(),
-                            });
-                        }
-                    }
-                }
-            },
-        };
-
-        match block {
+        match self.cached_block(block_id).await {
             Some(block) => {
-                let tx_hashes = block
-                    .clone()
-                    .transactions
-                    .into_iter()
-                    .map(|transaction| match transaction {
-                        Transaction::Invoke(tx) => match tx {
-                            InvokeTransaction::V0(v0_tx) => v0_tx.transaction_hash,
-                            InvokeTransaction::V1(v1_tx) => v1_tx.transaction_hash,
-                        },
-                        Transaction::Declare(tx) => match tx {
-                            DeclareTransaction::V0(v0_tx) => v0_tx.transaction_hash,
-                            DeclareTransaction::V1(v1_tx) => v1_tx.transaction_hash,
-                            DeclareTransaction::V2(v2_tx) => v2_tx.transaction_hash,
-                        },
-                        Transaction::L1Handler(L1HandlerTransaction {
-                            transaction_hash, ..
-                        })
-                        | Transaction::Deploy(DeployTransaction {
-                            transaction_hash, ..
-                        })
-                        | Transaction::DeployAccount(DeployAccountTransaction {
-                            transaction_hash,
-                            ..
-                        }) => transaction_hash,
-                    })
-                    .collect();
+                let tx_hashes = block.transactions.iter().map(transaction_hash).collect();
                 let block_with_tx_hashes = BlockWithTxHashes {
                     transactions: tx_hashes,
                     status: block.status,
@@ -838,16 +2494,19 @@ impl BeerusLightClient {
                 };
                 Ok(MaybePendingBlockWithTxHashes::Block(block_with_tx_hashes))
             }
-            // TODO: Select a correct error code for "Error while retrieving block.", now its BLOCK NOT FOUND
-            _ => Err(JsonRpcError {
-                code: 24,
-                message: "Error while retrieving block.".to_string(),
-            }),
+            None => {
+                self.starknet_lightclient
+                    .get_block_with_tx_hashes(block_id)
+                    .await
+            }
         }
     }
 
     /// Get a transaction by its hash.
     ///
+    /// The transaction is served from the block cache when possible, from the StarkNet full
+    /// node otherwise.
+    ///
     /// # Arguments
     ///
     /// * `tx_hash` - The transaction hash as a string.
@@ -865,17 +2524,19 @@ impl BeerusLightClient {
     ) -> Result<Transaction, JsonRpcError> {
         let hash = FieldElement::from_str(&tx_hash).map_err(|_| invalid_call_data("hash"))?;
 
-        let transaction = self
-            .starknet_lightclient
+        if let Some(transaction) = self.node.read().await.payload.find_transaction(&hash) {
+            return Ok(transaction.clone());
+        }
+        self.starknet_lightclient
             .get_transaction_by_hash(hash)
             .await
-            .unwrap();
-
-        Ok(transaction)
     }
 
     /// Get a transaction by the block identifier and transaction index.
     ///
+    /// The transaction is served from the block cache when possible, from the StarkNet full
+    /// node otherwise.
+    ///
     /// # Arguments
     ///
     /// * `block_id` - The identifier of the block.
@@ -888,24 +2549,34 @@ impl BeerusLightClient {
     ///
     /// # Errors
     ///
-    /// This method can return a `JsonRpcError` in case of failure.
+    /// This method can return a `JsonRpcError` in case of failure. Possible error codes include:
+    ///
+    /// - `27`: Invalid transaction index in a block.
     pub async fn get_transaction_by_block_and_index(
         &self,
         block_id: &BlockId,
         index: u64,
     ) -> Result<Transaction, JsonRpcError> {
-        let block_with_txs = self.get_block_with_txs(block_id).await.unwrap();
-
-        let transactions = match block_with_txs {
-            MaybePendingBlockWithTxs::Block(block) => block.transactions,
-            MaybePendingBlockWithTxs::PendingBlock(block) => block.transactions,
-        };
-
-        Ok(transactions[index as usize].clone())
+        match self.cached_block(block_id).await {
+            Some(mut block) => {
+                if index as usize >= block.transactions.len() {
+                    return Err(rpc_invalid_transaction_index());
+                }
+                Ok(block.transactions.swap_remove(index as usize))
+            }
+            None => {
+                self.starknet_lightclient
+                    .get_transaction_by_block_id_and_index(block_id, index)
+                    .await
+            }
+        }
     }
 
     /// Get the transaction count of a requested block.
     ///
+    /// The count is served from the block cache when possible, from the StarkNet full node
+    /// otherwise.
+    ///
     /// # Arguments
     ///
     /// * `block_id` - The identifier of the block.
@@ -922,20 +2593,13 @@ impl BeerusLightClient {
         &self,
         block_id: &BlockId,
     ) -> Result<usize, JsonRpcError> {
-        let block_with_txs = self
-            .starknet_lightclient
-            .get_block_with_txs(block_id)
-            .await
-            .unwrap();
-
-        let transactions = match block_with_txs {
-            MaybePendingBlockWithTxs::Block(block) => block.transactions,
-            MaybePendingBlockWithTxs::PendingBlock(block) => block.transactions,
-        };
-
-        let transaction_count = transactions.len();
-
-        Ok(transaction_count)
+        match self.cached_block(block_id).await {
+            Some(block) => Ok(block.transactions.len()),
+            None => Ok(self
+                .starknet_lightclient
+                .get_block_transaction_count(block_id)
+                .await? as usize),
+        }
     }
 
     /// Returns the pending transactions in the StarkNet transaction pool.
@@ -957,6 +2621,215 @@ impl BeerusLightClient {
     }
 }
 
+/// Read the last StarkNet state proven on L1 from the StarkNet core contract.
+///
+/// The state root, block number and block hash are all read at the same Ethereum block,
+/// so that an L1 state update between two calls cannot yield a mismatched snapshot.
+///
+/// # Arguments
+///
+/// * `ethereum_lightclient` - The Ethereum light client.
+/// * `starknet_core_contract_address` - The StarkNet core contract address.
+/// * `starknet_core_abi` - The StarkNet core contract ABI.
+///
+/// # Returns
+///
+/// Returns `Ok(ProvenState)` if the operation was successful, or an `Err(eyre::Report)` if the operation failed.
+pub async fn fetch_proven_state(
+    ethereum_lightclient: &dyn EthereumLightClient,
+    starknet_core_contract_address: H160,
+    starknet_core_abi: &Abi,
+) -> EyreResult<ProvenState> {
+    let l1_block = ethereum_lightclient.get_block_number().await?;
+
+    let call_core_contract = |function_name: &'static str| async move {
+        let data =
+            ethers_helper::encode_function_data((), starknet_core_abi.clone(), function_name)?;
+
+        let call_opts = CallOpts {
+            from: None,
+            to: Some(starknet_core_contract_address),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(data.to_vec()),
+        };
+
+        let call_response = ethereum_lightclient
+            .call(&call_opts, BlockTag::Number(l1_block))
+            .await?;
+
+        Ok::<_, eyre::Report>(U256::from_big_endian(&call_response))
+    };
+
+    let state_root = call_core_contract("stateRoot").await?;
+    let block_number = call_core_contract("stateBlockNumber").await?;
+    let block_hash = call_core_contract("stateBlockHash").await?;
+
+    Ok(ProvenState {
+        block_number: block_number.low_u64(),
+        state_root: u256_to_felt(state_root)?,
+        block_hash: u256_to_felt(block_hash)?,
+        l1_block,
+    })
+}
+
+/// Index the `LogStateUpdate` events emitted since the last indexed L1 block into the node
+/// state history.
+///
+/// Logs are fetched by `fetch_state_updates` one window at a time, so that a long first
+/// synchronization does not hold back the reads. Every indexed window is kept, a failure
+/// resumes from the next one on the following call.
+///
+/// # Arguments
+///
+/// * `ethereum_lightclient` - The Ethereum light client.
+/// * `node` - The node data holding the state history.
+/// * `starknet_core_contract_address` - The StarkNet core contract address.
+/// * `starknet_core_abi` - The StarkNet core contract ABI.
+/// * `to_l1_block` - The last L1 block to index.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the operation was successful, or an `Err(eyre::Report)` if the operation failed.
+pub(crate) async fn index_state_history(
+    ethereum_lightclient: &Mutex<Box<dyn EthereumLightClient>>,
+    node: &RwLock<NodeData>,
+    starknet_core_contract_address: H160,
+    starknet_core_abi: &Abi,
+    to_l1_block: u64,
+) -> EyreResult<()> {
+    let from_l1_block = node.read().await.state_history.next_l1_block(to_l1_block);
+    if from_l1_block > to_l1_block {
+        return Ok(());
+    }
+
+    let mut windows = Box::pin(fetch_state_updates(
+        ethereum_lightclient,
+        starknet_core_contract_address,
+        starknet_core_abi,
+        from_l1_block,
+        to_l1_block,
+    ));
+    while let Some(window) = windows.next().await {
+        let (proven_states, window_end) = window?;
+        node.write()
+            .await
+            .state_history
+            .extend(proven_states, window_end);
+    }
+    Ok(())
+}
+
+pub(crate) fn u256_to_felt(value: U256) -> EyreResult<FieldElement> {
+    FieldElement::from_bytes_be(&ethers_helper::u256_to_bytes32_slice(value))
+        .map_err(|_| eyre::eyre!("Value {value} does not fit in a FieldElement"))
+}
+
+/// Verify storage values against a storage proof anchored to a proven state, returning the
+/// membership of every key.
+fn verify_storage_proof(
+    proof: &GetProofOutput,
+    proven_state: &ProvenState,
+    contract_address: FieldElement,
+    storage_keys: &[FieldElement],
+    storage_values: &[FieldElement],
+) -> Result<Vec<Membership>, JsonRpcError> {
+    let report = proof
+        .verify(
+            proven_state.state_root,
+            contract_address,
+            storage_keys,
+            storage_values,
+        )
+        .map_err(|e| {
+            rpc_proof_verification_failed(format!(
+                "Invalid proof for contract 0x{contract_address:x}: {e}"
+            ))
+        })?;
+
+    storage_keys
+        .iter()
+        .zip(storage_values.iter())
+        .zip(report.storage.into_iter())
+        .map(
+            |((storage_key, storage_value), membership)| match membership {
+                Ok(Membership::Member) => Ok(Membership::Member),
+                // A valid non-membership proof is only consistent with an empty slot.
+                Ok(Membership::NonMember) if *storage_value == FieldElement::ZERO => {
+                    Ok(Membership::NonMember)
+                }
+                Ok(Membership::NonMember) => Err(rpc_proof_verification_failed(format!(
+                    "Non-membership proof for non-zero value 0x{storage_value:x} at key 0x{storage_key:x} of contract 0x{contract_address:x}"
+                ))),
+                Err(e) => Err(rpc_proof_verification_failed(format!(
+                    "Invalid storage proof for key 0x{storage_key:x} of contract 0x{contract_address:x}: {e}"
+                ))),
+            },
+        )
+        .collect()
+}
+
+/// Flatten a state diff into the entries checked against proofs.
+///
+/// A contract deployed and then replaced in the same block is only checked against its final
+/// class hash.
+fn state_diff_checks(state_diff: &StateDiff) -> Vec<StateDiffCheck> {
+    let mut checks = Vec::new();
+    for diff in state_diff.storage_diffs.iter() {
+        for entry in diff.storage_entries.iter() {
+            checks.push(StateDiffCheck::Storage {
+                contract_address: diff.address,
+                key: entry.key,
+                value: entry.value,
+            });
+        }
+    }
+    for nonce in state_diff.nonces.iter() {
+        checks.push(StateDiffCheck::Nonce {
+            contract_address: nonce.contract_address,
+            nonce: nonce.nonce,
+        });
+    }
+    for declared in state_diff.declared_classes.iter() {
+        checks.push(StateDiffCheck::CompiledClassHash {
+            class_hash: declared.class_hash,
+            compiled_class_hash: declared.compiled_class_hash,
+        });
+    }
+    for replaced in state_diff.replaced_classes.iter() {
+        checks.push(StateDiffCheck::ClassHash {
+            contract_address: replaced.contract_address,
+            class_hash: replaced.class_hash,
+        });
+    }
+    for deployed in state_diff.deployed_contracts.iter() {
+        let replaced = state_diff
+            .replaced_classes
+            .iter()
+            .any(|replaced| replaced.contract_address == deployed.address);
+        if !replaced {
+            checks.push(StateDiffCheck::ClassHash {
+                contract_address: deployed.address,
+                class_hash: deployed.class_hash,
+            });
+        }
+    }
+    checks
+}
+
+/// Check that a contract class hashes to the expected class hash.
+fn verify_class_hash(class: &ContractClass, class_hash: FieldElement) -> Result<(), JsonRpcError> {
+    let computed_class_hash = calculate_class_hash(class)
+        .map_err(|e| rpc_class_hash_mismatch(format!("cannot compute class hash: {e}")))?;
+    if computed_class_hash != class_hash {
+        return Err(rpc_class_hash_mismatch(format!(
+            "expected 0x{class_hash:x}, got 0x{computed_class_hash:x}"
+        )));
+    }
+    Ok(())
+}
+
 fn invalid_call_data(param: &str) -> JsonRpcError {
     let message = format!("Invalid params: cannot parse '{}'.", param);
     JsonRpcError { code: 400, message }
@@ -965,3 +2838,49 @@ fn invalid_call_data(param: &str) -> JsonRpcError {
 fn rpc_unknown_error(message: String) -> JsonRpcError {
     JsonRpcError { code: 520, message }
 }
+
+fn rpc_proof_verification_failed(message: String) -> JsonRpcError {
+    JsonRpcError {
+        code: 10001,
+        message: format!("Proof verification failed: {message}"),
+    }
+}
+
+fn rpc_contract_not_found(contract_address: FieldElement) -> JsonRpcError {
+    JsonRpcError {
+        code: CONTRACT_NOT_FOUND_CODE,
+        message: format!("Contract not found: 0x{contract_address:x}"),
+    }
+}
+
+fn rpc_invalid_transaction_index() -> JsonRpcError {
+    JsonRpcError {
+        code: INVALID_TXN_INDEX_CODE,
+        message: "Invalid transaction index in a block".to_string(),
+    }
+}
+
+fn rpc_class_hash_mismatch(message: String) -> JsonRpcError {
+    JsonRpcError {
+        code: 10002,
+        message: format!("Class hash mismatch: {message}"),
+    }
+}
+
+fn rpc_events_limit_exceeded(first_block: u64, last_block: u64) -> JsonRpcError {
+    JsonRpcError {
+        code: 10003,
+        message: format!(
+            "Too many blocks to verify: {first_block} to {last_block}, at most {MAX_EVENTS_BLOCK_RANGE} per page of events"
+        ),
+    }
+}
+
+fn rpc_proof_limit_exceeded(storage_keys: usize) -> JsonRpcError {
+    JsonRpcError {
+        code: 10000,
+        message: format!(
+            "Too many storage keys requested: {storage_keys}, at most {MAX_STORAGE_KEYS_PER_PROOF} per proof"
+        ),
+    }
+}
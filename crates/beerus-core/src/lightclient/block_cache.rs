@@ -0,0 +1,318 @@
+use crate::stdlib::{collections::BTreeMap, vec::Vec};
+
+use super::starknet::commitment::transaction_hash;
+use eyre::Result;
+#[cfg(feature = "std")]
+use log::warn;
+use starknet::core::types::{
+    BlockWithTxs, FieldElement, MaybePendingTransactionReceipt, Transaction,
+};
+#[cfg(feature = "std")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Default number of blocks kept in the block cache.
+pub const DEFAULT_BLOCK_CACHE_MAX_BLOCKS: usize = 1024;
+/// Directory of the block cache, relative to `Config::data_dir`.
+pub const BLOCK_CACHE_DIR: &str = "blocks";
+
+/// Retention window of the block cache. The most recent blocks are kept within both limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockRetention {
+    /// Maximum number of blocks.
+    pub max_blocks: usize,
+    /// Maximum size of the blocks and their receipts serialized as JSON, unbounded if `None`.
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for BlockRetention {
+    fn default() -> Self {
+        Self {
+            max_blocks: DEFAULT_BLOCK_CACHE_MAX_BLOCKS,
+            max_bytes: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CachedBlock {
+    block: BlockWithTxs,
+    /// Receipts of the block in transaction order, once it has been verified.
+    receipts: Option<Vec<MaybePendingTransactionReceipt>>,
+    /// Size of the block and its receipts serialized as JSON.
+    size: usize,
+}
+
+/// StarkNet blocks fetched from the full node, indexed by block number, block hash and
+/// transaction hash.
+///
+/// The cache keeps the most recent blocks within its retention window and, when opened on a
+/// directory, persists every block as a JSON file so that it survives restarts. Receipts are
+/// only kept in memory.
+#[derive(Clone, Debug, Default)]
+pub struct BlockCache {
+    blocks: BTreeMap<u64, CachedBlock>,
+    hashes: BTreeMap<[u8; 32], u64>,
+    /// Block number and index of every cached transaction, by transaction hash.
+    transactions: BTreeMap<[u8; 32], (u64, usize)>,
+    total_bytes: usize,
+    retention: BlockRetention,
+    #[cfg(feature = "std")]
+    dir: Option<PathBuf>,
+}
+
+impl BlockCache {
+    /// Create an in-memory block cache.
+    pub fn new(retention: BlockRetention) -> Self {
+        Self {
+            retention,
+            ..Self::default()
+        }
+    }
+
+    /// Open a block cache persisted in `dir`, loading the blocks stored by a previous run.
+    ///
+    /// Unreadable block files are discarded, blocks outside the retention window are evicted.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(BlockCache)` if the operation was successful, or an `Err(eyre::Report)` if
+    /// the directory cannot be created or listed.
+    #[cfg(feature = "std")]
+    pub fn open(dir: impl Into<PathBuf>, retention: BlockRetention) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let mut cache = Self::new(retention);
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "json")
+            {
+                continue;
+            }
+            match read_block(&path) {
+                Ok((block, size)) => cache.insert_cached(block, size),
+                Err(err) => {
+                    warn!("Discarding cached block {}: {}", path.display(), err);
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+
+        cache.dir = Some(dir);
+        cache.evict()?;
+        Ok(cache)
+    }
+
+    /// Insert a block, evicting the oldest blocks outside the retention window.
+    ///
+    /// The block is cached in memory even if it cannot be persisted.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the operation was successful, or an `Err(eyre::Report)` if the
+    /// block cannot be serialized or written to disk.
+    pub fn insert(&mut self, block: BlockWithTxs) -> Result<()> {
+        let json = serde_json::to_vec(&block)?;
+        #[cfg(feature = "std")]
+        let block_number = block.block_number;
+        self.insert_cached(block, json.len());
+        self.evict()?;
+
+        #[cfg(feature = "std")]
+        if let Some(dir) = &self.dir {
+            // The block may already be outside the retention window.
+            if self.blocks.contains_key(&block_number) {
+                fs::write(block_path(dir, block_number), json)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the blocks numbered `from` and above, e.g. orphaned by a reorganization of the
+    /// chain, and return them by ascending block number.
+    pub fn truncate(&mut self, from: u64) -> Vec<BlockWithTxs> {
+        let block_numbers: Vec<u64> = self
+            .blocks
+            .range(from..)
+            .map(|(block_number, _)| *block_number)
+            .collect();
+        let mut removed = Vec::with_capacity(block_numbers.len());
+        for block_number in block_numbers {
+            if let Some(cached) = self.remove_cached(block_number) {
+                removed.push(cached.block);
+            }
+
+            // A stale file is overwritten once the canonical block is cached.
+            #[cfg(feature = "std")]
+            if let Err(err) = self.remove_file(block_number) {
+                warn!("Error removing cached block {}: {}", block_number, err);
+            }
+        }
+        removed
+    }
+
+    /// Return the block with the given block number, if cached.
+    pub fn get(&self, block_number: u64) -> Option<&BlockWithTxs> {
+        self.blocks.get(&block_number).map(|cached| &cached.block)
+    }
+
+    /// Return the block with the given block hash, if cached.
+    pub fn get_by_hash(&self, block_hash: &FieldElement) -> Option<&BlockWithTxs> {
+        self.hashes
+            .get(&block_hash.to_bytes_be())
+            .and_then(|block_number| self.get(*block_number))
+    }
+
+    /// Return the most recent cached block, if any.
+    pub fn latest(&self) -> Option<&BlockWithTxs> {
+        self.blocks.values().next_back().map(|cached| &cached.block)
+    }
+
+    /// Attach the receipts of the cached block with the given block hash, in transaction order,
+    /// so that the block can be verified again without fetching them.
+    ///
+    /// Receipts of a block that is not cached, or already cached with its receipts, are dropped.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the operation was successful, or an `Err(eyre::Report)` if the
+    /// receipts cannot be serialized or an evicted block cannot be removed from disk.
+    pub fn insert_receipts(
+        &mut self,
+        block_hash: &FieldElement,
+        receipts: Vec<MaybePendingTransactionReceipt>,
+    ) -> Result<()> {
+        let cached = match self
+            .hashes
+            .get(&block_hash.to_bytes_be())
+            .and_then(|block_number| self.blocks.get_mut(block_number))
+        {
+            Some(cached) if cached.receipts.is_none() => cached,
+            _ => return Ok(()),
+        };
+        let size = serde_json::to_vec(&receipts)?.len();
+        cached.receipts = Some(receipts);
+        cached.size += size;
+        self.total_bytes += size;
+        self.evict()
+    }
+
+    /// Return the receipts of the cached block with the given block hash, if cached along with
+    /// the block.
+    pub fn receipts(&self, block_hash: &FieldElement) -> Option<&[MaybePendingTransactionReceipt]> {
+        let block_number = self.hashes.get(&block_hash.to_bytes_be())?;
+        self.blocks.get(block_number)?.receipts.as_deref()
+    }
+
+    /// Return the cached transaction with the given hash, from the most recent block holding it.
+    pub fn find_transaction(&self, tx_hash: &FieldElement) -> Option<&Transaction> {
+        let (block_number, index) = self.transactions.get(&tx_hash.to_bytes_be())?;
+        self.get(*block_number)
+            .and_then(|block| block.transactions.get(*index))
+    }
+
+    /// Iterate over the cached blocks by ascending block number.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &BlockWithTxs> {
+        self.blocks.values().map(|cached| &cached.block)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Return the size of the cached blocks serialized as JSON.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    pub fn retention(&self) -> BlockRetention {
+        self.retention
+    }
+
+    fn insert_cached(&mut self, block: BlockWithTxs, size: usize) {
+        let block_number = block.block_number;
+        self.remove_cached(block_number);
+        self.hashes
+            .insert(block.block_hash.to_bytes_be(), block_number);
+        for (index, transaction) in block.transactions.iter().enumerate() {
+            let entry = self
+                .transactions
+                .entry(transaction_hash(transaction).to_bytes_be())
+                .or_insert((block_number, index));
+            if entry.0 < block_number {
+                *entry = (block_number, index);
+            }
+        }
+        self.total_bytes += size;
+        self.blocks.insert(
+            block_number,
+            CachedBlock {
+                block,
+                receipts: None,
+                size,
+            },
+        );
+    }
+
+    fn remove_cached(&mut self, block_number: u64) -> Option<CachedBlock> {
+        let cached = self.blocks.remove(&block_number)?;
+        self.hashes.remove(&cached.block.block_hash.to_bytes_be());
+        for transaction in cached.block.transactions.iter() {
+            let tx_hash = transaction_hash(transaction).to_bytes_be();
+            if matches!(self.transactions.get(&tx_hash), Some((number, _)) if *number == block_number)
+            {
+                self.transactions.remove(&tx_hash);
+            }
+        }
+        self.total_bytes -= cached.size;
+        Some(cached)
+    }
+
+    /// Evict the oldest blocks until the cache fits its retention window.
+    fn evict(&mut self) -> Result<()> {
+        while self.blocks.len() > self.retention.max_blocks
+            || matches!(self.retention.max_bytes, Some(max_bytes) if self.total_bytes > max_bytes)
+        {
+            let oldest = match self.blocks.keys().next() {
+                Some(block_number) => *block_number,
+                None => break,
+            };
+            self.remove_cached(oldest);
+            #[cfg(feature = "std")]
+            self.remove_file(oldest)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn remove_file(&self, block_number: u64) -> Result<()> {
+        if let Some(dir) = &self.dir {
+            let path = block_path(dir, block_number);
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+fn block_path(dir: &Path, block_number: u64) -> PathBuf {
+    dir.join(format!("{block_number}.json"))
+}
+
+#[cfg(feature = "std")]
+fn read_block(path: &Path) -> Result<(BlockWithTxs, usize)> {
+    let json = fs::read(path)?;
+    let block = serde_json::from_slice(&json)?;
+    Ok((block, json.len()))
+}
@@ -1,3 +1,8 @@
+pub mod backfill;
 pub mod beerus;
+pub mod block_cache;
 pub mod ethereum;
 pub mod starknet;
+pub mod state_history;
+#[cfg(feature = "std")]
+pub mod sync;
@@ -0,0 +1,41 @@
+use eyre::Result;
+use flate2::read::GzDecoder;
+use serde_json::json;
+use starknet::core::types::{
+    contract::legacy::LegacyContractClass, CompressedLegacyContractClass, ContractClass,
+    FieldElement,
+};
+
+use crate::stdlib::{io::Read, vec::Vec};
+
+/// Compute the hash of a contract class.
+///
+/// Sierra classes are hashed from their flattened program and entry points. Legacy classes are
+/// decompressed first, then hashed like the sequencer does on declaration.
+///
+/// # Arguments
+///
+/// * `class` - The contract class returned by the StarkNet full node.
+///
+/// # Errors
+///
+/// This function returns an error if a legacy class program cannot be decompressed or parsed.
+pub fn calculate_class_hash(class: &ContractClass) -> Result<FieldElement> {
+    match class {
+        ContractClass::Sierra(class) => Ok(class.class_hash()),
+        ContractClass::Legacy(class) => Ok(decompress_legacy_class(class)?.class_hash()?),
+    }
+}
+
+/// Rebuild the full legacy class from its compressed RPC representation.
+fn decompress_legacy_class(class: &CompressedLegacyContractClass) -> Result<LegacyContractClass> {
+    let mut program = Vec::new();
+    GzDecoder::new(class.program.as_slice()).read_to_end(&mut program)?;
+    let program: serde_json::Value = serde_json::from_slice(&program)?;
+
+    Ok(serde_json::from_value(json!({
+        "abi": class.abi,
+        "entry_points_by_type": class.entry_points_by_type,
+        "program": program,
+    }))?)
+}
@@ -0,0 +1,223 @@
+use starknet::core::{
+    chain_id,
+    crypto::{compute_hash_on_elements, pedersen_hash},
+    types::{
+        BlockWithTxs, DeclareTransaction, Event, FieldElement, InvokeTransaction,
+        MaybePendingTransactionReceipt, Transaction, TransactionReceipt,
+    },
+};
+
+use crate::stdlib::vec::Vec;
+
+/// Height of the Patricia trees used for the transaction and event commitments.
+pub const COMMITMENT_TREE_HEIGHT: usize = 64;
+
+/// A node of a Patricia tree being built bottom-up.
+///
+/// `path` holds the `len` bits of the edge leading to the node, empty for leaves and binary nodes.
+struct PatriciaNode {
+    hash: FieldElement,
+    path: u64,
+    len: usize,
+}
+
+impl PatriciaNode {
+    /// Hash of the node as seen by its parent, including its edge if any.
+    fn hash(&self) -> FieldElement {
+        if self.len == 0 {
+            self.hash
+        } else {
+            pedersen_hash(&self.hash, &FieldElement::from(self.path))
+                + FieldElement::from(self.len as u64)
+        }
+    }
+}
+
+/// Compute the root of a height-64 Patricia tree whose leaf at index `i` is `leaves[i]`.
+///
+/// Binary nodes hash to `pedersen(left, right)` and edges to `pedersen(child, path) + length`.
+/// An empty tree has a zero root.
+pub fn calculate_patricia_root(leaves: &[FieldElement]) -> FieldElement {
+    let leaves: Vec<(u64, FieldElement)> = leaves
+        .iter()
+        .enumerate()
+        .map(|(index, leaf)| (index as u64, *leaf))
+        .collect();
+
+    match build_patricia_node(&leaves, COMMITMENT_TREE_HEIGHT) {
+        Some(node) => node.hash(),
+        None => FieldElement::ZERO,
+    }
+}
+
+/// Build the node of height `height` holding the given sorted leaves.
+fn build_patricia_node(leaves: &[(u64, FieldElement)], height: usize) -> Option<PatriciaNode> {
+    if leaves.is_empty() {
+        return None;
+    }
+    if height == 0 {
+        return Some(PatriciaNode {
+            hash: leaves[0].1,
+            path: 0,
+            len: 0,
+        });
+    }
+
+    let bit = height - 1;
+    let split = leaves.partition_point(|(index, _)| (index >> bit) & 1 == 0);
+    let left = build_patricia_node(&leaves[..split], bit);
+    let right = build_patricia_node(&leaves[split..], bit);
+
+    match (left, right) {
+        (Some(left), Some(right)) => Some(PatriciaNode {
+            hash: pedersen_hash(&left.hash(), &right.hash()),
+            path: 0,
+            len: 0,
+        }),
+        // A single child: extend its edge by one bit.
+        (Some(child), None) => Some(PatriciaNode {
+            path: child.path,
+            len: child.len + 1,
+            hash: child.hash,
+        }),
+        (None, Some(child)) => Some(PatriciaNode {
+            path: child.path | (1 << child.len),
+            len: child.len + 1,
+            hash: child.hash,
+        }),
+        (None, None) => None,
+    }
+}
+
+/// Return the hash of a transaction.
+pub fn transaction_hash(transaction: &Transaction) -> FieldElement {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => tx.transaction_hash,
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => tx.transaction_hash,
+        Transaction::L1Handler(tx) => tx.transaction_hash,
+        Transaction::Declare(DeclareTransaction::V0(tx)) => tx.transaction_hash,
+        Transaction::Declare(DeclareTransaction::V1(tx)) => tx.transaction_hash,
+        Transaction::Declare(DeclareTransaction::V2(tx)) => tx.transaction_hash,
+        Transaction::Deploy(tx) => tx.transaction_hash,
+        Transaction::DeployAccount(tx) => tx.transaction_hash,
+    }
+}
+
+/// Return the signature of a transaction, empty for unsigned transaction types.
+pub fn transaction_signature(transaction: &Transaction) -> &[FieldElement] {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => &tx.signature,
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V0(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V1(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V2(tx)) => &tx.signature,
+        Transaction::DeployAccount(tx) => &tx.signature,
+        Transaction::L1Handler(_) | Transaction::Deploy(_) => &[],
+    }
+}
+
+/// Return the events emitted by a transaction, `None` if the transaction is still pending.
+pub fn receipt_events(receipt: &MaybePendingTransactionReceipt) -> Option<&[Event]> {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => Some(match receipt {
+            TransactionReceipt::Invoke(receipt) => &receipt.events,
+            TransactionReceipt::L1Handler(receipt) => &receipt.events,
+            TransactionReceipt::Declare(receipt) => &receipt.events,
+            TransactionReceipt::Deploy(receipt) => &receipt.events,
+            TransactionReceipt::DeployAccount(receipt) => &receipt.events,
+        }),
+        MaybePendingTransactionReceipt::PendingReceipt(_) => None,
+    }
+}
+
+/// Return the number of the block a transaction was included in, `None` if it is still pending.
+pub fn receipt_block_number(receipt: &MaybePendingTransactionReceipt) -> Option<u64> {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => Some(match receipt {
+            TransactionReceipt::Invoke(receipt) => receipt.block_number,
+            TransactionReceipt::L1Handler(receipt) => receipt.block_number,
+            TransactionReceipt::Declare(receipt) => receipt.block_number,
+            TransactionReceipt::Deploy(receipt) => receipt.block_number,
+            TransactionReceipt::DeployAccount(receipt) => receipt.block_number,
+        }),
+        MaybePendingTransactionReceipt::PendingReceipt(_) => None,
+    }
+}
+
+/// Compute the transaction commitment of a block.
+///
+/// The leaf at index `i` is `h(transaction_hash, h(signature))` of the `i`-th transaction.
+pub fn calculate_transaction_commitment(transactions: &[Transaction]) -> FieldElement {
+    let leaves: Vec<FieldElement> = transactions
+        .iter()
+        .map(|transaction| {
+            pedersen_hash(
+                &transaction_hash(transaction),
+                &compute_hash_on_elements(transaction_signature(transaction)),
+            )
+        })
+        .collect();
+    calculate_patricia_root(&leaves)
+}
+
+/// Compute the hash of an event: `h(from_address, h(keys), h(data))`.
+pub fn calculate_event_hash(event: &Event) -> FieldElement {
+    compute_hash_on_elements(&[
+        event.from_address,
+        compute_hash_on_elements(&event.keys),
+        compute_hash_on_elements(&event.data),
+    ])
+}
+
+/// Compute the event commitment of a block from its events, in emission order.
+pub fn calculate_event_commitment(events: &[Event]) -> FieldElement {
+    let leaves: Vec<FieldElement> = events.iter().map(calculate_event_hash).collect();
+    calculate_patricia_root(&leaves)
+}
+
+/// First mainnet block hashed with the StarkNet v0.7.0 formula.
+pub const MAINNET_FIRST_0_7_BLOCK: u64 = 833;
+/// First goerli block hashed with the StarkNet v0.7.0 formula.
+pub const GOERLI_FIRST_0_7_BLOCK: u64 = 47028;
+
+/// Return `true` if the block was hashed before StarkNet v0.7.0, with a formula
+/// `calculate_block_hash` does not support.
+///
+/// Other chains than mainnet and goerli started after v0.7.0.
+pub fn has_legacy_block_hash(chain_id: FieldElement, block_number: u64) -> bool {
+    if chain_id == chain_id::MAINNET {
+        block_number < MAINNET_FIRST_0_7_BLOCK
+    } else if chain_id == chain_id::TESTNET {
+        block_number < GOERLI_FIRST_0_7_BLOCK
+    } else {
+        false
+    }
+}
+
+/// Compute the hash of a block from its header fields and commitments.
+///
+/// `h(block_number, state_root, sequencer_address, timestamp, transaction_count,
+/// transaction_commitment, event_count, event_commitment, 0, 0, parent_hash)`
+///
+/// This is the block hash of StarkNet blocks since v0.7.0, see `has_legacy_block_hash` for
+/// older blocks.
+///
+/// # Arguments
+///
+/// * `block` - The block with its transactions.
+/// * `events` - All the events emitted in the block, in emission order.
+pub fn calculate_block_hash(block: &BlockWithTxs, events: &[Event]) -> FieldElement {
+    compute_hash_on_elements(&[
+        FieldElement::from(block.block_number),
+        block.new_root,
+        block.sequencer_address,
+        FieldElement::from(block.timestamp),
+        FieldElement::from(block.transactions.len() as u64),
+        calculate_transaction_commitment(&block.transactions),
+        FieldElement::from(events.len() as u64),
+        calculate_event_commitment(events),
+        FieldElement::ZERO,
+        FieldElement::ZERO,
+        block.parent_hash,
+    ])
+}
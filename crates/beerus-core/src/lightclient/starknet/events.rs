@@ -0,0 +1,111 @@
+use starknet::core::types::{
+    BlockWithTxs, EmittedEvent, Event, EventFilter, FieldElement, MaybePendingTransactionReceipt,
+};
+
+use super::commitment::{receipt_events, transaction_hash};
+use crate::stdlib::vec::Vec;
+
+/// Return `true` if an event matches the address and keys of a filter.
+///
+/// The `i`-th key of the event must be one of the `i`-th keys of the filter, an empty set of keys
+/// matching any value. The block range of the filter is not checked.
+pub fn event_matches_filter(
+    from_address: FieldElement,
+    keys: &[FieldElement],
+    filter: &EventFilter,
+) -> bool {
+    if let Some(address) = filter.address {
+        if address != from_address {
+            return false;
+        }
+    }
+
+    match &filter.keys {
+        Some(filter_keys) => filter_keys.iter().enumerate().all(|(index, allowed)| {
+            allowed.is_empty() || keys.get(index).map_or(false, |key| allowed.contains(key))
+        }),
+        None => true,
+    }
+}
+
+/// Return the events of a block matching a filter, in emission order.
+///
+/// # Arguments
+///
+/// * `block` - The block with its transactions.
+/// * `receipts` - The receipts of the block transactions, in transaction order.
+/// * `filter` - The event filter.
+pub fn block_events_matching(
+    block: &BlockWithTxs,
+    receipts: &[MaybePendingTransactionReceipt],
+    filter: &EventFilter,
+) -> Vec<EmittedEvent> {
+    block
+        .transactions
+        .iter()
+        .zip(receipts.iter())
+        .flat_map(|(transaction, receipt)| {
+            let tx_hash = transaction_hash(transaction);
+            receipt_events(receipt)
+                .unwrap_or_default()
+                .iter()
+                .filter(|event| event_matches_filter(event.from_address, &event.keys, filter))
+                .map(move |event| emitted_event(event, block, tx_hash))
+        })
+        .collect()
+}
+
+/// Return `true` if the events returned for a block are a contiguous run of the matching
+/// events of the block.
+///
+/// # Arguments
+///
+/// * `expected` - All the events of the block matching the filter, in emission order.
+/// * `page` - The events of the block returned in the page.
+/// * `starts_block` - `true` if the page must hold the first matching event of the block.
+/// * `ends_block` - `true` if the page must hold the last matching event of the block.
+pub fn is_complete_block_slice(
+    expected: &[EmittedEvent],
+    page: &[EmittedEvent],
+    starts_block: bool,
+    ends_block: bool,
+) -> bool {
+    if page.len() > expected.len() {
+        return false;
+    }
+
+    (0..=expected.len() - page.len())
+        .filter(|offset| !starts_block || *offset == 0)
+        .filter(|offset| !ends_block || offset + page.len() == expected.len())
+        .any(|offset| {
+            expected[offset..offset + page.len()]
+                .iter()
+                .zip(page.iter())
+                .all(|(expected, event)| emitted_event_eq(expected, event))
+        })
+}
+
+/// Compare two emitted events field by field.
+pub fn emitted_event_eq(left: &EmittedEvent, right: &EmittedEvent) -> bool {
+    left.from_address == right.from_address
+        && left.keys == right.keys
+        && left.data == right.data
+        && left.block_hash == right.block_hash
+        && left.block_number == right.block_number
+        && left.transaction_hash == right.transaction_hash
+}
+
+fn emitted_event(
+    event: &Event,
+    block: &BlockWithTxs,
+    transaction_hash: FieldElement,
+) -> EmittedEvent {
+    EmittedEvent {
+        from_address: event.from_address,
+        keys: event.keys.clone(),
+        data: event.data.clone(),
+        block_hash: block.block_hash,
+        block_number: block.block_number,
+        transaction_hash,
+    }
+}
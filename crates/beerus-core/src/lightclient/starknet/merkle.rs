@@ -0,0 +1,186 @@
+use eyre::{eyre, Result};
+use starknet::core::types::FieldElement;
+
+use super::storage_proof::{felt_to_bits_be, Binary, Edge, Path, ProofNode, TrieHash};
+use crate::stdlib::boxed::Box;
+use crate::stdlib::collections::BTreeMap;
+use crate::stdlib::vec::Vec;
+
+/// Number of bits of the keys of the StarkNet trees.
+pub const KEY_LEN: usize = 251;
+
+/// StarkNet binary Patricia-Merkle tree, as used for the contract tree, the storage trees and
+/// the class tree.
+///
+/// The tree only stores its leaves: nodes are rebuilt when the root or a proof is requested.
+/// It is meant for local fixtures and proof serving, not for large states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    trie_hash: TrieHash,
+    leaves: BTreeMap<FieldElement, FieldElement>,
+}
+
+impl MerkleTree {
+    /// Create an empty tree hashing its nodes with `trie_hash`.
+    pub fn new(trie_hash: TrieHash) -> Self {
+        Self {
+            trie_hash,
+            leaves: BTreeMap::new(),
+        }
+    }
+
+    /// Set the value of a leaf. Setting a leaf to zero removes it from the tree.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the key does not fit in 251 bits.
+    pub fn insert(&mut self, key: FieldElement, value: FieldElement) -> Result<()> {
+        if felt_to_bits_be(key)[..256 - KEY_LEN].iter().any(|bit| *bit) {
+            return Err(eyre!("Key 0x{key:x} does not fit in {KEY_LEN} bits"));
+        }
+
+        if value == FieldElement::ZERO {
+            self.leaves.remove(&key);
+        } else {
+            self.leaves.insert(key, value);
+        }
+        Ok(())
+    }
+
+    /// Return the value of a leaf, zero if it is not set.
+    pub fn get(&self, key: FieldElement) -> FieldElement {
+        self.leaves.get(&key).copied().unwrap_or(FieldElement::ZERO)
+    }
+
+    /// Return the root hash of the tree, zero for an empty tree.
+    pub fn root(&self) -> FieldElement {
+        self.build().map_or(FieldElement::ZERO, |root| root.hash())
+    }
+
+    /// Return the membership or non-membership proof of a key, from the root down.
+    ///
+    /// The proof of any key of an empty tree is empty.
+    pub fn get_proof(&self, key: FieldElement) -> Vec<ProofNode> {
+        let mut proof = Vec::new();
+        let root = match self.build() {
+            Some(root) => root,
+            None => return proof,
+        };
+
+        let bits = key_bits(key);
+        let mut depth = 0;
+        let mut node = &root;
+        loop {
+            match node {
+                Node::Leaf(_) => return proof,
+                Node::Binary { left, right, .. } => {
+                    proof.push(ProofNode::Binary(Binary {
+                        left: left.hash(),
+                        right: right.hash(),
+                    }));
+                    node = if bits[depth] { right } else { left };
+                    depth += 1;
+                }
+                Node::Edge { path, child, .. } => {
+                    proof.push(ProofNode::Edge(Edge {
+                        path: Path::new(bits_to_felt(path), path.len() as u8),
+                        child: child.hash(),
+                    }));
+                    if bits[depth..depth + path.len()] != path[..] {
+                        return proof;
+                    }
+                    node = child;
+                    depth += path.len();
+                }
+            }
+        }
+    }
+
+    fn build(&self) -> Option<Node> {
+        let mut leaves: Vec<([bool; KEY_LEN], FieldElement)> = self
+            .leaves
+            .iter()
+            .map(|(key, value)| (key_bits(*key), *value))
+            .collect();
+        leaves.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+        if leaves.is_empty() {
+            return None;
+        }
+        Some(build(&leaves, 0, self.trie_hash))
+    }
+}
+
+enum Node {
+    Leaf(FieldElement),
+    Binary {
+        left: Box<Node>,
+        right: Box<Node>,
+        hash: FieldElement,
+    },
+    Edge {
+        path: Vec<bool>,
+        child: Box<Node>,
+        hash: FieldElement,
+    },
+}
+
+impl Node {
+    fn hash(&self) -> FieldElement {
+        match self {
+            Node::Leaf(value) => *value,
+            Node::Binary { hash, .. } | Node::Edge { hash, .. } => *hash,
+        }
+    }
+}
+
+/// Build the subtree of sorted leaves sharing their first `depth` bits.
+fn build(leaves: &[([bool; KEY_LEN], FieldElement)], depth: usize, trie_hash: TrieHash) -> Node {
+    if depth == KEY_LEN {
+        return Node::Leaf(leaves[0].1);
+    }
+
+    // The common prefix of the first and last keys is shared by all the keys.
+    let first = &leaves[0].0;
+    let last = &leaves[leaves.len() - 1].0;
+    let prefix_len = (depth..KEY_LEN)
+        .take_while(|i| first[*i] == last[*i])
+        .count();
+
+    if prefix_len > 0 {
+        let path = first[depth..depth + prefix_len].to_vec();
+        let child = build(leaves, depth + prefix_len, trie_hash);
+        let hash = trie_hash.hash(&child.hash(), &bits_to_felt(&path))
+            + FieldElement::from(path.len() as u64);
+        Node::Edge {
+            path,
+            child: Box::new(child),
+            hash,
+        }
+    } else {
+        let split = leaves.partition_point(|(key, _)| !key[depth]);
+        let left = build(&leaves[..split], depth + 1, trie_hash);
+        let right = build(&leaves[split..], depth + 1, trie_hash);
+        let hash = trie_hash.hash(&left.hash(), &right.hash());
+        Node::Binary {
+            left: Box::new(left),
+            right: Box::new(right),
+            hash,
+        }
+    }
+}
+
+fn key_bits(key: FieldElement) -> [bool; KEY_LEN] {
+    let mut bits = [false; KEY_LEN];
+    bits.copy_from_slice(&felt_to_bits_be(key)[256 - KEY_LEN..]);
+    bits
+}
+
+fn bits_to_felt(bits: &[bool]) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    for (i, bit) in bits.iter().rev().enumerate() {
+        if *bit {
+            bytes[31 - i / 8] |= 1 << (i % 8);
+        }
+    }
+    FieldElement::from_bytes_be(&bytes).unwrap()
+}
@@ -1,4 +1,7 @@
-use crate::{config::Config, lightclient::starknet::storage_proof::GetProofOutput};
+use crate::{
+    config::Config,
+    lightclient::starknet::storage_proof::{GetClassProofOutput, GetProofOutput},
+};
 
 use crate::lightclient::starknet::errors::JsonRpcClientErrorWrapper;
 use crate::stdlib::boxed::Box;
@@ -29,8 +32,14 @@ use starknet::providers::jsonrpc::{
 };
 use starknet::providers::{Provider as StarknetProvider, ProviderError};
 use url::Url;
+pub mod class_hash;
+pub mod commitment;
 mod errors;
+pub mod events;
+pub mod merkle;
+pub mod storage_key;
 pub mod storage_proof;
+pub mod transaction_hash;
 
 // #[cfg(feature="std")]
 // #[automock]
@@ -148,6 +157,12 @@ pub trait StarkNetLightClient: Send + Sync {
         block: &BlockId,
     ) -> Result<GetProofOutput, JsonRpcError>;
 
+    async fn get_class_proof(
+        &self,
+        class_hash: FieldElement,
+        block: &BlockId,
+    ) -> Result<GetClassProofOutput, JsonRpcError>;
+
     async fn get_block_with_txs(
         &self,
         block_id: &BlockId,
@@ -759,6 +774,56 @@ impl StarkNetLightClient for StarkNetLightClientImpl {
             })
     }
 
+    /// Get a class proof from the class tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `class_hash`: Hash of the class.
+    /// * `block_id`: ID of the block the proof is needed for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `GetClassProofOutput` if the operation was successful,
+    /// or an `Err` containing a `JsonRpcError` if the operation failed.
+    ///
+    /// ## Errors
+    ///
+    /// This method can return a `JsonRpcError` in case of failure.
+    async fn get_class_proof(
+        &self,
+        class_hash: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<GetClassProofOutput, JsonRpcError> {
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        enum Param<'a> {
+            Block(&'a BlockId),
+            ClassHash(String),
+        }
+
+        let params = [
+            Param::Block(block_id),
+            Param::ClassHash(format!("0x{class_hash:x}")),
+        ];
+
+        self.provider
+            .request::<Vec<Param>, GetClassProofOutput>(
+                "pathfinder_getClassProof",
+                Vec::from(params),
+            )
+            .await
+            .map_err(|e| {
+                let error = JsonRpcError::try_from(JsonRpcClientErrorWrapper::from(e));
+                match error {
+                    Ok(rpc_error) => rpc_error,
+                    Err(unknown_error) => JsonRpcError {
+                        code: 520,
+                        message: "[get_class_proof] ".to_owned() + &unknown_error.to_string(),
+                    },
+                }
+            })
+    }
+
     /// Get the transactions of a given block.
     ///
     /// # Arguments
@@ -0,0 +1,108 @@
+use ethabi::Uint as U256;
+use eyre::{eyre, Result};
+use starknet::core::{crypto::pedersen_hash, types::FieldElement, utils::starknet_keccak};
+
+use crate::stdlib::vec::Vec;
+
+/// Upper bound of StarkNet storage addresses: `2**251 - 256`.
+const ADDR_BOUND: &str = "0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00";
+
+/// Return the storage address of a Cairo storage variable.
+///
+/// The address is `sn_keccak(name)` for a plain variable, and is folded with pedersen over the
+/// key arguments of a mapping: `h(...h(h(sn_keccak(name), k1), k2)..., kn)`. A nested mapping
+/// simply takes the keys of every level in order. The result is reduced modulo `2**251 - 256`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the storage variable.
+/// * `keys` - The key arguments of the storage variable, empty for a plain variable.
+pub fn storage_var_address(name: &str, keys: &[FieldElement]) -> FieldElement {
+    let address = keys
+        .iter()
+        .fold(starknet_keccak(name.as_bytes()), |address, key| {
+            pedersen_hash(&address, key)
+        });
+
+    let addr_bound = FieldElement::from_hex_be(ADDR_BOUND).unwrap();
+    if address >= addr_bound {
+        address - addr_bound
+    } else {
+        address
+    }
+}
+
+/// Return the `(low, high)` felts of a `Uint256` key argument, in the order Cairo hashes them.
+pub fn u256_to_felts(value: U256) -> [FieldElement; 2] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+
+    let mut low = [0u8; 32];
+    let mut high = [0u8; 32];
+    low[16..].copy_from_slice(&bytes[16..]);
+    high[16..].copy_from_slice(&bytes[..16]);
+    [
+        FieldElement::from_bytes_be(&low).unwrap(),
+        FieldElement::from_bytes_be(&high).unwrap(),
+    ]
+}
+
+/// A value stored in one or more consecutive storage slots of a Cairo storage variable.
+pub trait StorageValue: Sized {
+    /// Number of consecutive storage slots the value spans.
+    const SLOTS: usize;
+
+    /// Decode the value from its storage slots.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the slots do not hold a valid value.
+    fn from_felts(felts: &[FieldElement]) -> Result<Self>;
+}
+
+impl StorageValue for FieldElement {
+    const SLOTS: usize = 1;
+
+    fn from_felts(felts: &[FieldElement]) -> Result<Self> {
+        match felts {
+            [value] => Ok(*value),
+            _ => Err(eyre!("Expected 1 storage slot, got {}", felts.len())),
+        }
+    }
+}
+
+/// A `Uint256` stored as its low 128 bits followed by its high 128 bits.
+impl StorageValue for U256 {
+    const SLOTS: usize = 2;
+
+    fn from_felts(felts: &[FieldElement]) -> Result<Self> {
+        match felts {
+            [low, high] => {
+                let low = felt_to_u128_bytes(*low)?;
+                let high = felt_to_u128_bytes(*high)?;
+                let mut bytes = [0u8; 32];
+                bytes[..16].copy_from_slice(&high);
+                bytes[16..].copy_from_slice(&low);
+                Ok(U256::from_big_endian(&bytes))
+            }
+            _ => Err(eyre!("Expected 2 storage slots, got {}", felts.len())),
+        }
+    }
+}
+
+/// Return the storage addresses of the slots of a value stored at `address`.
+pub fn storage_slots<T: StorageValue>(address: FieldElement) -> Vec<FieldElement> {
+    (0..T::SLOTS as u64)
+        .map(|offset| address + FieldElement::from(offset))
+        .collect()
+}
+
+fn felt_to_u128_bytes(value: FieldElement) -> Result<[u8; 16]> {
+    let bytes = value.to_bytes_be();
+    if bytes[..16].iter().any(|byte| *byte != 0) {
+        return Err(eyre!("Value 0x{value:x} does not fit in 128 bits"));
+    }
+    let mut low = [0u8; 16];
+    low.copy_from_slice(&bytes[16..]);
+    Ok(low)
+}
@@ -1,6 +1,11 @@
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use starknet::core::{crypto::pedersen_hash, types::FieldElement};
+use starknet::core::{
+    crypto::pedersen_hash, types::FieldElement, utils::cairo_short_string_to_felt,
+};
+use starknet::providers::jsonrpc::JsonRpcError;
+use starknet_crypto::{poseidon_hash, poseidon_hash_many};
 
+use crate::stdlib::format;
 use crate::stdlib::vec::Vec;
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -10,6 +15,13 @@ pub struct Path {
     len: u8,
 }
 
+impl Path {
+    /// Create the path of an edge, `value` holding its `len` bits.
+    pub fn new(value: FieldElement, len: u8) -> Self {
+        Self { value, len }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Edge {
     pub path: Path,
@@ -26,20 +38,39 @@ pub struct Binary {
     pub right: FieldElement,
 }
 
+/// Hash function of the nodes of a Merkle-Patricia tree.
+///
+/// The contract tree and the storage trees use Pedersen, the class tree uses Poseidon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrieHash {
+    Pedersen,
+    Poseidon,
+}
+
+impl TrieHash {
+    /// Hash two field elements.
+    pub fn hash(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        match self {
+            TrieHash::Pedersen => pedersen_hash(left, right),
+            TrieHash::Poseidon => poseidon_hash(*left, *right),
+        }
+    }
+}
+
 impl Edge {
-    fn hash(&self) -> FieldElement {
+    fn hash(&self, trie_hash: TrieHash) -> FieldElement {
         let child_hash = self.child;
 
         // Length should be smaller than the maximum size of a stark hash.
         let length = FieldElement::from(self.path.len);
 
-        pedersen_hash(&child_hash, &self.path.value) + length
+        trie_hash.hash(&child_hash, &self.path.value) + length
     }
 }
 
 impl Binary {
-    fn hash(&self) -> FieldElement {
-        pedersen_hash(&self.left, &self.right)
+    fn hash(&self, trie_hash: TrieHash) -> FieldElement {
+        trie_hash.hash(&self.left, &self.right)
     }
 }
 
@@ -51,10 +82,10 @@ pub enum ProofNode {
 }
 
 impl ProofNode {
-    fn hash(&self) -> FieldElement {
+    fn hash(&self, trie_hash: TrieHash) -> FieldElement {
         match self {
-            ProofNode::Binary(bin) => bin.hash(),
-            ProofNode::Edge(edge) => edge.hash(),
+            ProofNode::Binary(bin) => bin.hash(trie_hash),
+            ProofNode::Edge(edge) => edge.hash(trie_hash),
         }
     }
 }
@@ -75,6 +106,53 @@ where
     serializer.serialize_str(&format!("0x{v:x}"))
 }
 
+/// Utility function to deserialize an optional FieldElement
+fn from_hex_deser_opt<'de, D>(deserializer: D) -> Result<Option<FieldElement>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<&str> = de::Deserialize::deserialize(deserializer)?;
+    s.map(|s| FieldElement::from_hex_be(s).map_err(de::Error::custom))
+        .transpose()
+}
+
+fn to_hex_ser_opt<S>(v: &Option<FieldElement>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match v {
+        Some(v) => to_hex_ser(v, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Return the global state root committing to the contract tree and the class tree.
+///
+/// Since StarkNet v0.11 the state root is `poseidon("STARKNET_STATE_V0", contracts_tree_root,
+/// classes_tree_root)`. It is the contract tree root itself as long as the class tree is empty.
+pub fn global_state_root(
+    contracts_tree_root: FieldElement,
+    classes_tree_root: FieldElement,
+) -> FieldElement {
+    if classes_tree_root == FieldElement::ZERO {
+        return contracts_tree_root;
+    }
+    poseidon_hash_many(&[
+        cairo_short_string_to_felt("STARKNET_STATE_V0").unwrap(),
+        contracts_tree_root,
+        classes_tree_root,
+    ])
+}
+
+/// Return the value of the class tree leaf of a class: `poseidon("CONTRACT_CLASS_LEAF_V0",
+/// compiled_class_hash)`.
+pub fn class_leaf_hash(compiled_class_hash: FieldElement) -> FieldElement {
+    poseidon_hash(
+        cairo_short_string_to_felt("CONTRACT_CLASS_LEAF_V0").unwrap(),
+        compiled_class_hash,
+    )
+}
+
 /// Holds the data and proofs for a specific contract.
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ContractData {
@@ -100,6 +178,25 @@ pub struct ContractData {
 /// Holds the membership/non-membership of a contract and its associated contract contract if the contract exists.
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GetProofOutput {
+    /// Root of the contract tree. When missing, it is the hash of the first node of the contract proof.
+    #[serde(
+        default,
+        deserialize_with = "from_hex_deser_opt",
+        serialize_with = "to_hex_ser_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contracts_tree_root: Option<FieldElement>,
+
+    /// Root of the class tree, only returned since StarkNet v0.11.
+    #[serde(
+        default,
+        alias = "class_commitment",
+        deserialize_with = "from_hex_deser_opt",
+        serialize_with = "to_hex_ser_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub classes_tree_root: Option<FieldElement>,
+
     /// Membership / Non-membership proof for the queried contract
     pub contract_proof: Vec<ProofNode>,
 
@@ -108,17 +205,69 @@ pub struct GetProofOutput {
 }
 
 impl GetProofOutput {
+    /// Verifies that the contract tree and class tree roots of the output commit to
+    /// `state_root`, and returns the contract tree root the contract proof is checked against.
+    ///
+    /// An output without a classes tree root is checked against the commitment used before
+    /// StarkNet v0.11, where the state root is the contract tree root.
+    pub fn verify_global_root(&self, state_root: FieldElement) -> Result<FieldElement, ProofError> {
+        let contracts_tree_root = match (self.contracts_tree_root, self.classes_tree_root) {
+            (Some(contracts_tree_root), _) => contracts_tree_root,
+            (None, None) => state_root,
+            (None, Some(_)) => self
+                .contract_proof
+                .first()
+                .map(|node| node.hash(TrieHash::Pedersen))
+                .ok_or(ProofError::MissingContractsTreeRoot)?,
+        };
+
+        let actual = global_state_root(
+            contracts_tree_root,
+            self.classes_tree_root.unwrap_or(FieldElement::ZERO),
+        );
+        if actual != state_root {
+            return Err(ProofError::GlobalRootMismatch {
+                expected: state_root,
+                actual,
+            });
+        }
+        Ok(contracts_tree_root)
+    }
+
     /// Verifies a full outpout (i.e, the contract proof and the storage proofs).
     /// The user is expected to provide `state_root`, `contract_address`, `storage_keys` and `storage_values`.
     /// Those values should come from a DIFFERENT source or else the proof verification would serve no purpose.
+    ///
+    /// Returns a `ProofError` if the contract proof itself is invalid, else a `VerificationReport`
+    /// holding the result of every storage proof.
+    ///
+    /// A contract that is not deployed has no contract data: its contract proof must then be a
+    /// non-membership proof, and every storage key is reported as a `NonMember` of its empty
+    /// storage.
     pub fn verify(
         &self,
         state_root: FieldElement,
         contract_address: FieldElement,
         storage_keys: &[FieldElement],
         storage_values: &[FieldElement],
-    ) -> Option<Vec<Option<Membership>>> {
-        let contract_data = self.contract_data.clone()?;
+    ) -> Result<VerificationReport, ProofError> {
+        let contracts_tree_root = self.verify_global_root(state_root)?;
+        let contract_key = felt_to_bits_be(contract_address);
+        let contract_data = match self.contract_data.clone() {
+            Some(contract_data) => contract_data,
+            None => {
+                return verify_contract_absence(
+                    ProofRequest::new(
+                        contracts_tree_root,
+                        &contract_key[contract_key.len() - 251..],
+                        FieldElement::ZERO,
+                        &self.contract_proof,
+                    ),
+                    storage_keys,
+                    storage_values,
+                )
+            }
+        };
         let class_hash = contract_data.class_hash;
         let contract_nonce = contract_data.nonce;
         let contract_root = contract_data.root;
@@ -129,9 +278,8 @@ impl GetProofOutput {
         let b = pedersen_hash(&a, &contract_nonce);
         let contract_state_hash = pedersen_hash(&b, &version);
 
-        let contract_key = felt_to_bits_be(contract_address);
         let contract_request = ProofRequest::new(
-            state_root,
+            contracts_tree_root,
             &contract_key[contract_key.len() - 251..],
             contract_state_hash,
             &self.contract_proof,
@@ -146,7 +294,11 @@ impl GetProofOutput {
         .windows(2)
         .all(|w| w[0] == w[1]);
         if !equal_lengths {
-            return None;
+            return Err(ProofError::LengthMismatch {
+                storage_keys: storage_keys.len(),
+                storage_values: storage_values.len(),
+                storage_proofs: contract_data.storage_proofs.len(),
+            });
         }
 
         // Allocate a vector of the correct size
@@ -169,14 +321,166 @@ impl GetProofOutput {
         // Verify the proofs
         verify_proof_requests(contract_request, &storage_requests)
     }
+
+    /// Return the value every storage proof leads to, in the order of `storage_keys`: the leaf
+    /// of a member key, zero for a key the proof shows to be absent.
+    ///
+    /// The values are read without any check, they only hold once `verify` accepted them.
+    pub fn storage_values(&self, storage_keys: &[FieldElement]) -> Vec<FieldElement> {
+        let storage_proofs = match &self.contract_data {
+            Some(contract_data) => &contract_data.storage_proofs[..],
+            // The storage of a contract that is not deployed is empty.
+            None => &[],
+        };
+
+        storage_keys
+            .iter()
+            .enumerate()
+            .map(|(i, storage_key)| match storage_proofs.get(i) {
+                Some(proof) => {
+                    let key = felt_to_bits_be(*storage_key);
+                    proven_leaf(proof, &key[key.len() - 251..])
+                }
+                None => FieldElement::ZERO,
+            })
+            .collect()
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
 pub enum Membership {
     Member,
     NonMember,
 }
 
+/// Proof of the membership/non-membership of a class in the class tree.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+pub struct GetClassProofOutput {
+    /// Membership / Non-membership proof for the queried class
+    pub class_proof: Vec<ProofNode>,
+}
+
+impl GetClassProofOutput {
+    /// Verifies the class proof of `class_hash` against a class tree root, the leaf of a
+    /// declared class committing to its `compiled_class_hash`.
+    ///
+    /// `classes_tree_root` should come from a `GetProofOutput` verified against the L1 state
+    /// root, see `GetProofOutput::verify_global_root`.
+    pub fn verify(
+        &self,
+        classes_tree_root: FieldElement,
+        class_hash: FieldElement,
+        compiled_class_hash: FieldElement,
+    ) -> Result<Membership, ProofError> {
+        let class_key = felt_to_bits_be(class_hash);
+        ProofRequest::new(
+            classes_tree_root,
+            &class_key[class_key.len() - 251..],
+            class_leaf_hash(compiled_class_hash),
+            &self.class_proof,
+        )
+        .with_hash(TrieHash::Poseidon)
+        .verify()
+    }
+}
+
+/// Reason a proof failed to verify.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProofError {
+    #[error("got {storage_keys} storage keys, {storage_values} storage values and {storage_proofs} storage proofs")]
+    LengthMismatch {
+        storage_keys: usize,
+        storage_values: usize,
+        storage_proofs: usize,
+    },
+    #[error("missing contract data")]
+    MissingContractData,
+    #[error("key of {0} bits is too short, expected at least 251 bits")]
+    KeyTooShort(usize),
+    #[error("hash mismatch at node {node}: expected 0x{expected:x}, got 0x{actual:x}")]
+    HashMismatch {
+        node: usize,
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+    #[error("proof node {node} runs past the end of the key")]
+    PathOverflow { node: usize },
+    #[error("proof ends {remaining} bits above the leaf")]
+    PathIncomplete { remaining: usize },
+    #[error("leaf mismatch: expected 0x{expected:x}, got 0x{actual:x}")]
+    LeafMismatch {
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+    #[error("contract is not a member of the contract tree")]
+    ContractNonMember,
+    #[error("missing contracts tree root")]
+    MissingContractsTreeRoot,
+    #[error("global root mismatch: expected 0x{expected:x}, got 0x{actual:x}")]
+    GlobalRootMismatch {
+        expected: FieldElement,
+        actual: FieldElement,
+    },
+}
+
+impl From<ProofError> for JsonRpcError {
+    fn from(err: ProofError) -> Self {
+        JsonRpcError {
+            code: 10001,
+            message: format!("Proof verification failed: {err}"),
+        }
+    }
+}
+
+/// Result of the verification of a `GetProofOutput` whose contract proof is valid.
+///
+/// `contract` is `NonMember` when the proof shows that the contract is not deployed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Membership of the contract in the contract tree.
+    pub contract: Membership,
+    /// Result of every storage proof, in the order of the requested keys.
+    pub storage: Vec<Result<Membership, ProofError>>,
+}
+
+impl VerificationReport {
+    /// Return the membership of every storage key, or the first storage proof error.
+    pub fn storage_memberships(self) -> Result<Vec<Membership>, ProofError> {
+        self.storage.into_iter().collect()
+    }
+}
+
+/// Follow `key` down the proof and return the leaf it reaches, or zero if an edge diverges from
+/// the key. Ill-formed proofs are left to `ProofRequest::verify` to reject.
+fn proven_leaf(proof: &[ProofNode], key: &[bool]) -> FieldElement {
+    let mut value = FieldElement::ZERO;
+    let mut remaining_path = key;
+
+    for proof_node in proof {
+        match proof_node {
+            ProofNode::Binary(bin) => match remaining_path.split_first() {
+                Some((direction, rest)) => {
+                    value = if *direction { bin.right } else { bin.left };
+                    remaining_path = rest;
+                }
+                None => return FieldElement::ZERO,
+            },
+            ProofNode::Edge(edge) => {
+                let len = edge.path.len as usize;
+                if len > remaining_path.len()
+                    || !path_matches(edge.path.value, &remaining_path[..len])
+                {
+                    return FieldElement::ZERO;
+                }
+                value = edge.child;
+                remaining_path = &remaining_path[len..];
+            }
+        }
+    }
+    value
+}
+
 /// Verifies that `value` and `remaining_path` share the same bits
 fn path_matches(value: FieldElement, remaining_path: &[bool]) -> bool {
     let bits = felt_to_bits_be(value);
@@ -184,7 +488,7 @@ fn path_matches(value: FieldElement, remaining_path: &[bool]) -> bool {
 }
 
 /// Utility function to convert a [`FieldElement`] to a big endian bit representation
-fn felt_to_bits_be(value: FieldElement) -> [bool; 256] {
+pub(crate) fn felt_to_bits_be(value: FieldElement) -> [bool; 256] {
     let mut bits = value.to_bits_le();
     bits.reverse();
     bits
@@ -196,6 +500,7 @@ pub struct ProofRequest<'a> {
     key: &'a [bool],
     value: FieldElement,
     proof: &'a [ProofNode],
+    trie_hash: TrieHash,
 }
 
 impl<'a> ProofRequest<'a> {
@@ -211,30 +516,50 @@ impl<'a> ProofRequest<'a> {
             key,
             value,
             proof,
+            trie_hash: TrieHash::Pedersen,
         }
     }
 
-    /// Verifies the proof request. Returns `None` if there's a hash mismatch or
-    /// if the key is too small; else returns a `Membership` variant.
-    fn verify(&self) -> Option<Membership> {
+    /// Set the hash function of the tree, Pedersen by default.
+    pub fn with_hash(mut self, trie_hash: TrieHash) -> Self {
+        self.trie_hash = trie_hash;
+        self
+    }
+
+    /// Verifies the proof request. Returns a `ProofError` if there's a hash mismatch, if
+    /// the key is too small or if the proof does not follow the key down to a leaf; else
+    /// returns a `Membership` variant.
+    fn verify(&self) -> Result<Membership, ProofError> {
         // Protect from ill-formed keys
         if self.key.len() < 251 {
-            return None;
+            return Err(ProofError::KeyTooShort(self.key.len()));
+        }
+
+        // No key is a member of an empty tree
+        if self.proof.is_empty() && self.root == FieldElement::ZERO {
+            return Ok(Membership::NonMember);
         }
 
         let mut expected_hash = self.root;
         let mut remaining_path = self.key;
 
-        for proof_node in self.proof.iter() {
-            // Hash mismatch? Return None.
-            if proof_node.hash() != expected_hash {
-                return None;
+        for (node, proof_node) in self.proof.iter().enumerate() {
+            let actual_hash = proof_node.hash(self.trie_hash);
+            if actual_hash != expected_hash {
+                return Err(ProofError::HashMismatch {
+                    node,
+                    expected: expected_hash,
+                    actual: actual_hash,
+                });
             }
             match proof_node {
                 ProofNode::Binary(bin) => {
                     // Direction will always correspond to the 0th index
                     // because we're removing bits on every iteration.
-                    let direction = remaining_path[0];
+                    let direction = match remaining_path.first() {
+                        Some(direction) => *direction,
+                        None => return Err(ProofError::PathOverflow { node }),
+                    };
 
                     // Set the next hash to be the left or right hash,
                     // depending on the direction
@@ -247,32 +572,75 @@ impl<'a> ProofRequest<'a> {
                     remaining_path = &remaining_path[1..];
                 }
                 ProofNode::Edge(edge) => {
-                    let path_matches =
-                        path_matches(edge.path.value, &remaining_path[..edge.path.len as usize]);
+                    let len = edge.path.len as usize;
+                    if len > remaining_path.len() {
+                        return Err(ProofError::PathOverflow { node });
+                    }
+                    let path_matches = path_matches(edge.path.value, &remaining_path[..len]);
                     if !path_matches {
                         // If paths don't match, we've found a proof of non membership because we:
                         // 1. Correctly moved towards the target insofar as is possible, and
                         // 2. hashing all the nodes along the path does result in the root hash, which means
                         // 3. the target definitely does not exist in this tree
-                        return Some(Membership::NonMember);
+                        return Ok(Membership::NonMember);
                     }
 
                     // Set the next hash to the child's hash
                     expected_hash = edge.child;
 
                     // Advance by the whole edge path
-                    remaining_path = &remaining_path[edge.path.len as usize..];
+                    remaining_path = &remaining_path[len..];
                 }
             }
         }
 
+        // A proof stopping above the leaf would let an internal node pass for the value
+        if !remaining_path.is_empty() {
+            return Err(ProofError::PathIncomplete {
+                remaining: remaining_path.len(),
+            });
+        }
+
         // At this point, we should reach `value` !
         if expected_hash == self.value {
-            Some(Membership::Member)
+            Ok(Membership::Member)
         } else {
-            // Hash mismatch. Return `None`.
-            None
+            Err(ProofError::LeafMismatch {
+                expected: expected_hash,
+                actual: self.value,
+            })
+        }
+    }
+}
+
+/// Verifies that the contract of a proof request without contract data is not deployed.
+fn verify_contract_absence(
+    contract_request: ProofRequest,
+    storage_keys: &[FieldElement],
+    storage_values: &[FieldElement],
+) -> Result<VerificationReport, ProofError> {
+    if storage_keys.len() != storage_values.len() {
+        return Err(ProofError::LengthMismatch {
+            storage_keys: storage_keys.len(),
+            storage_values: storage_values.len(),
+            storage_proofs: 0,
+        });
+    }
+
+    match contract_request.verify() {
+        // The storage of a contract that is not deployed is empty.
+        Ok(Membership::NonMember) => Ok(VerificationReport {
+            contract: Membership::NonMember,
+            storage: storage_keys
+                .iter()
+                .map(|_| Ok(Membership::NonMember))
+                .collect(),
+        }),
+        // The proof reaches a leaf, so the contract data bound to it is required.
+        Ok(Membership::Member) | Err(ProofError::LeafMismatch { .. }) => {
+            Err(ProofError::MissingContractData)
         }
+        Err(e) => Err(e),
     }
 }
 
@@ -280,19 +648,19 @@ impl<'a> ProofRequest<'a> {
 fn verify_proof_requests(
     contract_request: ProofRequest,
     storage_requests: &[ProofRequest],
-) -> Option<Vec<Option<Membership>>> {
+) -> Result<VerificationReport, ProofError> {
     // Verify the contract proof
-    let contract_verified = contract_request.verify()?;
+    let contract = contract_request.verify()?;
 
-    // Return None if it's invalid
-    if contract_verified == Membership::NonMember {
-        return None;
+    // A contract absent from the contract tree cannot hold the given contract data
+    if contract == Membership::NonMember {
+        return Err(ProofError::ContractNonMember);
     }
 
     // Verify Storage Proofs
-    let mut res = vec![];
-    for request in storage_requests {
-        res.push(request.verify());
-    }
-    Some(res)
+    let storage = storage_requests
+        .iter()
+        .map(|request| request.verify())
+        .collect();
+    Ok(VerificationReport { contract, storage })
 }
@@ -0,0 +1,145 @@
+use starknet::{
+    core::{
+        crypto::compute_hash_on_elements,
+        types::{DeclareTransaction, FieldElement, InvokeTransaction, Transaction},
+        utils::get_contract_address,
+    },
+    macros::selector,
+};
+
+use crate::stdlib::vec::Vec;
+
+/// Selector of the `constructor` entry point, used by deploy transactions.
+const CONSTRUCTOR_ENTRY_POINT_SELECTOR: FieldElement = selector!("constructor");
+
+/// Compute the hash of a transaction from its fields.
+///
+/// This is the transaction hash of StarkNet blocks since v0.8.0, see `may_have_legacy_hash`
+/// for transactions from older blocks.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction.
+/// * `chain_id` - The StarkNet chain id.
+pub fn calculate_transaction_hash(
+    transaction: &Transaction,
+    chain_id: FieldElement,
+) -> FieldElement {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => compute_hash_on_elements(&[
+            short_string("invoke"),
+            FieldElement::ZERO,
+            tx.contract_address,
+            tx.entry_point_selector,
+            compute_hash_on_elements(&tx.calldata),
+            tx.max_fee,
+            chain_id,
+        ]),
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => compute_hash_on_elements(&[
+            short_string("invoke"),
+            FieldElement::ONE,
+            tx.sender_address,
+            FieldElement::ZERO,
+            compute_hash_on_elements(&tx.calldata),
+            tx.max_fee,
+            chain_id,
+            tx.nonce,
+        ]),
+        Transaction::L1Handler(tx) => compute_hash_on_elements(&[
+            short_string("l1_handler"),
+            FieldElement::from(tx.version),
+            tx.contract_address,
+            tx.entry_point_selector,
+            compute_hash_on_elements(&tx.calldata),
+            FieldElement::ZERO,
+            chain_id,
+            FieldElement::from(tx.nonce),
+        ]),
+        Transaction::Declare(DeclareTransaction::V0(tx)) => compute_hash_on_elements(&[
+            short_string("declare"),
+            FieldElement::ZERO,
+            tx.sender_address,
+            FieldElement::ZERO,
+            compute_hash_on_elements(&[]),
+            tx.max_fee,
+            chain_id,
+            tx.class_hash,
+        ]),
+        Transaction::Declare(DeclareTransaction::V1(tx)) => compute_hash_on_elements(&[
+            short_string("declare"),
+            FieldElement::ONE,
+            tx.sender_address,
+            FieldElement::ZERO,
+            compute_hash_on_elements(&[tx.class_hash]),
+            tx.max_fee,
+            chain_id,
+            tx.nonce,
+        ]),
+        Transaction::Declare(DeclareTransaction::V2(tx)) => compute_hash_on_elements(&[
+            short_string("declare"),
+            FieldElement::TWO,
+            tx.sender_address,
+            FieldElement::ZERO,
+            compute_hash_on_elements(&[tx.class_hash]),
+            tx.max_fee,
+            chain_id,
+            tx.nonce,
+            tx.compiled_class_hash,
+        ]),
+        Transaction::Deploy(tx) => compute_hash_on_elements(&[
+            short_string("deploy"),
+            FieldElement::from(tx.version),
+            get_contract_address(
+                tx.contract_address_salt,
+                tx.class_hash,
+                &tx.constructor_calldata,
+                FieldElement::ZERO,
+            ),
+            CONSTRUCTOR_ENTRY_POINT_SELECTOR,
+            compute_hash_on_elements(&tx.constructor_calldata),
+            FieldElement::ZERO,
+            chain_id,
+        ]),
+        Transaction::DeployAccount(tx) => {
+            let mut calldata = Vec::with_capacity(tx.constructor_calldata.len() + 2);
+            calldata.push(tx.class_hash);
+            calldata.push(tx.contract_address_salt);
+            calldata.extend_from_slice(&tx.constructor_calldata);
+
+            compute_hash_on_elements(&[
+                short_string("deploy_account"),
+                FieldElement::ONE,
+                get_contract_address(
+                    tx.contract_address_salt,
+                    tx.class_hash,
+                    &tx.constructor_calldata,
+                    FieldElement::ZERO,
+                ),
+                FieldElement::ZERO,
+                compute_hash_on_elements(&calldata),
+                tx.max_fee,
+                chain_id,
+                tx.nonce,
+            ])
+        }
+    }
+}
+
+/// Return `true` if the transaction may have been hashed before StarkNet v0.8.0, with a formula
+/// `calculate_transaction_hash` does not support.
+///
+/// Invoke v0, declare v0, deploy and L1 handler v0 transactions all date back to those blocks.
+pub fn may_have_legacy_hash(transaction: &Transaction) -> bool {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(_))
+        | Transaction::Declare(DeclareTransaction::V0(_))
+        | Transaction::Deploy(_) => true,
+        Transaction::L1Handler(tx) => tx.version == 0,
+        _ => false,
+    }
+}
+
+/// Encode a Cairo short string, used for the transaction hash prefixes.
+fn short_string(value: &str) -> FieldElement {
+    FieldElement::from_byte_slice_be(value.as_bytes()).unwrap()
+}
@@ -0,0 +1,222 @@
+use crate::stdlib::boxed::Box;
+use crate::stdlib::collections::BTreeMap;
+use crate::stdlib::vec::Vec;
+
+use super::{
+    beerus::{u256_to_felt, ProvenState},
+    ethereum::EthereumLightClient,
+};
+use ethers::{
+    abi::{Abi, RawLog},
+    types::{Filter, Log, H160},
+};
+use eyre::{eyre, Result};
+use futures::stream::{self, Stream};
+use starknet::core::types::FieldElement;
+use tokio::sync::Mutex;
+
+/// Name of the StarkNet core contract event emitted on every L1 state update.
+pub const LOG_STATE_UPDATE_EVENT: &str = "LogStateUpdate";
+/// Number of L1 blocks queried per `get_logs` call.
+/// Kept small since the Ethereum light client limits the number of logs it can verify per call.
+pub const LOG_STATE_UPDATE_BLOCK_RANGE: u64 = 100;
+/// Number of L1 blocks indexed backwards on the first synchronization (~1 day of Ethereum blocks).
+pub const STATE_HISTORY_LOOKBACK_L1_BLOCKS: u64 = 7200;
+/// Maximum number of proven states kept in the history.
+pub const MAX_STATE_HISTORY_ENTRIES: usize = 1024;
+
+/// History of the StarkNet states proven on L1, indexed by StarkNet block number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateHistory {
+    states: BTreeMap<u64, ProvenState>,
+    last_indexed_l1_block: Option<u64>,
+}
+
+impl StateHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a proven state, evicting the oldest entries once the history is full.
+    pub fn insert(&mut self, proven_state: ProvenState) {
+        self.states.insert(proven_state.block_number, proven_state);
+        while self.states.len() > MAX_STATE_HISTORY_ENTRIES {
+            let oldest = *self.states.keys().next().unwrap();
+            self.states.remove(&oldest);
+        }
+    }
+
+    /// Return the proven state of the given StarkNet block, if it is known.
+    pub fn get(&self, block_number: u64) -> Option<&ProvenState> {
+        self.states.get(&block_number)
+    }
+
+    /// Return the proven state of the block with the given block hash, if indexed.
+    pub fn find_by_block_hash(&self, block_hash: FieldElement) -> Option<&ProvenState> {
+        self.states
+            .values()
+            .find(|proven_state| proven_state.block_hash == block_hash)
+    }
+
+    /// Return the most recent proven state before the given StarkNet block, if any.
+    pub fn previous(&self, block_number: u64) -> Option<&ProvenState> {
+        self.states
+            .range(..block_number)
+            .next_back()
+            .map(|(_, state)| state)
+    }
+
+    /// Return the most recent proven state, if any.
+    pub fn latest(&self) -> Option<&ProvenState> {
+        self.states.values().next_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Return the last L1 block whose `LogStateUpdate` events have been indexed.
+    pub fn last_indexed_l1_block(&self) -> Option<u64> {
+        self.last_indexed_l1_block
+    }
+
+    /// Return the first L1 block to index in order to catch up with `to_l1_block`.
+    ///
+    /// The first synchronization starts `STATE_HISTORY_LOOKBACK_L1_BLOCKS` before `to_l1_block`.
+    pub fn next_l1_block(&self, to_l1_block: u64) -> u64 {
+        match self.last_indexed_l1_block {
+            Some(l1_block) => l1_block + 1,
+            None => to_l1_block.saturating_sub(STATE_HISTORY_LOOKBACK_L1_BLOCKS),
+        }
+    }
+
+    /// Insert the proven states of already fetched `LogStateUpdate` events and move the
+    /// indexing cursor to `to_l1_block`.
+    pub fn extend(&mut self, proven_states: Vec<ProvenState>, to_l1_block: u64) {
+        for proven_state in proven_states {
+            self.insert(proven_state);
+        }
+        if self.last_indexed_l1_block < Some(to_l1_block) {
+            self.last_indexed_l1_block = Some(to_l1_block);
+        }
+    }
+}
+
+/// Decode a `LogStateUpdate` event of the StarkNet core contract.
+///
+/// # Arguments
+///
+/// * `starknet_core_abi` - The StarkNet core contract ABI.
+/// * `log` - The log to decode.
+///
+/// # Returns
+///
+/// Returns `Ok(ProvenState)` if the operation was successful, or an `Err(eyre::Report)` if the
+/// log is not a `LogStateUpdate` event or has no L1 block number.
+pub fn decode_log_state_update(starknet_core_abi: &Abi, log: &Log) -> Result<ProvenState> {
+    let event = starknet_core_abi.event(LOG_STATE_UPDATE_EVENT)?;
+    let raw_log = RawLog {
+        topics: log.topics.clone(),
+        data: log.data.to_vec(),
+    };
+    let parsed_log = event.parse_log(raw_log)?;
+
+    let param = |name: &str| {
+        parsed_log
+            .params
+            .iter()
+            .find(|param| param.name == name)
+            .map(|param| param.value.clone())
+            .ok_or_else(|| eyre!("Missing {name} in {LOG_STATE_UPDATE_EVENT} event"))
+    };
+
+    let global_root = param("globalRoot")?
+        .into_uint()
+        .ok_or_else(|| eyre!("Invalid globalRoot in {LOG_STATE_UPDATE_EVENT} event"))?;
+    let block_number = param("blockNumber")?
+        .into_int()
+        .ok_or_else(|| eyre!("Invalid blockNumber in {LOG_STATE_UPDATE_EVENT} event"))?;
+    let block_hash = param("blockHash")?
+        .into_uint()
+        .ok_or_else(|| eyre!("Invalid blockHash in {LOG_STATE_UPDATE_EVENT} event"))?;
+    let l1_block = log
+        .block_number
+        .ok_or_else(|| eyre!("Missing L1 block number in {LOG_STATE_UPDATE_EVENT} event"))?;
+
+    Ok(ProvenState {
+        block_number: block_number.low_u64(),
+        state_root: u256_to_felt(global_root)?,
+        block_hash: u256_to_felt(block_hash)?,
+        l1_block: l1_block.as_u64(),
+    })
+}
+
+/// Fetch and decode the `LogStateUpdate` events emitted by the StarkNet core contract between
+/// `from_l1_block` and `to_l1_block` (both included).
+///
+/// Logs are queried in windows of `LOG_STATE_UPDATE_BLOCK_RANGE` blocks, and the Ethereum light
+/// client is only locked while a window is queried. The stream yields the proven states of each
+/// window along with its last L1 block, and ends after the first error.
+///
+/// # Arguments
+///
+/// * `ethereum_lightclient` - The Ethereum light client.
+/// * `starknet_core_contract_address` - The StarkNet core contract address.
+/// * `starknet_core_abi` - The StarkNet core contract ABI.
+/// * `from_l1_block` - The first L1 block to query.
+/// * `to_l1_block` - The last L1 block to query.
+pub fn fetch_state_updates<'a>(
+    ethereum_lightclient: &'a Mutex<Box<dyn EthereumLightClient>>,
+    starknet_core_contract_address: H160,
+    starknet_core_abi: &'a Abi,
+    from_l1_block: u64,
+    to_l1_block: u64,
+) -> impl Stream<Item = Result<(Vec<ProvenState>, u64)>> + 'a {
+    stream::unfold(Some(from_l1_block), move |window_start| async move {
+        let window_start = window_start.filter(|window_start| *window_start <= to_l1_block)?;
+        let window_end = to_l1_block.min(window_start + LOG_STATE_UPDATE_BLOCK_RANGE - 1);
+        let proven_states = {
+            let ethereum_lightclient = ethereum_lightclient.lock().await;
+            fetch_window(
+                &**ethereum_lightclient,
+                starknet_core_contract_address,
+                starknet_core_abi,
+                window_start,
+                window_end,
+            )
+            .await
+        };
+        let next_window_start = proven_states.is_ok().then_some(window_end + 1);
+        Some((
+            proven_states.map(|proven_states| (proven_states, window_end)),
+            next_window_start,
+        ))
+    })
+}
+
+/// Fetch and decode the `LogStateUpdate` events of a single window of L1 blocks.
+async fn fetch_window(
+    ethereum_lightclient: &dyn EthereumLightClient,
+    starknet_core_contract_address: H160,
+    starknet_core_abi: &Abi,
+    from_l1_block: u64,
+    to_l1_block: u64,
+) -> Result<Vec<ProvenState>> {
+    let topic = starknet_core_abi.event(LOG_STATE_UPDATE_EVENT)?.signature();
+    let filter = Filter::new()
+        .address(starknet_core_contract_address)
+        .topic0(topic)
+        .from_block(from_l1_block)
+        .to_block(to_l1_block);
+
+    ethereum_lightclient
+        .get_logs(&filter)
+        .await?
+        .iter()
+        .map(|log| decode_log_state_update(starknet_core_abi, log))
+        .collect()
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use ethers::{
+    abi::Abi,
+    types::{SyncingStatus, H160},
+};
+use eyre::Result;
+use log::{debug, error, info, warn};
+use starknet::core::types::{BlockId, BlockTag, MaybePendingBlockWithTxs};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+    time::{self, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::stdlib::{boxed::Box, sync::Arc};
+
+use super::{
+    backfill::track_tip,
+    beerus::{fetch_proven_state, index_state_history, NodeData},
+    ethereum::EthereumLightClient,
+    starknet::StarkNetLightClient,
+};
+
+/// Maximum delay between two synchronization rounds after consecutive failures.
+pub const MAX_SYNC_BACKOFF_SECS: u64 = 300;
+
+/// Clients and node data shared between the light client and its sync loop.
+#[derive(Clone)]
+pub struct SyncContext {
+    pub ethereum_lightclient: Arc<Mutex<Box<dyn EthereumLightClient>>>,
+    pub starknet_lightclient: Arc<Box<dyn StarkNetLightClient>>,
+    pub node: Arc<RwLock<NodeData>>,
+    pub starknet_core_abi: Abi,
+    pub starknet_core_contract_address: H160,
+    /// Delay between two synchronization rounds.
+    pub poll_interval: Duration,
+    /// Number of blocks fetched concurrently when filling blocks skipped between two rounds.
+    pub backfill_concurrency: usize,
+}
+
+/// Handle of a running sync loop, used to stop it.
+#[derive(Debug)]
+pub struct SyncHandle {
+    cancellation: CancellationToken,
+    join_handle: JoinHandle<()>,
+}
+
+impl SyncHandle {
+    /// Return a token that stops the sync loop once cancelled.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    /// Return `true` if the sync loop has exited.
+    pub fn is_finished(&self) -> bool {
+        self.join_handle.is_finished()
+    }
+
+    /// Stop the sync loop and wait for the current round to complete.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` once the loop has exited, or an `Err(eyre::Report)` if the sync task
+    /// panicked.
+    pub async fn stop(self) -> Result<()> {
+        self.cancellation.cancel();
+        self.join_handle.await?;
+        Ok(())
+    }
+}
+
+/// Spawn the sync loop on the tokio runtime.
+///
+/// The loop runs a synchronization round every `poll_interval` until the returned handle is
+/// stopped. A failed round is retried after an exponential backoff instead of aborting the loop.
+pub fn spawn(context: SyncContext) -> SyncHandle {
+    let cancellation = CancellationToken::new();
+    let join_handle = tokio::spawn(run(context, cancellation.clone()));
+    SyncHandle {
+        cancellation,
+        join_handle,
+    }
+}
+
+async fn run(context: SyncContext, cancellation: CancellationToken) {
+    let mut interval = time::interval(context.poll_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut failures = 0;
+
+    loop {
+        tokio::select! {
+            _ = cancellation.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+
+        // Let an in-flight round complete so that the node data is never left half updated.
+        let result = sync_once(&context).await;
+        context.node.write().await.sync.record_round(&result);
+        match result {
+            Ok(()) => failures = 0,
+            Err(err) => {
+                failures += 1;
+                let delay = backoff(context.poll_interval, failures);
+                error!("Sync round failed ({failures} in a row), retrying in {delay:?}: {err}");
+                tokio::select! {
+                    _ = cancellation.cancelled() => break,
+                    _ = time::sleep(delay) => interval.reset(),
+                }
+            }
+        }
+    }
+    info!("Sync loop stopped");
+}
+
+/// Run a single synchronization round: check the Ethereum sync status, refresh the state proven
+/// on L1, index the state history, fetch the latest StarkNet block and the blocks skipped since
+/// the previous round.
+///
+/// The progress of every step is recorded in the node sync state.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the operation was successful, or an `Err(eyre::Report)` if the proven
+/// state or the latest block could not be fetched.
+pub async fn sync_once(context: &SyncContext) -> Result<()> {
+    // Hold the lock for the whole snapshot so that it is read from a single L1 block, but never
+    // while locking the node data: readers lock them the other way around.
+    let (l1_synced, proven_state) = {
+        let ethereum_lightclient = context.ethereum_lightclient.lock().await;
+        let l1_synced = matches!(
+            ethereum_lightclient.syncing().await?,
+            SyncingStatus::IsFalse
+        );
+        let proven_state = fetch_proven_state(
+            &**ethereum_lightclient,
+            context.starknet_core_contract_address,
+            &context.starknet_core_abi,
+        )
+        .await;
+        (l1_synced, proven_state)
+    };
+    context.node.write().await.sync.record_l1_synced(l1_synced);
+    let proven_state = proven_state?;
+    info!("State Root: {}", proven_state.state_root);
+    info!("Block Number: {}", proven_state.block_number);
+
+    let l1_block = proven_state.l1_block;
+    {
+        let mut data = context.node.write().await;
+        data.state_history.insert(proven_state.clone());
+        data.sync.record_proven_state(&proven_state);
+        data.proven_state = Some(proven_state);
+    }
+
+    // The history only serves reads at older blocks, a failure must not hold back the tip.
+    if let Err(err) = index_state_history(
+        &context.ethereum_lightclient,
+        &context.node,
+        context.starknet_core_contract_address,
+        &context.starknet_core_abi,
+        l1_block,
+    )
+    .await
+    {
+        error!("Error indexing state history: {}", err);
+    }
+
+    let block = context
+        .starknet_lightclient
+        .get_block_with_txs(&BlockId::Tag(BlockTag::Latest))
+        .await?;
+    debug!("block: {block:?}");
+
+    match block {
+        MaybePendingBlockWithTxs::Block(block) => {
+            context
+                .node
+                .write()
+                .await
+                .sync
+                .record_l2_tip(block.block_number, block.block_hash);
+            track_tip(
+                &**context.starknet_lightclient,
+                &context.node,
+                block,
+                context.backfill_concurrency,
+            )
+            .await;
+        }
+        MaybePendingBlockWithTxs::PendingBlock(_) => {
+            warn!("Pending Block");
+        }
+    }
+    Ok(())
+}
+
+/// Return the delay before the next synchronization round after `failures` consecutive
+/// failures: the poll interval doubled on every failure, capped to `MAX_SYNC_BACKOFF_SECS`.
+///
+/// The delay is never shorter than the poll interval itself.
+pub fn backoff(poll_interval: Duration, failures: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failures);
+    poll_interval
+        .saturating_mul(factor)
+        .min(Duration::from_secs(MAX_SYNC_BACKOFF_SECS))
+        .max(poll_interval)
+}
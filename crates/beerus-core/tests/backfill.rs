@@ -0,0 +1,497 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod tests {
+    use beerus_core::lightclient::{
+        backfill::{check_continuity, missing_blocks, track_tip, ChainReorg, Reorg},
+        beerus::{NodeData, SyncBlock},
+        block_cache::{BlockCache, BlockRetention},
+        starknet::MockStarkNetLightClient,
+    };
+    use starknet::core::types::{
+        BlockId, BlockStatus, BlockWithTxs, FieldElement, MaybePendingBlockWithTxs,
+    };
+    use starknet::providers::jsonrpc::JsonRpcError;
+    use tokio::sync::RwLock;
+
+    /// Return a block linked to the block before it: its parent hash is the hash of `block(n - 1)`.
+    fn block(block_number: u64) -> BlockWithTxs {
+        BlockWithTxs {
+            status: BlockStatus::AcceptedOnL2,
+            block_hash: FieldElement::from(block_number + 100),
+            parent_hash: FieldElement::from(block_number + 99),
+            block_number,
+            new_root: FieldElement::from(block_number),
+            timestamp: block_number,
+            sequencer_address: FieldElement::ZERO,
+            transactions: vec![],
+        }
+    }
+
+    /// Return a block of the canonical chain forked after block 8.
+    fn forked_block(block_number: u64) -> BlockWithTxs {
+        let mut block = block(block_number);
+        block.block_hash = FieldElement::from(block_number + 200);
+        if block_number > 9 {
+            block.parent_hash = FieldElement::from(block_number + 199);
+        }
+        block
+    }
+
+    fn sync_block(block: &BlockWithTxs) -> SyncBlock {
+        SyncBlock {
+            block_number: block.block_number,
+            block_hash: block.block_hash,
+        }
+    }
+
+    fn cache(block_numbers: &[u64]) -> BlockCache {
+        let mut cache = BlockCache::default();
+        for block_number in block_numbers {
+            cache.insert(block(*block_number)).unwrap();
+        }
+        cache
+    }
+
+    /// Return node data tracking `block_number`, with the given blocks cached.
+    fn node(block_number: u64, cached: &[u64]) -> RwLock<NodeData> {
+        let mut data = NodeData::new();
+        data.set_payload(cache(cached));
+        data.block_number = block_number;
+        RwLock::new(data)
+    }
+
+    /// Test that the blocks skipped since the last tracked block are missing, except the cached ones.
+    #[test]
+    fn given_gap_when_missing_blocks_then_uncached_blocks_are_returned() {
+        let cache = cache(&[10, 12]);
+
+        assert_eq!(missing_blocks(&cache, 10, 15), vec![11, 13, 14]);
+        assert!(missing_blocks(&cache, 10, 11).is_empty());
+        // Nothing is tracked yet, the gap is not backfilled.
+        assert!(missing_blocks(&cache, 0, 15).is_empty());
+    }
+
+    /// Test that the missing blocks are limited to the blocks the cache retains alongside the tip.
+    #[test]
+    fn given_gap_larger_than_retention_when_missing_blocks_then_oldest_are_skipped() {
+        let cache = BlockCache::new(BlockRetention {
+            max_blocks: 3,
+            max_bytes: None,
+        });
+
+        assert_eq!(missing_blocks(&cache, 1, 100), vec![98, 99]);
+    }
+
+    /// Test that a block whose parent hash does not match the cached block before it is flagged.
+    #[test]
+    fn given_broken_linkage_when_check_continuity_then_reorg_is_flagged() {
+        let mut cache = cache(&[1, 2, 4]);
+        let mut forked = block(3);
+        forked.parent_hash = FieldElement::from(42u64);
+        cache.insert(forked).unwrap();
+
+        assert_eq!(
+            check_continuity(&cache, 1, 4),
+            vec![Reorg {
+                block_number: 3,
+                parent_hash: FieldElement::from(42u64),
+                expected_parent_hash: block(2).block_hash,
+            }]
+        );
+        assert!(check_continuity(&cache, 4, 4).is_empty());
+    }
+
+    /// Test that tracking a new tip fetches and caches the skipped blocks.
+    #[tokio::test]
+    async fn given_skipped_blocks_when_track_tip_then_gap_is_filled() {
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(3)
+            .returning(|block_id| match block_id {
+                BlockId::Number(block_number) => {
+                    Ok(MaybePendingBlockWithTxs::Block(block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(10, &[10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(14), 2).await;
+
+        assert_eq!(report.filled, vec![11, 12, 13]);
+        assert!(report.failed.is_empty());
+        assert!(report.reorgs.is_empty());
+        let data = node.read().await;
+        assert_eq!(data.block_number, 14);
+        assert_eq!(data.state_root, block(14).new_root.to_string());
+        assert_eq!(
+            data.payload
+                .values()
+                .map(|block| block.block_number)
+                .collect::<Vec<_>>(),
+            vec![10, 11, 12, 13, 14]
+        );
+    }
+
+    /// Test that skipped blocks which cannot be fetched are reported and only the blocks below
+    /// the first failure are tracked.
+    #[tokio::test]
+    async fn given_fetch_error_when_track_tip_then_block_is_reported_failed() {
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(2)
+            .returning(|block_id| match block_id {
+                BlockId::Number(12) => Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                }),
+                BlockId::Number(block_number) => {
+                    Ok(MaybePendingBlockWithTxs::Block(block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(10, &[10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(13), 4).await;
+
+        assert_eq!(report.filled, vec![11]);
+        assert_eq!(report.failed, vec![12]);
+        let data = node.read().await;
+        assert_eq!(data.block_number, 11);
+        assert_eq!(data.state_root, block(11).new_root.to_string());
+        assert!(data.payload.get(12).is_none());
+        assert!(data.payload.get(13).is_none());
+    }
+
+    /// Test that a failure right after the last tracked block tracks nothing, and that the
+    /// failed block is fetched again and checked against its parent on the next round.
+    #[tokio::test]
+    async fn given_fetch_error_after_last_block_when_track_tip_then_block_is_retried() {
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .returning(|_block_id| {
+                Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                })
+            });
+        let node = node(10, &[10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(12), 4).await;
+
+        assert!(report.filled.is_empty());
+        assert_eq!(report.failed, vec![11]);
+        assert_eq!(node.read().await.block_number, 10);
+        assert!(node.read().await.payload.get(12).is_none());
+
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(2)
+            .returning(|block_id| match block_id {
+                // Block 10 was reorganized while block 11 could not be fetched.
+                BlockId::Number(10) => {
+                    let mut block = block(10);
+                    block.block_hash = FieldElement::from(42u64);
+                    Ok(MaybePendingBlockWithTxs::Block(block))
+                }
+                BlockId::Number(11) => {
+                    let mut block = block(11);
+                    block.parent_hash = FieldElement::from(42u64);
+                    Ok(MaybePendingBlockWithTxs::Block(block))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(12), 4).await;
+
+        assert_eq!(report.filled, vec![11]);
+        assert_eq!(
+            report.rollback.unwrap().orphaned,
+            vec![sync_block(&block(10))]
+        );
+        assert_eq!(node.read().await.block_number, 12);
+    }
+
+    /// Test that a break in the linkage of the backfilled blocks is rolled back like a reorg of
+    /// the last tracked block: the blocks below the break are replaced by the canonical chain.
+    #[tokio::test]
+    async fn given_backfilled_blocks_on_another_branch_when_track_tip_then_cache_is_repaired() {
+        // Block 11 was reorganized between the fetches of blocks 11 and 12.
+        let mut fetched_11 = false;
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(3)
+            .returning(move |block_id| match block_id {
+                BlockId::Number(11) if fetched_11 => {
+                    let mut block = block(11);
+                    block.block_hash = FieldElement::from(42u64);
+                    Ok(MaybePendingBlockWithTxs::Block(block))
+                }
+                BlockId::Number(12) => {
+                    let mut block = block(12);
+                    block.parent_hash = FieldElement::from(42u64);
+                    Ok(MaybePendingBlockWithTxs::Block(block))
+                }
+                BlockId::Number(block_number) => {
+                    fetched_11 |= *block_number == 11;
+                    Ok(MaybePendingBlockWithTxs::Block(block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(10, &[10]);
+        let mut reorgs = node.read().await.reorg_notifier.subscribe();
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(13), 1).await;
+
+        let expected_reorg = ChainReorg {
+            fork_point: Some(sync_block(&block(10))),
+            orphaned: vec![sync_block(&block(11))],
+            tip: sync_block(&block(13)),
+        };
+        assert_eq!(report.rollback, Some(expected_reorg.clone()));
+        assert!(report.reorgs.is_empty());
+        assert_eq!(reorgs.try_recv().unwrap(), expected_reorg);
+        let data = node.read().await;
+        assert_eq!(data.block_number, 13);
+        assert_eq!(
+            data.payload.get(11).unwrap().block_hash,
+            FieldElement::from(42u64)
+        );
+        assert_eq!(data.sync.reorg_count, 1);
+    }
+
+    /// Test that a break in the linkage of the backfilled blocks whose fork point cannot be
+    /// fetched is flagged, and that the blocks above it are fetched again on the next round.
+    #[tokio::test]
+    async fn given_fork_point_fetch_error_when_backfilled_blocks_break_then_reorg_is_flagged() {
+        let mut fetched_11 = false;
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(3)
+            .returning(move |block_id| match block_id {
+                BlockId::Number(11) if fetched_11 => Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                }),
+                BlockId::Number(12) => {
+                    let mut block = block(12);
+                    block.parent_hash = FieldElement::from(42u64);
+                    Ok(MaybePendingBlockWithTxs::Block(block))
+                }
+                BlockId::Number(block_number) => {
+                    fetched_11 |= *block_number == 11;
+                    Ok(MaybePendingBlockWithTxs::Block(block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(10, &[10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, block(13), 1).await;
+
+        assert!(report.rollback.is_none());
+        assert_eq!(
+            report.reorgs,
+            vec![Reorg {
+                block_number: 12,
+                parent_hash: FieldElement::from(42u64),
+                expected_parent_hash: block(11).block_hash,
+            }]
+        );
+        let data = node.read().await;
+        assert_eq!(data.block_number, 11);
+        assert_eq!(data.state_root, block(11).new_root.to_string());
+        assert!(data.payload.get(12).is_none());
+        assert!(data.payload.get(13).is_none());
+    }
+
+    /// Test that a tip replacing the last tracked block at the same height is rolled back like
+    /// any other reorganization.
+    #[tokio::test]
+    async fn given_tip_replacing_last_block_when_track_tip_then_cache_is_rolled_back() {
+        let starknet_lightclient_mock = MockStarkNetLightClient::new();
+        let node = node(10, &[9, 10]);
+        let mut reorgs = node.read().await.reorg_notifier.subscribe();
+        let mut tip = block(10);
+        tip.block_hash = FieldElement::from(42u64);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, tip.clone(), 4).await;
+
+        let expected_reorg = ChainReorg {
+            fork_point: Some(sync_block(&block(9))),
+            orphaned: vec![sync_block(&block(10))],
+            tip: sync_block(&tip),
+        };
+        assert_eq!(report.rollback, Some(expected_reorg.clone()));
+        assert_eq!(reorgs.try_recv().unwrap(), expected_reorg);
+        let data = node.read().await;
+        assert_eq!(data.block_number, 10);
+        assert_eq!(data.payload.get(10).unwrap().block_hash, tip.block_hash);
+        assert_eq!(data.sync.reorg_count, 1);
+    }
+
+    /// Test that a tip below the last tracked block on another branch rolls the cache back to
+    /// the fork point and drops the blocks above the shorter canonical chain.
+    #[tokio::test]
+    async fn given_shorter_chain_tip_when_track_tip_then_cache_is_rolled_back() {
+        // The canonical chain forks after block 8 and only reaches block 10.
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .returning(|block_id| match block_id {
+                BlockId::Number(block_number) => {
+                    Ok(MaybePendingBlockWithTxs::Block(forked_block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(12, &[8, 9, 10, 11, 12]);
+        let mut reorgs = node.read().await.reorg_notifier.subscribe();
+
+        let report = track_tip(&starknet_lightclient_mock, &node, forked_block(10), 4).await;
+
+        let expected_reorg = ChainReorg {
+            fork_point: Some(sync_block(&block(8))),
+            orphaned: (9..=12).map(|n| sync_block(&block(n))).collect(),
+            tip: sync_block(&forked_block(10)),
+        };
+        assert_eq!(report.rollback, Some(expected_reorg.clone()));
+        assert!(report.reorgs.is_empty());
+        assert_eq!(reorgs.try_recv().unwrap(), expected_reorg);
+
+        let data = node.read().await;
+        assert_eq!(data.block_number, 10);
+        assert_eq!(
+            data.payload
+                .values()
+                .map(|block| block.block_hash)
+                .collect::<Vec<_>>(),
+            vec![
+                block(8).block_hash,
+                forked_block(9).block_hash,
+                forked_block(10).block_hash,
+            ]
+        );
+        assert_eq!(data.sync.reorg_count, 1);
+    }
+
+    /// Test that the blocks orphaned by a reorganization are rolled back to the fork point,
+    /// replaced by the canonical blocks and notified to the subscribers.
+    #[tokio::test]
+    async fn given_tip_on_another_branch_when_track_tip_then_cache_is_rolled_back() {
+        // The canonical chain forks after block 8.
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(2)
+            .returning(|block_id| match block_id {
+                BlockId::Number(block_number) => {
+                    Ok(MaybePendingBlockWithTxs::Block(forked_block(*block_number)))
+                }
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        let node = node(10, &[8, 9, 10]);
+        let mut reorgs = node.read().await.reorg_notifier.subscribe();
+
+        let report = track_tip(&starknet_lightclient_mock, &node, forked_block(11), 4).await;
+
+        let expected_reorg = ChainReorg {
+            fork_point: Some(sync_block(&block(8))),
+            orphaned: vec![sync_block(&block(9)), sync_block(&block(10))],
+            tip: sync_block(&forked_block(11)),
+        };
+        assert_eq!(report.rollback, Some(expected_reorg.clone()));
+        assert!(report.reorgs.is_empty());
+        assert_eq!(reorgs.try_recv().unwrap(), expected_reorg);
+
+        let data = node.read().await;
+        assert_eq!(data.block_number, 11);
+        assert_eq!(
+            data.payload
+                .values()
+                .map(|block| block.block_hash)
+                .collect::<Vec<_>>(),
+            vec![
+                block(8).block_hash,
+                forked_block(9).block_hash,
+                forked_block(10).block_hash,
+                forked_block(11).block_hash,
+            ]
+        );
+        assert_eq!(data.sync.reorg_count, 1);
+        assert_eq!(data.sync.last_reorg, Some(expected_reorg));
+    }
+
+    /// Test that a fork older than the cached blocks rolls back every cached block it orphans.
+    #[tokio::test]
+    async fn given_fork_older_than_cache_when_track_tip_then_orphaned_blocks_are_rolled_back() {
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .returning(|_block_id| Ok(MaybePendingBlockWithTxs::Block(forked_block(10))));
+        let node = node(10, &[10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, forked_block(11), 4).await;
+
+        let reorg = report.rollback.unwrap();
+        assert_eq!(reorg.fork_point, None);
+        assert_eq!(reorg.orphaned, vec![sync_block(&block(10))]);
+        let data = node.read().await;
+        assert_eq!(
+            data.payload.get(10).unwrap().block_hash,
+            forked_block(10).block_hash
+        );
+    }
+
+    /// Test that the tip is not tracked if the fork point cannot be fetched.
+    #[tokio::test]
+    async fn given_fork_point_fetch_error_when_track_tip_then_tip_is_not_tracked() {
+        let mut starknet_lightclient_mock = MockStarkNetLightClient::new();
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .returning(|_block_id| {
+                Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                })
+            });
+        let node = node(10, &[9, 10]);
+
+        let report = track_tip(&starknet_lightclient_mock, &node, forked_block(11), 4).await;
+
+        assert!(report.rollback.is_none());
+        let data = node.read().await;
+        assert_eq!(data.block_number, 10);
+        assert_eq!(
+            data.payload.get(10).unwrap().block_hash,
+            block(10).block_hash
+        );
+        assert!(data.payload.get(11).is_none());
+        assert_eq!(data.sync.reorg_count, 0);
+    }
+
+    /// Test that a tip not newer than the last tracked block is ignored if it matches the cached
+    /// block at its height, or is not cached.
+    #[tokio::test]
+    async fn given_stale_tip_when_track_tip_then_nothing_is_tracked() {
+        let starknet_lightclient_mock = MockStarkNetLightClient::new();
+        let node = node(10, &[9, 10]);
+
+        for tip in [block(8), block(9), block(10)] {
+            let report = track_tip(&starknet_lightclient_mock, &node, tip, 4).await;
+
+            assert_eq!(report, Default::default());
+        }
+        let data = node.read().await;
+        assert_eq!(data.block_number, 10);
+        assert!(data.payload.get(8).is_none());
+        assert_eq!(data.sync.reorg_count, 0);
+    }
+}
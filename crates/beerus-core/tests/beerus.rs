@@ -1,48 +1,76 @@
 #![cfg(not(target_arch = "wasm32"))]
 
 pub mod common;
-use common::{mock_block_with_txs, mock_broadcasted_transaction, mock_clients, mock_invoke_tx_v1};
+use common::{
+    mock_absent_contract_proof, mock_block_with_txs, mock_broadcasted_transaction,
+    mock_class_proof, mock_clients, mock_invoke_tx_v1, mock_proof_output, mock_proven_state,
+    mock_storage_proof, MOCK_ABSENT_CONTRACT_ADDRESS, MOCK_PROOF_CONTRACT_ADDRESS,
+    MOCK_PROOF_STATE_ROOT,
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use beerus_core::{
         config::Config,
+        ethers_helper,
         lightclient::{
-            beerus::{BeerusLightClient, NodeData, SyncStatus},
+            backfill::DEFAULT_BACKFILL_CONCURRENCY,
+            beerus::{
+                BeerusLightClient, NodeData, ProvenState, SyncBlock, SyncState, SyncStatus,
+                ERC20_ALLOWANCES_VAR, ERC20_BALANCES_VAR, MAX_EVENTS_BLOCK_RANGE,
+                MAX_STORAGE_KEYS_PER_PROOF,
+            },
+            block_cache::BlockCache,
             ethereum::helios_lightclient::HeliosLightClient,
-            starknet::{StarkNetLightClient, StarkNetLightClientImpl},
+            starknet::{
+                commitment::{calculate_block_hash, transaction_hash, GOERLI_FIRST_0_7_BLOCK},
+                storage_key::storage_var_address,
+                storage_proof::{global_state_root, Membership},
+                transaction_hash::calculate_transaction_hash,
+                StarkNetLightClient, StarkNetLightClientImpl,
+            },
+            state_history::LOG_STATE_UPDATE_BLOCK_RANGE,
+            sync::{backoff, sync_once, SyncContext, MAX_SYNC_BACKOFF_SECS},
         },
         starknet_helper::create_mock_broadcasted_transaction,
     };
     use ethabi::Uint as U256;
-    use ethers::types::{Address, Transaction, H256};
+    use ethers::types::{Address, SyncingStatus, Transaction, H256};
 
     use eyre::eyre;
     use helios::types::{BlockTag, CallOpts, ExecutionBlock, Transactions};
     use starknet::providers::jsonrpc::JsonRpcError;
     use starknet::{
+        core::chain_id,
         core::types::{
             BlockHashAndNumber, BlockId, BlockStatus, BlockTag as StarknetBlockTag,
             BlockWithTxHashes, BlockWithTxs, BroadcastedDeclareTransaction,
             BroadcastedDeclareTransactionV1, BroadcastedInvokeTransaction,
-            BroadcastedInvokeTransactionV0, CompressedLegacyContractClass, DeclareTransaction,
-            DeclareTransactionResult, DeclareTransactionV1, DeclareTransactionV2,
-            DeployAccountTransaction, DeployTransaction, EventFilter, FeeEstimate, FieldElement,
-            InvokeTransaction, InvokeTransactionReceipt, InvokeTransactionResult,
-            InvokeTransactionV0, L1HandlerTransaction, LegacyContractEntryPoint,
-            LegacyEntryPointsByType, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
-            MaybePendingStateUpdate, MaybePendingTransactionReceipt, PendingBlockWithTxs,
-            StateDiff, StateUpdate, SyncStatusType, Transaction as StarknetTransaction,
-            TransactionReceipt, TransactionStatus,
+            BroadcastedInvokeTransactionV0, CompressedLegacyContractClass, ContractClass,
+            ContractStorageDiffItem, DeclareTransaction, DeclareTransactionResult,
+            DeclareTransactionV1, DeclareTransactionV2, DeployAccountTransaction,
+            DeployTransaction, EmittedEvent, EntryPointsByType, Event, EventFilter, EventsPage,
+            FeeEstimate, FieldElement, FlattenedSierraClass, InvokeTransaction,
+            InvokeTransactionReceipt, InvokeTransactionResult, InvokeTransactionV0,
+            L1HandlerTransaction, LegacyContractEntryPoint, LegacyEntryPointsByType,
+            MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate,
+            MaybePendingTransactionReceipt, NonceUpdate, PendingBlockWithTxHashes,
+            PendingBlockWithTxs, StateDiff, StateUpdate, StorageEntry, SyncStatusType,
+            Transaction as StarknetTransaction, TransactionReceipt, TransactionStatus,
         },
         macros::selector,
     };
-    use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+    use std::{str::FromStr, sync::Arc, time::Duration};
     use tokio::sync::RwLock;
 
     const UNKNOWN_ERROR_CODE: i64 = 520;
+    const CONTRACT_NOT_FOUND_CODE: i64 = 20;
     const TRANSACTION_HASH_NOT_FOUND_CODE: i64 = 25;
+    const PROOF_LIMIT_EXCEEDED_CODE: i64 = 10000;
+    const PROOF_VERIFICATION_FAILED_CODE: i64 = 10001;
+    const CLASS_HASH_MISMATCH_CODE: i64 = 10002;
+    const EVENTS_LIMIT_EXCEEDED_CODE: i64 = 10003;
 
     const STARKNET_LIGHT_CLIENT_ERROR: &str = "StarkNet light client error";
     const WRONG_URL: &str = "Wrong Url";
@@ -126,8 +154,8 @@ mod tests {
         // Then
         // Assert that the `start` method of the Beerus light client returns `Ok`.
         assert!(result.is_ok());
-        // Assert that the sync status of the Beerus light client is `SyncStatus::Synced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::Synced);
+        // Assert that the Beerus light client is syncing until the sync loop reports progress.
+        assert_eq!(beerus.sync_status().await, SyncStatus::Syncing);
     }
 
     /// Test the `start` method when the Ethereum light client returns an error.
@@ -164,7 +192,334 @@ mod tests {
         // Assert that the error returned by the `start` method of the Beerus light client is the expected error.
         assert_eq!(result.unwrap_err().to_string(), expected_error.to_string());
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
+    }
+
+    /// Test that `stop` ends the sync loop started by `start`, even while it backs off after
+    /// an L1 error.
+    #[tokio::test]
+    async fn given_failing_sync_when_call_stop_then_should_stop_sync_loop() {
+        // Given
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        ethereum_lightclient_mock
+            .expect_start()
+            .times(1)
+            .return_once(move || Ok(()));
+        starknet_lightclient_mock
+            .expect_start()
+            .times(1)
+            .return_once(move || Ok(()));
+        // Every sync round fails on the first L1 call after the sync status.
+        ethereum_lightclient_mock
+            .expect_syncing()
+            .returning(|| Ok(SyncingStatus::IsFalse));
+        ethereum_lightclient_mock
+            .expect_get_block_number()
+            .returning(|| Err(eyre!("Ethereum light client error")));
+
+        let mut beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.start().await.unwrap();
+
+        // When
+        let result = tokio::time::timeout(Duration::from_secs(5), beerus.stop()).await;
+
+        // Then
+        // Assert that the loop stopped without waiting for the backoff delay.
+        assert!(result.expect("sync loop did not stop").is_ok());
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
+        assert!(beerus.take_sync_handle().is_none());
+    }
+
+    /// Test that a sync round records the Ethereum sync status, the proven block and the
+    /// StarkNet tip, and reports the light client as synced.
+    #[tokio::test]
+    async fn given_normal_conditions_when_sync_once_should_report_synced_with_lag() {
+        // Given
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let state_root = FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap();
+        let proven_block_hash = FieldElement::from_hex_be("0x1234").unwrap();
+        let tip_block_hash = FieldElement::from_hex_be("0x5678").unwrap();
+
+        ethereum_lightclient_mock
+            .expect_syncing()
+            .times(1)
+            .return_once(|| Ok(SyncingStatus::IsFalse));
+        ethereum_lightclient_mock
+            .expect_get_block_number()
+            .times(1)
+            .return_once(|| Ok(100));
+        ethereum_lightclient_mock
+            .expect_call()
+            .times(3)
+            .returning(move |opts, _block| {
+                let selector = &opts.data.as_ref().unwrap()[..4];
+                let value = if selector == ethers::utils::id("stateRoot()") {
+                    state_root.to_bytes_be().to_vec()
+                } else if selector == ethers::utils::id("stateBlockNumber()") {
+                    ethers_helper::u256_to_bytes32_slice(U256::from(10)).to_vec()
+                } else {
+                    proven_block_hash.to_bytes_be().to_vec()
+                };
+                Ok(value)
+            });
+        ethereum_lightclient_mock
+            .expect_get_logs()
+            .returning(|_filter| Ok(vec![]));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| {
+                Ok(MaybePendingBlockWithTxs::Block(mock_block_with_txs(
+                    vec![],
+                    15,
+                    BlockStatus::AcceptedOnL2,
+                    tip_block_hash,
+                )))
+            });
+
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.sync.start();
+        let context = SyncContext {
+            ethereum_lightclient: beerus.ethereum_lightclient.clone(),
+            starknet_lightclient: beerus.starknet_lightclient.clone(),
+            node: beerus.node.clone(),
+            starknet_core_abi: beerus.starknet_core_abi.clone(),
+            starknet_core_contract_address: beerus.starknet_core_contract_address,
+            poll_interval: Duration::from_secs(5),
+            backfill_concurrency: DEFAULT_BACKFILL_CONCURRENCY,
+        };
+
+        // When
+        sync_once(&context).await.unwrap();
+
+        // Then
+        let sync = beerus.sync_state().await;
+        assert_eq!(sync.status, SyncStatus::Synced);
+        assert!(sync.l1_synced);
+        assert_eq!(sync.l1_block, Some(100));
+        let proven_block = SyncBlock {
+            block_number: 10,
+            block_hash: proven_block_hash,
+        };
+        assert_eq!(sync.starting_block, Some(proven_block.clone()));
+        assert_eq!(sync.proven_block, Some(proven_block));
+        assert_eq!(
+            sync.l2_tip,
+            Some(SyncBlock {
+                block_number: 15,
+                block_hash: tip_block_hash,
+            })
+        );
+        assert_eq!(sync.l2_lag, Some(5));
+    }
+
+    /// Test that a sync round releases the Ethereum light client before locking the node data,
+    /// which readers lock the other way around.
+    #[tokio::test]
+    async fn given_node_data_read_lock_when_sync_once_should_not_hold_ethereum_lock() {
+        // Given
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        ethereum_lightclient_mock
+            .expect_syncing()
+            .times(1)
+            .return_once(|| Ok(SyncingStatus::IsFalse));
+        ethereum_lightclient_mock
+            .expect_get_block_number()
+            .times(1)
+            .return_once(|| Ok(100));
+        ethereum_lightclient_mock
+            .expect_call()
+            .returning(|_opts, _block| Err(eyre!(NETWORK_FAILURE)));
+
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        let context = SyncContext {
+            ethereum_lightclient: beerus.ethereum_lightclient.clone(),
+            starknet_lightclient: beerus.starknet_lightclient.clone(),
+            node: beerus.node.clone(),
+            starknet_core_abi: beerus.starknet_core_abi.clone(),
+            starknet_core_contract_address: beerus.starknet_core_contract_address,
+            poll_interval: Duration::from_secs(5),
+            backfill_concurrency: DEFAULT_BACKFILL_CONCURRENCY,
+        };
+
+        // When
+        // A reader holds the node data while the round runs up to its first write.
+        let node = beerus.node.read().await;
+        let round = tokio::spawn(async move { sync_once(&context).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Then
+        let ethereum_lightclient =
+            tokio::time::timeout(Duration::from_secs(1), beerus.ethereum_lightclient.lock()).await;
+        assert!(ethereum_lightclient.is_ok(), "sync round holds the lock");
+        drop(ethereum_lightclient);
+        drop(node);
+
+        assert!(round.await.unwrap().is_err());
+        assert!(beerus.sync_state().await.l1_synced);
+    }
+
+    /// Test the transitions of the sync state between its statuses.
+    #[test]
+    fn given_sync_progress_when_record_in_sync_state_should_update_status() {
+        let mut sync = SyncState::default();
+        assert_eq!(sync.status, SyncStatus::NotSynced);
+
+        // Progress recorded before the light client is started does not change its status.
+        sync.record_l2_tip(12, FieldElement::TWO);
+        assert_eq!(sync.status, SyncStatus::NotSynced);
+
+        sync.start();
+        assert_eq!(sync.status, SyncStatus::Syncing);
+
+        sync.record_proven_state(&ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+        assert_eq!(sync.l2_lag, Some(2));
+        // Ethereum has not reported its consensus sync as complete yet.
+        assert_eq!(sync.status, SyncStatus::Syncing);
+
+        sync.record_l1_synced(true);
+        assert_eq!(sync.status, SyncStatus::Synced);
+
+        sync.record_round(&Err(eyre!("StarkNet full node error")));
+        sync.record_round(&Err(eyre!("StarkNet full node error")));
+        assert_eq!(sync.consecutive_failures, 2);
+        assert_eq!(sync.last_error.as_deref(), Some("StarkNet full node error"));
+        sync.record_round(&Ok(()));
+        assert_eq!(sync.consecutive_failures, 0);
+        assert_eq!(sync.last_error, None);
+
+        sync.record_l1_synced(false);
+        assert_eq!(sync.status, SyncStatus::Syncing);
+
+        sync.stop();
+        assert_eq!(sync.status, SyncStatus::NotSynced);
+        assert_eq!(sync.l2_lag, Some(2));
+    }
+
+    /// Test that `starknet_syncing` reports the light client progress while it is not synced.
+    #[tokio::test]
+    async fn given_not_synced_when_starknet_syncing_should_return_light_client_progress() {
+        // Given
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        {
+            let mut node = beerus.node.write().await;
+            node.sync.start();
+            node.sync.record_proven_state(&ProvenState {
+                block_number: 10,
+                ..mock_proven_state()
+            });
+            node.sync.record_l2_tip(15, FieldElement::TWO);
+        }
+
+        // When
+        let result = beerus.starknet_syncing().await.unwrap();
+
+        // Then
+        match result {
+            SyncStatusType::Syncing(status) => {
+                assert_eq!(status.starting_block_num, 10);
+                assert_eq!(status.starting_block_hash, FieldElement::ONE);
+                assert_eq!(status.current_block_num, 10);
+                assert_eq!(status.current_block_hash, FieldElement::ONE);
+                assert_eq!(status.highest_block_num, 15);
+                assert_eq!(status.highest_block_hash, FieldElement::TWO);
+            }
+            SyncStatusType::NotSyncing => panic!("Light client should be syncing"),
+        }
+    }
+
+    /// Test that `starknet_syncing` returns the StarkNet full node status once synced.
+    #[tokio::test]
+    async fn given_synced_when_starknet_syncing_should_return_full_node_status() {
+        // Given
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        starknet_lightclient_mock
+            .expect_syncing()
+            .times(1)
+            .return_once(|| Ok(SyncStatusType::NotSyncing));
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        {
+            let mut node = beerus.node.write().await;
+            node.sync.start();
+            node.sync.record_l1_synced(true);
+            node.sync.record_proven_state(&mock_proven_state());
+            node.sync.record_l2_tip(1, FieldElement::ONE);
+        }
+
+        // When
+        let result = beerus.starknet_syncing().await.unwrap();
+
+        // Then
+        assert!(matches!(result, SyncStatusType::NotSyncing));
+    }
+
+    /// Test that `stop` succeeds when the light client was never started.
+    #[tokio::test]
+    async fn given_not_started_when_call_stop_then_should_return_ok() {
+        // Given
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let mut beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        // When
+        let result = beerus.stop().await;
+
+        // Then
+        assert!(result.is_ok());
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
+    }
+
+    /// Test that the sync backoff doubles the poll interval on every failure, up to its cap.
+    #[test]
+    fn given_consecutive_failures_when_compute_backoff_then_should_double_up_to_cap() {
+        let poll_interval = Duration::from_secs(5);
+
+        assert_eq!(backoff(poll_interval, 0), poll_interval);
+        assert_eq!(backoff(poll_interval, 1), Duration::from_secs(10));
+        assert_eq!(backoff(poll_interval, 3), Duration::from_secs(40));
+        assert_eq!(
+            backoff(poll_interval, 10),
+            Duration::from_secs(MAX_SYNC_BACKOFF_SECS)
+        );
+        assert_eq!(
+            backoff(poll_interval, u32::MAX),
+            Duration::from_secs(MAX_SYNC_BACKOFF_SECS)
+        );
+        // A poll interval longer than the cap is never shortened.
+        let poll_interval = Duration::from_secs(MAX_SYNC_BACKOFF_SECS * 2);
+        assert_eq!(backoff(poll_interval, 2), poll_interval);
     }
 
     /// Test the `send_raw_transaction` method when everything is fine.
@@ -1427,7 +1782,7 @@ mod tests {
         // Assert that the error returned by the `start` method of the Beerus light client is the expected error.
         assert_eq!(result.unwrap_err().to_string(), expected_error.to_string());
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test that starknet state root is returned when the Ethereum light client returns a value.
@@ -1584,7 +1939,7 @@ mod tests {
     #[tokio::test]
     async fn given_normal_conditions_when_starknet_call_should_work() {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
         let expected_result = vec![
             FieldElement::from_hex_be("0x4e28f97185e801").unwrap(),
@@ -1599,15 +1954,16 @@ mod tests {
             .times(1)
             .return_once(move |_req, _block_nb| Ok(expected_result));
 
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10000)));
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10000,
+            ..mock_proven_state()
+        });
 
         // Perform the test call.
         let res = beerus
@@ -1635,7 +1991,7 @@ mod tests {
     async fn given_starknet_light_client_returns_error_when_starknet_call_should_fail_with_same_error(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
         starknet_lightclient_mock
             .expect_call()
@@ -1647,15 +2003,16 @@ mod tests {
                 })
             });
 
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10)));
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
 
         // Perform the test call.
         let result = beerus
@@ -1748,32 +2105,36 @@ mod tests {
         assert!(res.is_err());
     }
 
-    /// Test that starknet storage value is returned when the Starknet light client returns a value.
+    /// Test that starknet storage value is returned when the Starknet light client returns a value
+    /// backed by a valid storage proof.
     #[tokio::test]
     async fn given_normal_conditions_when_starknet_get_storage_at_should_work() {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
-        let expected_result = FieldElement::from_hex_be("298305742194").unwrap();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let expected_result = FieldElement::ZERO;
+        let proof = mock_proof_output();
         // Set the expected return value for the StarkNet light client mock.
         starknet_lightclient_mock
             .expect_get_storage_at()
             .times(1)
             .return_once(move |_address, _key, _block_nb| Ok(expected_result));
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10)));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
 
-        let address = FieldElement::from_hex_be(
-            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
-        )
-        .unwrap();
-        let key = selector!("ERC20_name");
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let key = FieldElement::ONE;
         let block_id = BlockId::Number(10);
         // Perform the test call.
         let res = beerus
@@ -1784,14 +2145,16 @@ mod tests {
         assert_eq!(res, expected_result);
     }
 
-    /// Test that starknet storage value is returned when the Starknet light client returns a value(second scenario)
+    /// Test that starknet storage value is returned when the Starknet light client returns a value
+    /// backed by a valid storage proof (second scenario).
     #[tokio::test]
     async fn given_normal_conditions_with_second_scenario_when_starknet_get_storage_at_should_work()
     {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
-        let expected_result = FieldElement::from_hex_be("298305742194").unwrap();
+        let expected_result = FieldElement::ZERO;
+        let proof = mock_proof_output();
 
         let test_block_with_tx_hashes = BlockWithTxHashes {
             status: BlockStatus::AcceptedOnL2,
@@ -1812,28 +2175,30 @@ mod tests {
             .times(1)
             .return_once(move |_address, _key, _block_nb| Ok(expected_result));
 
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
         starknet_lightclient_mock
             .expect_get_block_with_tx_hashes()
             .times(1)
             .return_once(move |_block_id| Ok(test_block));
 
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10)));
-
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
 
-        let address = FieldElement::from_hex_be(
-            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
-        )
-        .unwrap();
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
 
-        let key = selector!("ERC20_name");
+        let key = FieldElement::ONE;
         let block_id = BlockId::Hash(FieldElement::from_hex_be("0").unwrap());
 
         // Perform the test call.
@@ -1845,16 +2210,54 @@ mod tests {
         assert_eq!(res, expected_result);
     }
 
+    /// Test that an error is returned when the storage value does not match the storage proof.
+    #[tokio::test]
+    async fn given_value_not_matching_proof_when_starknet_get_storage_at_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+
+        // The proof says the slot is empty, the StarkNet node lies.
+        starknet_lightclient_mock
+            .expect_get_storage_at()
+            .times(1)
+            .return_once(move |_address, _key, _block_nb| Ok(FieldElement::TWO));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let key = FieldElement::ONE;
+        let block_id = BlockId::Number(10);
+
+        // Perform the test call.
+        let res = beerus
+            .starknet_get_storage_at(address, key, &block_id)
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
     /// Test that an error is return when getting storage at an unproven block
     #[tokio::test]
     async fn given_unproven_blockid_when_starknet_get_storage_at_should_fail_with_blockid_not_proven_err(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
-
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10)));
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -1862,6 +2265,10 @@ mod tests {
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
 
         let address = FieldElement::from_hex_be(
             "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
@@ -1889,7 +2296,7 @@ mod tests {
     async fn given_starknet_lightclient_returns_error_when_starknet_get_storage_at_should_fail_with_same_error(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, mut ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
         starknet_lightclient_mock
             .expect_get_storage_at()
@@ -1900,15 +2307,16 @@ mod tests {
                     message: WRONG_URL.to_string(),
                 })
             });
-        ethereum_lightclient_mock
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(10)));
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
 
         let address = FieldElement::from_hex_be(
             "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
@@ -2008,13 +2416,1846 @@ mod tests {
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
     }
 
-    /// Test that with a correct url we can create StarkNet light client.
-    #[test]
-    fn given_normal_conditions_when_create_sn_lightclient_should_work() {
-        // Mock config.
-        let (config, _, _) = mock_clients();
-        // Create a new StarkNet light client.
-        let sn_light_client = StarkNetLightClientImpl::new(&config);
+    /// Test that the verified nonce is read from a valid contract proof.
+    #[tokio::test]
+    async fn given_normal_conditions_when_starknet_get_verified_nonce_should_work() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        // Only the contract proof is needed.
+        let mut proof = mock_proof_output();
+        proof.contract_data.as_mut().unwrap().storage_proofs.clear();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let block_id = BlockId::Number(10);
+
+        // Get the verified nonce.
+        let res = beerus
+            .starknet_get_verified_nonce(address, &block_id)
+            .await
+            .unwrap();
+
+        assert_eq!(res, FieldElement::ZERO);
+    }
+
+    /// Test that the verified class hash is read from a valid contract proof.
+    #[tokio::test]
+    async fn given_normal_conditions_when_starknet_get_verified_class_hash_at_should_work() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        // Only the contract proof is needed.
+        let mut proof = mock_proof_output();
+        proof.contract_data.as_mut().unwrap().storage_proofs.clear();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let block_id = BlockId::Number(10);
+
+        // Get the verified class hash.
+        let res = beerus
+            .starknet_get_verified_class_hash_at(address, &block_id)
+            .await
+            .unwrap();
+
+        let expected_result = FieldElement::from_hex_be(
+            "0x10455c752b86932ce552f2b0fe81a880746649b9aee7e0d842bf3f52378f9f8",
+        )
+        .unwrap();
+        assert_eq!(res, expected_result);
+    }
+
+    /// Test that the verified nonce is rejected when the contract proof does not match the L1 state root.
+    #[tokio::test]
+    async fn given_wrong_state_root_when_starknet_get_verified_nonce_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let mut proof = mock_proof_output();
+        proof.contract_data.as_mut().unwrap().storage_proofs.clear();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            state_root: FieldElement::ONE,
+            ..mock_proven_state()
+        });
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let block_id = BlockId::Number(10);
+
+        // Get the verified nonce.
+        let res = beerus.starknet_get_verified_nonce(address, &block_id).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that the proven state is read from the StarkNet core contract at a single L1 block.
+    #[tokio::test]
+    async fn given_normal_conditions_when_refresh_proven_state_should_read_from_single_l1_block() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let state_root = FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap();
+        let block_hash = FieldElement::from_hex_be("0x1234").unwrap();
+
+        ethereum_lightclient_mock
+            .expect_get_block_number()
+            .times(1)
+            .return_once(|| Ok(100));
+        ethereum_lightclient_mock
+            .expect_call()
+            .times(3)
+            .withf(|_opts, block| ethers_helper::block_tag_eq(block, &BlockTag::Number(100)))
+            .returning(move |opts, _block| {
+                let selector = &opts.data.as_ref().unwrap()[..4];
+                let value = if selector == ethers::utils::id("stateRoot()") {
+                    state_root.to_bytes_be().to_vec()
+                } else if selector == ethers::utils::id("stateBlockNumber()") {
+                    ethers_helper::u256_to_bytes32_slice(U256::from(10)).to_vec()
+                } else {
+                    block_hash.to_bytes_be().to_vec()
+                };
+                Ok(value)
+            });
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        let expected_result = ProvenState {
+            block_number: 10,
+            state_root,
+            block_hash,
+            l1_block: 100,
+        };
+
+        // Perform the test call.
+        let res = beerus.refresh_proven_state().await.unwrap();
+
+        assert_eq!(res, expected_result);
+        assert_eq!(beerus.node.read().await.proven_state, Some(expected_result));
+    }
+
+    /// Test that an error is returned when the proven state cannot be read from the Ethereum light client.
+    #[tokio::test]
+    async fn given_ethereum_light_client_returns_error_when_refresh_proven_state_should_fail() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        let expected_error = "Ethereum client out of sync";
+        ethereum_lightclient_mock
+            .expect_get_block_number()
+            .times(1)
+            .return_once(move || Err(eyre!(expected_error)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        // Perform the test call.
+        let res = beerus.refresh_proven_state().await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().message, expected_error);
+        assert!(beerus.node.read().await.proven_state.is_none());
+    }
+
+    /// Test that a verified read at a block older than the proven one is rejected.
+    #[tokio::test]
+    async fn given_block_older_than_proven_state_when_starknet_get_storage_at_should_fail() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let block_id = BlockId::Number(9);
+
+        // Perform the test call.
+        let res = beerus
+            .starknet_get_storage_at(address, FieldElement::ONE, &block_id)
+            .await;
+
+        let expected_result = JsonRpcError {
+            code: 520,
+            message: "No L1 state root known for block 9".to_string(),
+        };
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), expected_result.to_string());
+    }
+
+    /// Test that a verified read at an older proven block is checked against its historical state root.
+    #[tokio::test]
+    async fn given_block_in_state_history_when_starknet_get_storage_at_should_work() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+
+        starknet_lightclient_mock
+            .expect_get_storage_at()
+            .times(1)
+            .withf(|_address, _key, block_id| *block_id == BlockId::Number(1))
+            .return_once(move |_address, _key, _block_nb| Ok(FieldElement::ZERO));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .withf(|_address, _keys, block_id| *block_id == BlockId::Number(1))
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        {
+            let mut node = beerus.node.write().await;
+            // The tip is proven against another root, block 1 is only known from the history.
+            node.proven_state = Some(ProvenState {
+                block_number: 10,
+                state_root: FieldElement::ONE,
+                ..mock_proven_state()
+            });
+            node.state_history.insert(mock_proven_state());
+        }
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let block_id = BlockId::Number(1);
+
+        // Perform the test call.
+        let res = beerus
+            .starknet_get_storage_at(address, FieldElement::ONE, &block_id)
+            .await
+            .unwrap();
+
+        assert_eq!(res, FieldElement::ZERO);
+    }
+
+    /// Test that the state history is indexed up to the L1 block of the proven state.
+    #[tokio::test]
+    async fn given_normal_conditions_when_sync_state_history_should_index_up_to_proven_l1_block() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        // L1 blocks 0 to 100 are queried in two windows.
+        ethereum_lightclient_mock
+            .expect_get_logs()
+            .times(2)
+            .returning(|_filter| Ok(vec![]));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            l1_block: 100,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        beerus.sync_state_history().await.unwrap();
+
+        assert_eq!(
+            beerus
+                .node
+                .read()
+                .await
+                .state_history
+                .last_indexed_l1_block(),
+            Some(100)
+        );
+    }
+
+    /// Test that the windows indexed before a `get_logs` failure are kept.
+    #[tokio::test]
+    async fn given_get_logs_error_when_sync_state_history_should_keep_indexed_windows() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, mut ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        // The first window of L1 blocks 0 to 100 is indexed, the second one fails.
+        let mut calls = 0;
+        ethereum_lightclient_mock
+            .expect_get_logs()
+            .times(2)
+            .returning(move |_filter| {
+                calls += 1;
+                if calls == 1 {
+                    Ok(vec![])
+                } else {
+                    Err(eyre!("get_logs failed"))
+                }
+            });
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            l1_block: 100,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        assert!(beerus.sync_state_history().await.is_err());
+
+        assert_eq!(
+            beerus
+                .node
+                .read()
+                .await
+                .state_history
+                .last_indexed_l1_block(),
+            Some(LOG_STATE_UPDATE_BLOCK_RANGE - 1)
+        );
+    }
+
+    /// Test that a proven block matching the block hash committed on L1 is verified.
+    #[tokio::test]
+    async fn given_proven_block_when_get_verified_block_with_txs_should_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let mut block =
+            mock_block_with_txs(vec![], 10, BlockStatus::AcceptedOnL1, FieldElement::ZERO);
+        block.block_hash = calculate_block_hash(&block, &[]);
+        let block_hash = block.block_hash;
+
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_block_with_txs(&BlockId::Number(10))
+            .await
+            .unwrap();
+
+        assert!(res.verified);
+    }
+
+    /// Test that the receipts of a cached block are fetched once, later reads of the block being
+    /// verified without any request to the full node.
+    #[tokio::test]
+    async fn given_cached_block_when_get_verified_block_with_txs_twice_should_fetch_receipts_once()
+    {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (tx, receipt) = mock_hashed_invoke_tx(chain_id::TESTNET);
+        let mut block =
+            mock_block_with_txs(vec![tx], 10, BlockStatus::AcceptedOnL1, FieldElement::ZERO);
+        block.block_hash = calculate_block_hash(&block, &[]);
+        let block_hash = block.block_hash;
+
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        {
+            let mut node = beerus.node.write().await;
+            node.proven_state = Some(ProvenState {
+                block_number: 10,
+                block_hash,
+                ..mock_proven_state()
+            });
+            node.payload.insert(block).unwrap();
+        }
+
+        // Perform the test calls.
+        for _ in 0..2 {
+            let res = beerus
+                .get_verified_block_with_txs(&BlockId::Number(10))
+                .await
+                .unwrap();
+            assert!(res.verified);
+        }
+    }
+
+    /// Test that a block not proven yet is returned unverified when it matches its own header.
+    #[tokio::test]
+    async fn given_unproven_block_when_get_verified_block_with_txs_should_not_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let mut block =
+            mock_block_with_txs(vec![], 11, BlockStatus::AcceptedOnL2, FieldElement::ZERO);
+        block.block_hash = calculate_block_hash(&block, &[]);
+
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_block_with_txs(&BlockId::Number(11))
+            .await
+            .unwrap();
+
+        assert!(!res.verified);
+    }
+
+    /// Test that a block whose header does not match its block hash is rejected.
+    #[tokio::test]
+    async fn given_wrong_block_hash_when_get_verified_block_with_txs_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let block = mock_block_with_txs(
+            vec![],
+            GOERLI_FIRST_0_7_BLOCK,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::from_hex_be("0x1234").unwrap(),
+        );
+
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: GOERLI_FIRST_0_7_BLOCK,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_block_with_txs(&BlockId::Number(GOERLI_FIRST_0_7_BLOCK))
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that a block hashed before StarkNet v0.7.0 is returned unverified instead of being
+    /// rejected.
+    #[tokio::test]
+    async fn given_block_before_v0_7_when_get_verified_block_with_txs_should_not_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let block = mock_block_with_txs(
+            vec![],
+            10,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::from_hex_be("0x1234").unwrap(),
+        );
+
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_chain_id()
+            .times(1)
+            .return_once(move || Ok(chain_id::TESTNET));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_block_with_txs(&BlockId::Number(10))
+            .await
+            .unwrap();
+
+        assert!(!res.verified);
+    }
+
+    /// Build an invoke transaction whose hash matches its fields on the given chain id.
+    fn mock_hashed_invoke_tx(
+        chain_id: FieldElement,
+    ) -> (StarknetTransaction, MaybePendingTransactionReceipt) {
+        let mut tx = mock_invoke_tx_v1(String::from("0x0"));
+        tx.calldata = vec![FieldElement::ONE, FieldElement::TWO];
+        tx.transaction_hash = calculate_transaction_hash(
+            &StarknetTransaction::Invoke(InvokeTransaction::V1(tx.clone())),
+            chain_id,
+        );
+        let receipt = MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(
+            InvokeTransactionReceipt {
+                transaction_hash: tx.transaction_hash,
+                actual_fee: FieldElement::ZERO,
+                status: TransactionStatus::AcceptedOnL1,
+                block_hash: FieldElement::ZERO,
+                block_number: 10,
+                messages_sent: vec![],
+                events: vec![],
+            },
+        ));
+        (
+            StarknetTransaction::Invoke(InvokeTransaction::V1(tx)),
+            receipt,
+        )
+    }
+
+    /// Test that a transaction included in a proven block is verified.
+    #[tokio::test]
+    async fn given_proven_block_when_get_verified_transaction_by_hash_should_be_verified() {
+        assert_verified_transaction_by_hash(chain_id::TESTNET).await;
+    }
+
+    /// Test that transaction hashes are recomputed with the chain id of the full node, not
+    /// one derived from the Ethereum network.
+    #[tokio::test]
+    async fn given_mainnet_node_when_get_verified_transaction_by_hash_should_use_node_chain_id() {
+        assert_verified_transaction_by_hash(chain_id::MAINNET).await;
+    }
+
+    async fn assert_verified_transaction_by_hash(chain_id: FieldElement) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (tx, receipt) = mock_hashed_invoke_tx(chain_id);
+        let tx_hash = format!("0x{:x}", transaction_hash(&tx));
+        let mut block = mock_block_with_txs(
+            vec![tx.clone()],
+            10,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::ZERO,
+        );
+        block.block_hash = calculate_block_hash(&block, &[]);
+        let block_hash = block.block_hash;
+
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(2)
+            .returning(move |_tx_hash| Ok(receipt.clone()));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_chain_id()
+            .times(1)
+            .return_once(move || Ok(chain_id));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_transaction_by_hash(tx_hash)
+            .await
+            .unwrap();
+
+        assert!(res.verified);
+        assert_eq!(res.transaction, tx);
+    }
+
+    /// Test that a transaction whose fields do not match its hash is rejected.
+    #[tokio::test]
+    async fn given_tampered_transaction_when_get_verified_transaction_receipt_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (tx, receipt) = mock_hashed_invoke_tx(chain_id::TESTNET);
+        let tx_hash = format!("0x{:x}", transaction_hash(&tx));
+        let tampered_tx = match tx {
+            StarknetTransaction::Invoke(InvokeTransaction::V1(mut tx)) => {
+                tx.calldata.push(FieldElement::from(3_u64));
+                StarknetTransaction::Invoke(InvokeTransaction::V1(tx))
+            }
+            _ => unreachable!(),
+        };
+        let block = mock_block_with_txs(
+            vec![tampered_tx],
+            10,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::ZERO,
+        );
+
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_chain_id()
+            .times(1)
+            .return_once(move || Ok(chain_id::TESTNET));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus.get_verified_transaction_receipt(tx_hash).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that a transaction hashed before StarkNet v0.8.0 is returned unverified instead of
+    /// being rejected.
+    #[tokio::test]
+    async fn given_legacy_transaction_when_get_verified_transaction_by_hash_should_not_be_verified()
+    {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let v1_tx = mock_invoke_tx_v1(String::from("0x1234"));
+        let tx = StarknetTransaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+            transaction_hash: v1_tx.transaction_hash,
+            max_fee: v1_tx.max_fee,
+            signature: vec![],
+            nonce: v1_tx.nonce,
+            contract_address: v1_tx.sender_address,
+            entry_point_selector: FieldElement::ONE,
+            calldata: vec![],
+        }));
+        let tx_hash = format!("0x{:x}", transaction_hash(&tx));
+        let mut block = mock_block_with_txs(
+            vec![tx.clone()],
+            GOERLI_FIRST_0_7_BLOCK,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::ZERO,
+        );
+        block.block_hash = calculate_block_hash(&block, &[]);
+        let block_hash = block.block_hash;
+        let receipt = MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(
+            InvokeTransactionReceipt {
+                transaction_hash: transaction_hash(&tx),
+                actual_fee: FieldElement::ZERO,
+                status: TransactionStatus::AcceptedOnL1,
+                block_hash,
+                block_number: GOERLI_FIRST_0_7_BLOCK,
+                messages_sent: vec![],
+                events: vec![],
+            },
+        ));
+
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(2)
+            .returning(move |_tx_hash| Ok(receipt.clone()));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_chain_id()
+            .times(1)
+            .return_once(move || Ok(chain_id::TESTNET));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: GOERLI_FIRST_0_7_BLOCK,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_transaction_by_hash(tx_hash)
+            .await
+            .unwrap();
+
+        assert!(!res.verified);
+        assert_eq!(res.transaction, tx);
+    }
+
+    /// Build a proven block 10 with a single transaction emitting two events, and the matching
+    /// page of events.
+    fn mock_block_with_events() -> (BlockWithTxs, MaybePendingTransactionReceipt, EventsPage) {
+        let events = vec![
+            Event {
+                from_address: FieldElement::ONE,
+                keys: vec![FieldElement::TWO],
+                data: vec![FieldElement::ONE],
+            },
+            Event {
+                from_address: FieldElement::ONE,
+                keys: vec![FieldElement::TWO],
+                data: vec![FieldElement::TWO],
+            },
+        ];
+        let tx = mock_invoke_tx_v1(String::from("0x1234"));
+        let mut block = mock_block_with_txs(
+            vec![StarknetTransaction::Invoke(InvokeTransaction::V1(tx))],
+            10,
+            BlockStatus::AcceptedOnL1,
+            FieldElement::ZERO,
+        );
+        block.block_hash = calculate_block_hash(&block, &events);
+
+        let page = EventsPage {
+            events: events
+                .iter()
+                .map(|event| EmittedEvent {
+                    from_address: event.from_address,
+                    keys: event.keys.clone(),
+                    data: event.data.clone(),
+                    block_hash: block.block_hash,
+                    block_number: 10,
+                    transaction_hash: FieldElement::from_hex_be("0x1234").unwrap(),
+                })
+                .collect(),
+            continuation_token: None,
+        };
+        let receipt = MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(
+            InvokeTransactionReceipt {
+                transaction_hash: FieldElement::from_hex_be("0x1234").unwrap(),
+                actual_fee: FieldElement::ZERO,
+                status: TransactionStatus::AcceptedOnL1,
+                block_hash: block.block_hash,
+                block_number: 10,
+                messages_sent: vec![],
+                events,
+            },
+        ));
+        (block, receipt, page)
+    }
+
+    /// Test that a complete page of events from a proven block is verified.
+    #[tokio::test]
+    async fn given_complete_page_when_get_verified_events_should_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (block, receipt, page) = mock_block_with_events();
+        let block_hash = block.block_hash;
+
+        starknet_lightclient_mock
+            .expect_get_events()
+            .times(1)
+            .return_once(move |_filter, _continuation_token, _chunk_size| Ok(page));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(10)),
+            to_block: Some(BlockId::Number(10)),
+            address: Some(FieldElement::ONE),
+            keys: None,
+        };
+
+        // Perform the test call.
+        let res = beerus.get_verified_events(filter, None, 10).await.unwrap();
+
+        assert!(res.verified);
+        assert_eq!(res.page.events.len(), 2);
+    }
+
+    /// Test that a page omitting a matching event is rejected.
+    #[tokio::test]
+    async fn given_omitted_event_when_get_verified_events_should_fail_with_proof_verification_err()
+    {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (block, receipt, mut page) = mock_block_with_events();
+        let block_hash = block.block_hash;
+        page.events.remove(0);
+
+        starknet_lightclient_mock
+            .expect_get_events()
+            .times(1)
+            .return_once(move |_filter, _continuation_token, _chunk_size| Ok(page));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(10)),
+            to_block: Some(BlockId::Number(10)),
+            address: None,
+            keys: None,
+        };
+
+        // Perform the test call.
+        let res = beerus.get_verified_events(filter, None, 10).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that an empty page is checked against the blocks of the filter range.
+    #[tokio::test]
+    async fn given_empty_page_omitting_events_when_get_verified_events_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (block, receipt, mut page) = mock_block_with_events();
+        let block_hash = block.block_hash;
+        page.events.clear();
+
+        starknet_lightclient_mock
+            .expect_get_events()
+            .times(1)
+            .return_once(move |_filter, _continuation_token, _chunk_size| Ok(page));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingBlockWithTxs::Block(block)));
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(10)),
+            to_block: Some(BlockId::Number(10)),
+            address: None,
+            keys: None,
+        };
+
+        // Perform the test call.
+        let res = beerus.get_verified_events(filter, None, 10).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that the blocks of the filter range after the last event of the last page are
+    /// checked to hold no matching event.
+    #[tokio::test]
+    async fn given_filter_range_beyond_last_event_when_get_verified_events_should_check_every_block(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (block, receipt, page) = mock_block_with_events();
+        let block_hash = block.block_hash;
+        let mut empty_block =
+            mock_block_with_txs(vec![], 11, BlockStatus::AcceptedOnL2, FieldElement::ZERO);
+        empty_block.block_hash = calculate_block_hash(&empty_block, &[]);
+
+        starknet_lightclient_mock
+            .expect_get_events()
+            .times(1)
+            .return_once(move |_filter, _continuation_token, _chunk_size| Ok(page));
+        starknet_lightclient_mock
+            .expect_get_block_with_txs()
+            .times(2)
+            .returning(move |block_id| match block_id {
+                BlockId::Number(10) => Ok(MaybePendingBlockWithTxs::Block(block.clone())),
+                BlockId::Number(11) => Ok(MaybePendingBlockWithTxs::Block(empty_block.clone())),
+                _ => panic!("unexpected block id {block_id:?}"),
+            });
+        starknet_lightclient_mock
+            .expect_get_transaction_receipt()
+            .times(1)
+            .return_once(move |_tx_hash| Ok(receipt));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            block_number: 10,
+            block_hash,
+            ..mock_proven_state()
+        });
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(10)),
+            to_block: Some(BlockId::Number(11)),
+            address: None,
+            keys: None,
+        };
+
+        // Perform the test call.
+        let res = beerus.get_verified_events(filter, None, 10).await.unwrap();
+
+        // Block 11 is not proven on L1 yet.
+        assert!(!res.verified);
+        assert_eq!(res.page.events.len(), 2);
+    }
+
+    /// Test that a page spanning too many blocks to verify is rejected.
+    #[tokio::test]
+    async fn given_too_large_filter_range_when_get_verified_events_should_fail_with_limit_err() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        starknet_lightclient_mock
+            .expect_get_events()
+            .times(1)
+            .return_once(move |_filter, _continuation_token, _chunk_size| {
+                Ok(EventsPage {
+                    events: vec![],
+                    continuation_token: None,
+                })
+            });
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(0)),
+            to_block: Some(BlockId::Number(MAX_EVENTS_BLOCK_RANGE)),
+            address: None,
+            keys: None,
+        };
+
+        // Perform the test call.
+        let res = beerus.get_verified_events(filter, None, 10).await;
+
+        assert_eq!(res.unwrap_err().code, EVENTS_LIMIT_EXCEEDED_CODE);
+    }
+
+    /// Test that a class hashing to the requested class hash is returned.
+    #[tokio::test]
+    async fn given_matching_class_hash_when_get_verified_class_should_work() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let class = FlattenedSierraClass {
+            sierra_program: vec![FieldElement::ONE, FieldElement::TWO],
+            contract_class_version: String::from("0.1.0"),
+            entry_points_by_type: EntryPointsByType {
+                constructor: vec![],
+                external: vec![],
+                l1_handler: vec![],
+            },
+            abi: String::from("[]"),
+        };
+        let class_hash = class.class_hash();
+
+        starknet_lightclient_mock
+            .expect_get_class()
+            .times(1)
+            .return_once(move |_block_id, _class_hash| Ok(ContractClass::Sierra(class)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_class(&BlockId::Number(10), class_hash)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    /// Test that a class not hashing to the requested class hash is rejected.
+    #[tokio::test]
+    async fn given_wrong_class_when_get_verified_class_should_fail_with_class_hash_mismatch_err() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let (class, _) = beerus_core::starknet_helper::create_mock_contract_class();
+
+        starknet_lightclient_mock
+            .expect_get_class()
+            .times(1)
+            .return_once(move |_block_id, _class_hash| Ok(class));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_class(
+                &BlockId::Number(10),
+                FieldElement::from_hex_be("0x123").unwrap(),
+            )
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, CLASS_HASH_MISMATCH_CODE);
+    }
+
+    /// Build a state update of the proof contract in `tests/common/data/data.json`, whose root is
+    /// `MOCK_PROOF_STATE_ROOT`.
+    fn mock_proven_state_update() -> StateUpdate {
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        StateUpdate {
+            block_hash: FieldElement::ONE,
+            new_root: FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap(),
+            old_root: FieldElement::ZERO,
+            state_diff: StateDiff {
+                storage_diffs: vec![ContractStorageDiffItem {
+                    address,
+                    storage_entries: vec![StorageEntry {
+                        key: FieldElement::ONE,
+                        value: FieldElement::ZERO,
+                    }],
+                }],
+                deprecated_declared_classes: vec![],
+                declared_classes: vec![],
+                deployed_contracts: vec![],
+                replaced_classes: vec![],
+                nonces: vec![NonceUpdate {
+                    contract_address: address,
+                    nonce: FieldElement::ZERO,
+                }],
+            },
+        }
+    }
+
+    /// Test that the state update of a proven block consistent with its proofs is verified.
+    #[tokio::test]
+    async fn given_proven_block_when_get_verified_state_update_should_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let state_update = mock_proven_state_update();
+        let proof = mock_proof_output();
+
+        starknet_lightclient_mock
+            .expect_get_state_update()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingStateUpdate::Update(state_update)));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(2)
+            .returning(move |_address, _keys, _block_nb| Ok(proof.clone()));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_state_update(&BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert!(res.verified);
+    }
+
+    /// Test that a state update whose new root differs from the L1 state root is rejected.
+    #[tokio::test]
+    async fn given_wrong_new_root_when_get_verified_state_update_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let state_update = StateUpdate {
+            new_root: FieldElement::from_hex_be("0x1234").unwrap(),
+            ..mock_proven_state_update()
+        };
+
+        starknet_lightclient_mock
+            .expect_get_state_update()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingStateUpdate::Update(state_update)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        // Perform the test call.
+        let res = beerus.get_verified_state_update(&BlockId::Number(1)).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that a state update whose old root differs from the L1 state root of its parent
+    /// block is rejected.
+    #[tokio::test]
+    async fn given_wrong_old_root_when_get_verified_state_update_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let state_update = mock_proven_state_update();
+
+        starknet_lightclient_mock
+            .expect_get_state_update()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingStateUpdate::Update(state_update)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        {
+            let mut node = beerus.node.write().await;
+            node.proven_state = Some(mock_proven_state());
+            node.state_history.insert(ProvenState {
+                block_number: 0,
+                state_root: FieldElement::from_hex_be("0x42").unwrap(),
+                block_hash: FieldElement::TWO,
+                l1_block: 0,
+            });
+        }
+
+        // Perform the test call.
+        let res = beerus.get_verified_state_update(&BlockId::Number(1)).await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that the storage diffs of a contract are checked with one proof per
+    /// `MAX_STORAGE_KEYS_PER_PROOF` keys.
+    #[tokio::test]
+    async fn given_large_storage_diff_when_get_verified_state_update_should_split_storage_proofs() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let state_update = StateUpdate {
+            state_diff: StateDiff {
+                storage_diffs: vec![ContractStorageDiffItem {
+                    address,
+                    storage_entries: vec![
+                        StorageEntry {
+                            key: FieldElement::ONE,
+                            value: FieldElement::ZERO,
+                        };
+                        MAX_STORAGE_KEYS_PER_PROOF + 1
+                    ],
+                }],
+                nonces: vec![],
+                ..mock_proven_state_update().state_diff
+            },
+            ..mock_proven_state_update()
+        };
+        let proof = mock_proof_output();
+
+        starknet_lightclient_mock
+            .expect_get_state_update()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingStateUpdate::Update(state_update)));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(2)
+            .returning(move |_address, keys, _block_nb| {
+                assert!(keys.len() <= MAX_STORAGE_KEYS_PER_PROOF);
+                let mut proof = proof.clone();
+                let contract_data = proof.contract_data.as_mut().unwrap();
+                contract_data.storage_proofs =
+                    vec![contract_data.storage_proofs[0].clone(); keys.len()];
+                Ok(proof)
+            });
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_state_update(&BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert!(res.verified);
+    }
+
+    /// Test that the state update of a block not proven yet is returned unverified.
+    #[tokio::test]
+    async fn given_unproven_block_when_get_verified_state_update_should_not_be_verified() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let state_update = StateUpdate {
+            block_hash: FieldElement::from_hex_be("0x1234").unwrap(),
+            ..mock_proven_state_update()
+        };
+
+        starknet_lightclient_mock
+            .expect_get_state_update()
+            .times(1)
+            .return_once(move |_block_id| Ok(MaybePendingStateUpdate::Update(state_update)));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        // Perform the test call.
+        let res = beerus
+            .get_verified_state_update(&BlockId::Number(2))
+            .await
+            .unwrap();
+
+        assert!(!res.verified);
+    }
+
+    /// Test that verified storage reads return the proof and the L1 anchor they were checked
+    /// against.
+    #[tokio::test]
+    async fn given_normal_conditions_when_get_verified_storage_should_return_proof_and_anchor() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+        let expected_proof = proof.clone();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        // Perform the test call.
+        let res = beerus
+            .get_verified_storage(address, vec![FieldElement::ONE], &BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert_eq!(res.storage_values, vec![FieldElement::ZERO]);
+        assert_eq!(res.memberships, vec![Membership::NonMember]);
+        assert_eq!(res.proof, expected_proof);
+        assert_eq!(res.state_root, mock_proven_state().state_root);
+        assert_eq!(res.l1_block, mock_proven_state().l1_block);
+    }
+
+    /// Test that batched storage reads request a single proof for all the keys, and no storage
+    /// value from the full node.
+    #[tokio::test]
+    async fn given_normal_conditions_when_get_storage_many_should_request_single_proof() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .withf(|_address, keys, _block_nb| keys == &vec![FieldElement::ONE])
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        // Perform the test call.
+        let res = beerus
+            .get_storage_many(address, vec![FieldElement::ONE], &BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert_eq!(res, vec![FieldElement::ZERO]);
+    }
+
+    /// Test that batched storage reads reject more keys than a single proof can hold.
+    #[tokio::test]
+    async fn given_too_many_keys_when_get_storage_many_should_fail_with_proof_limit_exceeded_err() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+
+        let keys = (0..=MAX_STORAGE_KEYS_PER_PROOF as u64)
+            .map(FieldElement::from)
+            .collect();
+        // Perform the test call.
+        let res = beerus
+            .get_storage_many(FieldElement::ONE, keys, &BlockId::Number(1))
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_LIMIT_EXCEEDED_CODE);
+    }
+
+    /// Test that ERC-20 balances are read from the two `ERC20_balances` slots of the owner, and
+    /// rejected when the storage proof does not cover both slots.
+    #[tokio::test]
+    async fn given_unproven_balance_when_erc20_balance_of_should_fail_with_proof_verification_err()
+    {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+        let owner = FieldElement::from_hex_be("0x1234").unwrap();
+        let balance_slot = storage_var_address(ERC20_BALANCES_VAR, &[owner]);
+        let expected_keys = vec![balance_slot, balance_slot + FieldElement::ONE];
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .withf(move |_address, keys, _block_nb| keys == &expected_keys)
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        let token = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        // Perform the test call.
+        let res = beerus
+            .erc20_balance_of(token, owner, &BlockId::Number(1))
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that an ERC-20 balance is decoded from the low and high `ERC20_balances` slots
+    /// proven in the storage of the token.
+    #[tokio::test]
+    async fn given_proven_balance_when_erc20_balance_of_should_return_low_and_high_slots() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let token = FieldElement::from_hex_be("0x49d3657").unwrap();
+        let owner = FieldElement::from_hex_be("0x1234").unwrap();
+        let other = FieldElement::from_hex_be("0x5678").unwrap();
+        let balance_slot = storage_var_address(ERC20_BALANCES_VAR, &[owner]);
+        let keys = vec![balance_slot, balance_slot + FieldElement::ONE];
+        let (proven_state, proof) = mock_storage_proof(
+            token,
+            &[
+                (balance_slot, FieldElement::from(5_u64)),
+                (balance_slot + FieldElement::ONE, FieldElement::TWO),
+                (
+                    storage_var_address(ERC20_BALANCES_VAR, &[other]),
+                    FieldElement::from(7_u64),
+                ),
+            ],
+            &keys,
+        );
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .withf(move |address, requested_keys, _block_nb| {
+                *address == token && requested_keys == &keys
+            })
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(proven_state);
+
+        // Perform the test call.
+        let res = beerus
+            .erc20_balance_of(token, owner, &BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert_eq!(res, (U256::from(2_u64) << 128) + U256::from(5_u64));
+    }
+
+    /// Test that an ERC-20 allowance is read from the `ERC20_allowances` slots keyed by the
+    /// owner then the spender, an absent high slot being read as zero.
+    #[tokio::test]
+    async fn given_proven_allowance_when_allowance_should_fold_owner_and_spender_keys() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let token = FieldElement::from_hex_be("0x49d3657").unwrap();
+        let owner = FieldElement::from_hex_be("0x1234").unwrap();
+        let spender = FieldElement::from_hex_be("0x5678").unwrap();
+        let allowance_slot = storage_var_address(ERC20_ALLOWANCES_VAR, &[owner, spender]);
+        let keys = vec![allowance_slot, allowance_slot + FieldElement::ONE];
+        let (proven_state, proof) = mock_storage_proof(
+            token,
+            &[
+                (allowance_slot, FieldElement::from(7_u64)),
+                // The allowance of the spender over the owner must not be read.
+                (
+                    storage_var_address(ERC20_ALLOWANCES_VAR, &[spender, owner]),
+                    FieldElement::from(9_u64),
+                ),
+            ],
+            &keys,
+        );
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .withf(move |address, requested_keys, _block_nb| {
+                *address == token && requested_keys == &keys
+            })
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(proven_state);
+
+        // Perform the test call.
+        let res = beerus
+            .allowance(token, owner, spender, &BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert_eq!(res, U256::from(7_u64));
+    }
+
+    /// Test that a contract reported as not found by the StarkNet full node is only reported as
+    /// such once its non-membership proof is verified.
+    #[tokio::test]
+    async fn given_undeployed_contract_when_starknet_get_storage_at_should_fail_with_contract_not_found_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (proven_state, proof) = mock_absent_contract_proof();
+
+        starknet_lightclient_mock
+            .expect_get_storage_at()
+            .times(1)
+            .return_once(move |_address, _key, _block_nb| {
+                Err(JsonRpcError {
+                    code: CONTRACT_NOT_FOUND_CODE,
+                    message: "Contract not found".to_string(),
+                })
+            });
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(proven_state);
+
+        let address = FieldElement::from_hex_be(MOCK_ABSENT_CONTRACT_ADDRESS).unwrap();
+        let res = beerus
+            .starknet_get_storage_at(address, FieldElement::ONE, &BlockId::Number(1))
+            .await;
+
+        assert_eq!(res.unwrap_err().code, CONTRACT_NOT_FOUND_CODE);
+    }
+
+    /// Test that a deployed contract reported as not found by the StarkNet full node is rejected.
+    #[tokio::test]
+    async fn given_deployed_contract_reported_not_found_when_starknet_get_storage_at_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let proof = mock_proof_output();
+
+        starknet_lightclient_mock
+            .expect_get_storage_at()
+            .times(1)
+            .return_once(move |_address, _key, _block_nb| {
+                Err(JsonRpcError {
+                    code: CONTRACT_NOT_FOUND_CODE,
+                    message: "Contract not found".to_string(),
+                })
+            });
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let res = beerus
+            .starknet_get_storage_at(address, FieldElement::ONE, &BlockId::Number(1))
+            .await;
+
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that the storage of an undeployed contract is read as zero with non-membership proofs.
+    #[tokio::test]
+    async fn given_undeployed_contract_when_get_verified_storage_should_return_non_memberships() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (proven_state, proof) = mock_absent_contract_proof();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(proven_state);
+
+        let address = FieldElement::from_hex_be(MOCK_ABSENT_CONTRACT_ADDRESS).unwrap();
+        let verified = beerus
+            .get_verified_storage(address, vec![FieldElement::ONE], &BlockId::Number(1))
+            .await
+            .unwrap();
+
+        assert_eq!(verified.storage_values, vec![FieldElement::ZERO]);
+        assert_eq!(verified.memberships, vec![Membership::NonMember]);
+    }
+
+    /// Test that the verified nonce of an undeployed contract fails with a contract not found error.
+    #[tokio::test]
+    async fn given_undeployed_contract_when_starknet_get_verified_nonce_should_fail_with_contract_not_found_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (proven_state, proof) = mock_absent_contract_proof();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(proven_state);
+
+        let address = FieldElement::from_hex_be(MOCK_ABSENT_CONTRACT_ADDRESS).unwrap();
+        let res = beerus
+            .starknet_get_verified_nonce(address, &BlockId::Number(1))
+            .await;
+
+        assert_eq!(res.unwrap_err().code, CONTRACT_NOT_FOUND_CODE);
+    }
+
+    /// Test that the compiled class hash of a declared class is verified against a class proof
+    /// bound to the global state root.
+    #[tokio::test]
+    async fn given_declared_class_when_verify_compiled_class_hash_should_work() {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let class_hash = FieldElement::from_hex_be("0x5").unwrap();
+        let compiled_class_hash = FieldElement::from_hex_be("0x6").unwrap();
+        let (classes_tree_root, class_proof) = mock_class_proof(class_hash, compiled_class_hash);
+        let mut proof = mock_proof_output();
+        proof.classes_tree_root = Some(classes_tree_root);
+        let contracts_tree_root = FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+        starknet_lightclient_mock
+            .expect_get_class_proof()
+            .times(1)
+            .return_once(move |_class_hash, _block_nb| Ok(class_proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            state_root: global_state_root(contracts_tree_root, classes_tree_root),
+            ..mock_proven_state()
+        });
+
+        let res = beerus
+            .verify_compiled_class_hash(&BlockId::Number(1), class_hash, compiled_class_hash)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    /// Test that a compiled class hash not matching the class proof is rejected.
+    #[tokio::test]
+    async fn given_wrong_compiled_class_hash_when_verify_compiled_class_hash_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let class_hash = FieldElement::from_hex_be("0x5").unwrap();
+        let (classes_tree_root, class_proof) =
+            mock_class_proof(class_hash, FieldElement::from_hex_be("0x6").unwrap());
+        let mut proof = mock_proof_output();
+        proof.classes_tree_root = Some(classes_tree_root);
+        let contracts_tree_root = FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap();
+
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+        starknet_lightclient_mock
+            .expect_get_class_proof()
+            .times(1)
+            .return_once(move |_class_hash, _block_nb| Ok(class_proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(ProvenState {
+            state_root: global_state_root(contracts_tree_root, classes_tree_root),
+            ..mock_proven_state()
+        });
+
+        let res = beerus
+            .verify_compiled_class_hash(&BlockId::Number(1), class_hash, FieldElement::ONE)
+            .await;
+
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that a storage proof is rejected when the classes tree root does not commit to the
+    /// L1 state root.
+    #[tokio::test]
+    async fn given_classes_tree_root_not_matching_global_root_when_starknet_get_storage_at_should_fail_with_proof_verification_err(
+    ) {
+        // Mock config, ethereum light client and starknet light client.
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let mut proof = mock_proof_output();
+        proof.classes_tree_root = Some(FieldElement::ONE);
+
+        starknet_lightclient_mock
+            .expect_get_storage_at()
+            .times(1)
+            .return_once(move |_address, _key, _block_nb| Ok(FieldElement::ZERO));
+        starknet_lightclient_mock
+            .expect_get_contract_storage_proof()
+            .times(1)
+            .return_once(move |_address, _keys, _block_nb| Ok(proof));
+
+        // Create a new Beerus light client.
+        let beerus = BeerusLightClient::new_from_clients(
+            config,
+            Box::new(ethereum_lightclient_mock),
+            Box::new(starknet_lightclient_mock),
+        );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
+
+        let address = FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap();
+        let res = beerus
+            .starknet_get_storage_at(address, FieldElement::ONE, &BlockId::Number(1))
+            .await;
+
+        assert_eq!(res.unwrap_err().code, PROOF_VERIFICATION_FAILED_CODE);
+    }
+
+    /// Test that with a correct url we can create StarkNet light client.
+    #[test]
+    fn given_normal_conditions_when_create_sn_lightclient_should_work() {
+        // Mock config.
+        let (config, _, _) = mock_clients();
+        // Create a new StarkNet light client.
+        let sn_light_client = StarkNetLightClientImpl::new(&config);
         assert!(sn_light_client.is_ok());
     }
 
@@ -2253,13 +4494,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2299,11 +4541,6 @@ mod tests {
             .times(1)
             .return_once(|_block_id| Ok(expected_result));
 
-        starknet_lightclient_mock
-            .expect_block_number()
-            .times(1)
-            .return_once(|| Ok(10));
-
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
@@ -2341,11 +4578,6 @@ mod tests {
             .times(1)
             .return_once(|_block_id| Ok(expected_result));
 
-        starknet_lightclient_mock
-            .expect_block_number()
-            .times(1)
-            .return_once(|| Ok(10));
-
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
             config,
@@ -2408,13 +4640,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2494,13 +4727,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("1"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2537,13 +4771,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2598,28 +4833,21 @@ mod tests {
         assert!(res.is_ok());
     }
 
-    /// Test that starknet gets transaction by block and index when Starknet light client returns a value and when `MaybePendingBlockWithTxs::Block` is returned
+    /// Test that starknet gets transaction by block and index from the full node when the block is not cached.
     #[tokio::test]
-    async fn given_normal_condition_and_block_get_transaction_by_block_and_index_should_work() {
+    async fn given_block_not_cached_when_get_transaction_by_block_and_index_should_query_full_node()
+    {
         // Mock config, ethereum light client and starknet light client.
         let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
-        let status = BlockStatus::Pending;
-        let block_hash = FieldElement::from_dec_str("01").unwrap();
-        let block_number = 0;
-
         let tx_hash = String::from("0x1234");
         let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
         let transaction = StarknetTransaction::Invoke(InvokeTransaction::V1(invoke_tx_v1));
-        let transactions = vec![transaction];
-
-        let block_with_txs = mock_block_with_txs(transactions, block_number, status, block_hash);
-        let expected_block_with_txs = MaybePendingBlockWithTxs::Block(block_with_txs);
 
         starknet_lightclient_mock
-            .expect_get_block_with_txs()
+            .expect_get_transaction_by_block_id_and_index()
             .times(1)
-            .return_once(|_block_id| Ok(expected_block_with_txs));
+            .return_once(|_block_id, _index| Ok(transaction));
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -2638,35 +4866,31 @@ mod tests {
         assert!(res.is_ok());
     }
 
-    /// Test that starknet gets transaction by block and index when Starknet light client returns a value and when `MaybePendingBlockWithTxs::PendingBlock` is returned
+    /// Test that starknet gets transaction by block and index from the block cache without querying the full node.
     #[tokio::test]
-    async fn given_normal_condition_and_pending_block_get_transaction_by_block_and_index_should_work(
+    async fn given_block_cached_when_get_transaction_by_block_and_index_should_return_cached_transaction(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
 
-        let parent_hash = FieldElement::from_dec_str("01").unwrap();
-        let timestamp: u64 = 0;
-        let sequencer_address = FieldElement::from_dec_str("01").unwrap();
+        let status = BlockStatus::AcceptedOnL2;
+        let block_hash = FieldElement::from_dec_str("01").unwrap();
+        let block_number = 1;
 
         let tx_hash = String::from("0x1234");
         let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
         let transaction = StarknetTransaction::Invoke(InvokeTransaction::V1(invoke_tx_v1));
-        let transactions = vec![transaction];
-
-        let pending_block = PendingBlockWithTxs {
-            timestamp,
-            sequencer_address,
-            transactions,
-            parent_hash,
-        };
-
-        let expected_block_with_txs = MaybePendingBlockWithTxs::PendingBlock(pending_block);
-
-        starknet_lightclient_mock
-            .expect_get_block_with_txs()
-            .times(1)
-            .return_once(|_block_id| Ok(expected_block_with_txs));
+        let transactions = vec![transaction.clone()];
+
+        let mut payload = BlockCache::default();
+        payload
+            .insert(mock_block_with_txs(
+                transactions,
+                block_number,
+                status,
+                block_hash,
+            ))
+            .unwrap();
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -2674,39 +4898,36 @@ mod tests {
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.payload = payload;
 
-        let block_id = BlockId::Number(1);
-        let index = 0;
+        let block_id = BlockId::Number(block_number);
         let res = beerus
-            .get_transaction_by_block_and_index(&block_id, index)
+            .get_transaction_by_block_and_index(&block_id, 0)
             .await;
 
         // Assert that the result is correct.
-        assert!(res.is_ok());
+        assert_eq!(
+            transaction_hash(&res.unwrap()),
+            transaction_hash(&transaction)
+        );
+
+        // An index past the end of the cached block is rejected.
+        let res = beerus
+            .get_transaction_by_block_and_index(&block_id, 1)
+            .await;
+        assert_eq!(res.unwrap_err().code, 27);
     }
 
-    /// Test that starknet gets block transaction count when Starknet light client returns a value and when `MaybePendingBlockWithTxs::Block` is returned
+    /// Test that starknet gets block transaction count from the full node when the block is not cached.
     #[tokio::test]
-    async fn given_normal_condition_and_block_get_block_transaction_count_should_work() {
+    async fn given_block_not_cached_when_get_block_transaction_count_should_query_full_node() {
         // Mock config, ethereum light client and starknet light client.
         let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
-        let status = BlockStatus::Pending;
-        let block_hash = FieldElement::from_dec_str("01").unwrap();
-        let block_number = 0;
-
-        let tx_hash = String::from("0x1234");
-        let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
-        let transaction = StarknetTransaction::Invoke(InvokeTransaction::V1(invoke_tx_v1));
-        let transactions = vec![transaction];
-
-        let block = mock_block_with_txs(transactions, block_number, status, block_hash);
-        let expected_block_with_txs = MaybePendingBlockWithTxs::Block(block);
-
         starknet_lightclient_mock
-            .expect_get_block_with_txs()
+            .expect_get_block_transaction_count()
             .times(1)
-            .return_once(|_block_id| Ok(expected_block_with_txs));
+            .return_once(|_block_id| Ok(1));
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -2723,34 +4944,30 @@ mod tests {
         assert_eq!(res.unwrap(), 1);
     }
 
-    /// Test that starknet gets block transaction count when Starknet light client returns a value and when `MaybePendingBlockWithTxs::PendingBlock` is returned
+    /// Test that starknet gets block transaction count from the block cache without querying the full node.
     #[tokio::test]
-    async fn given_normal_condition_and_pending_block_get_block_transaction_count_should_work() {
+    async fn given_block_cached_when_get_block_transaction_count_should_return_cached_count() {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
 
-        let parent_hash = FieldElement::from_dec_str("01").unwrap();
-        let timestamp: u64 = 0;
-        let sequencer_address = FieldElement::from_dec_str("01").unwrap();
+        let status = BlockStatus::AcceptedOnL2;
+        let block_hash = FieldElement::from_dec_str("01").unwrap();
+        let block_number = 1;
 
         let tx_hash = String::from("0x1234");
         let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
         let transaction = StarknetTransaction::Invoke(InvokeTransaction::V1(invoke_tx_v1));
-        let transactions = vec![transaction];
-
-        let pending_block = PendingBlockWithTxs {
-            timestamp,
-            sequencer_address,
-            transactions,
-            parent_hash,
-        };
-
-        let expected_block_with_txs = MaybePendingBlockWithTxs::PendingBlock(pending_block);
-
-        starknet_lightclient_mock
-            .expect_get_block_with_txs()
-            .times(1)
-            .return_once(|_block_id| Ok(expected_block_with_txs));
+        let transactions = vec![transaction.clone(), transaction];
+
+        let mut payload = BlockCache::default();
+        payload
+            .insert(mock_block_with_txs(
+                transactions,
+                block_number,
+                status,
+                block_hash,
+            ))
+            .unwrap();
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -2758,13 +4975,14 @@ mod tests {
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
+        beerus.node.write().await.payload = payload;
 
-        let block_id = BlockId::Number(1);
-        let res = beerus.get_block_transaction_count(&block_id).await;
+        let res = beerus
+            .get_block_transaction_count(&BlockId::Hash(block_hash))
+            .await;
 
         // Assert that the result is correct.
-        assert!(res.is_ok());
-        assert_eq!(res.unwrap(), 1);
+        assert_eq!(res.unwrap(), 2);
     }
 
     /// Test that starknet gets block with transaction hashes when Starknet light client returns a value and `block_id` is a number
@@ -2785,13 +5003,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2827,13 +5046,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2851,12 +5071,26 @@ mod tests {
         assert!(res.is_ok());
     }
 
-    /// Test that starknet gets block with transaction hashes when Starknet light client returns a value and `block_id` is a hash and hash not found
+    /// Test that starknet gets block with transaction hashes from the full node when `block_id` is a hash not found in the block cache
     #[tokio::test]
     async fn given_normal_condition_and_block_id_is_hash_and_hash_not_found_then_get_block_with_tx_hashes_should_return_error(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let expected_error = JsonRpcError {
+            code: 24,
+            message: "Block not found".to_string(),
+        };
+        starknet_lightclient_mock
+            .expect_get_block_with_tx_hashes()
+            .times(1)
+            .return_once(|_block_id| {
+                Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                })
+            });
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -2869,10 +5103,6 @@ mod tests {
         let block_id = BlockId::Hash(block_hash);
         let res = beerus.get_block_with_tx_hashes(&block_id).await;
 
-        let expected_error = JsonRpcError {
-            code: 24,
-            message: format!("Block with hash {} not found in the payload.", block_hash),
-        };
         // Assert that the result is correct.
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().to_string(), expected_error.to_string());
@@ -2897,13 +5127,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -2921,55 +5152,49 @@ mod tests {
         assert!(res.is_ok());
     }
 
-    /// Test that starknet gets block with transaction hashes when Starknet light client returns a value and `block_id` is a pending tag
+    /// Test that starknet gets block with transaction hashes from the full node when `block_id` is a pending tag
     #[tokio::test]
     async fn given_normal_condition_and_block_id_is_pending_tag_then_get_block_with_tx_hashes_should_work(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
-
-        let tx_hash = String::from("0x1234");
-        let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
-        let transaction = StarknetTransaction::Invoke(InvokeTransaction::V1(invoke_tx_v1));
-        let transactions = vec![transaction];
-
-        let block_number = 1;
-        let status = BlockStatus::Pending;
-        let block_hash = FieldElement::from_hex_be("0").unwrap();
-
-        let block_with_tx_hashes =
-            mock_block_with_txs(transactions, block_number, status, block_hash);
-
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
-        let node_data = NodeData {
-            block_number,
-            state_root: String::from("0x5678"),
-            payload: btree_map,
+        let pending_block = PendingBlockWithTxHashes {
+            transactions: vec![FieldElement::from_hex_be("0x1234").unwrap()],
+            timestamp: 0,
+            sequencer_address: FieldElement::from_dec_str("01").unwrap(),
+            parent_hash: FieldElement::from_dec_str("01").unwrap(),
         };
+        starknet_lightclient_mock
+            .expect_get_block_with_tx_hashes()
+            .times(1)
+            .return_once(|_block_id| {
+                Ok(MaybePendingBlockWithTxHashes::PendingBlock(pending_block))
+            });
 
         // Create a new Beerus light client.
-        let mut beerus = BeerusLightClient::new_from_clients(
+        let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(ethereum_lightclient_mock),
             Box::new(starknet_lightclient_mock),
         );
-        beerus.node = Arc::new(RwLock::new(node_data));
 
         let block_id = BlockId::Tag(StarknetBlockTag::Pending);
         let res = beerus.get_block_with_tx_hashes(&block_id).await;
 
         // Assert that the result is correct.
-        assert!(res.is_ok());
+        assert!(matches!(
+            res,
+            Ok(MaybePendingBlockWithTxHashes::PendingBlock(_))
+        ));
     }
 
-    /// Test that starknet gets block with transaction hashes when Starknet light client returns a value, `block_id` is a pending tag and pending tag not found
+    /// Test that starknet gets block with transaction hashes returns the full node error when `block_id` is a pending tag and the pending block is not found
     #[tokio::test]
     async fn given_normal_condition_and_block_id_is_pending_tag_and_pending_tag_not_found_then_get_block_with_tx_hashes_should_return_error(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
 
         let tx_hash = String::from("0x1234");
         let invoke_tx_v1 = mock_invoke_tx_v1(tx_hash);
@@ -2983,14 +5208,29 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
+        };
+
+        let expected_error = JsonRpcError {
+            code: 24,
+            message: "Block not found".to_string(),
         };
+        starknet_lightclient_mock
+            .expect_get_block_with_tx_hashes()
+            .times(1)
+            .return_once(|_block_id| {
+                Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                })
+            });
 
         // Create a new Beerus light client.
         let mut beerus = BeerusLightClient::new_from_clients(
@@ -3003,10 +5243,6 @@ mod tests {
         let block_id = BlockId::Tag(StarknetBlockTag::Pending);
         let res = beerus.get_block_with_tx_hashes(&block_id).await;
 
-        let expected_error = JsonRpcError {
-            code: 24,
-            message: "Block with pending status not found in the payload.".to_string(),
-        };
         // Assert that the result is correct.
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().to_string(), expected_error.to_string());
@@ -3119,13 +5355,14 @@ mod tests {
         let block_with_tx_hashes =
             mock_block_with_txs(transactions, block_number, status, block_hash);
 
-        let mut btree_map: BTreeMap<u64, BlockWithTxs> = BTreeMap::new();
-        btree_map.insert(block_number, block_with_tx_hashes);
+        let mut payload = BlockCache::default();
+        payload.insert(block_with_tx_hashes).unwrap();
 
         let node_data = NodeData {
             block_number,
             state_root: String::from("0x5678"),
-            payload: btree_map,
+            payload,
+            ..NodeData::default()
         };
 
         // Create a new Beerus light client.
@@ -3143,12 +5380,26 @@ mod tests {
         assert!(res.is_ok());
     }
 
-    /// Test that starknet gets block with transaction hashes when Starknet light client returns a value, `block_id` is a number and block number not found
+    /// Test that starknet gets block with transaction hashes returns the full node error when `block_id` is a number neither cached nor found by the full node
     #[tokio::test]
     async fn given_normal_condition_and_block_id_is_number_and_number_not_found_then_get_block_with_tx_hashes_should_return_error(
     ) {
         // Mock config, ethereum light client and starknet light client.
-        let (config, ethereum_lightclient_mock, starknet_lightclient_mock) = mock_clients();
+        let (config, ethereum_lightclient_mock, mut starknet_lightclient_mock) = mock_clients();
+
+        let expected_error = JsonRpcError {
+            code: 24,
+            message: "Block not found".to_string(),
+        };
+        starknet_lightclient_mock
+            .expect_get_block_with_tx_hashes()
+            .times(1)
+            .return_once(|_block_id| {
+                Err(JsonRpcError {
+                    code: 24,
+                    message: "Block not found".to_string(),
+                })
+            });
 
         // Create a new Beerus light client.
         let beerus = BeerusLightClient::new_from_clients(
@@ -3161,10 +5412,6 @@ mod tests {
         let block_id = BlockId::Number(block_number);
         let res = beerus.get_block_with_tx_hashes(&block_id).await;
 
-        let expected_error = JsonRpcError {
-            code: 24,
-            message: "Error while retrieving block.".to_string(),
-        };
         // Assert that the result is correct.
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().to_string(), expected_error.to_string());
@@ -3239,7 +5486,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     // Test the `starknet_l1_to_l2_message_nonce` method when everything is fine.
@@ -3312,7 +5559,7 @@ mod tests {
             "JSON-RPC error: code=520, message=\"Ethereum lightclient error\"".to_string()
         );
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `block_hash_and_number` method when everything is fine.
@@ -3396,7 +5643,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `get_class` method when everything is fine.
@@ -3485,7 +5732,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test that msg_fee + 1 for the message with the given 'msgHash is returned when the Ethereum light client returns a value.
@@ -3726,7 +5973,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `get_block_transaction_count` method when everything is fine.
@@ -3807,7 +6054,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     // /// Test the `get_logs` when everything is fine.
@@ -3986,7 +6233,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `syncing` method when everything is fine.
@@ -4104,7 +6351,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `estimate_fee` method when everything is fine.
@@ -4532,7 +6779,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `get_transaction_by_block_id_and_index` method when everything is fine.
@@ -4638,7 +6885,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `pending_transactions` method when everything is fine.
@@ -4716,7 +6963,7 @@ mod tests {
         assert_eq!(result_err.message, STARKNET_LIGHT_CLIENT_ERROR.to_string());
         assert_eq!(result_err.code, UNKNOWN_ERROR_CODE);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `get_transaction_receipt` method when everything is fine.
@@ -4807,7 +7054,7 @@ mod tests {
         assert_eq!(result_err.code, TRANSACTION_HASH_NOT_FOUND_CODE);
 
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
 
     /// Test the `get_block_with_tx_hashes` method when everything is fine.
@@ -4912,7 +7159,7 @@ mod tests {
         assert_eq!(unwraped_err.message, expected_message);
         assert_eq!(unwraped_err.code, expected_code);
         // Assert that the sync status of the Beerus light client is `SyncStatus::NotSynced`.
-        assert_eq!(beerus.sync_status().clone(), SyncStatus::NotSynced);
+        assert_eq!(beerus.sync_status().await, SyncStatus::NotSynced);
     }
     /// Test the `get_transaction_by_hash` method when the StarkNet light client returns an error.
     /// This test mocks external dependencies.
@@ -0,0 +1,259 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod tests {
+    use beerus_core::lightclient::block_cache::{BlockCache, BlockRetention};
+    use starknet::core::types::{
+        BlockStatus, BlockWithTxs, FieldElement, InvokeTransaction, InvokeTransactionReceipt,
+        InvokeTransactionV1, MaybePendingTransactionReceipt, Transaction, TransactionReceipt,
+        TransactionStatus,
+    };
+    use std::{fs, path::PathBuf};
+
+    fn invoke_transaction(tx_hash: u64) -> Transaction {
+        Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+            transaction_hash: FieldElement::from(tx_hash),
+            max_fee: FieldElement::ZERO,
+            signature: vec![],
+            nonce: FieldElement::ZERO,
+            sender_address: FieldElement::ZERO,
+            calldata: vec![],
+        }))
+    }
+
+    fn invoke_receipt(tx_hash: u64) -> MaybePendingTransactionReceipt {
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(
+            InvokeTransactionReceipt {
+                transaction_hash: FieldElement::from(tx_hash),
+                actual_fee: FieldElement::ZERO,
+                status: TransactionStatus::AcceptedOnL2,
+                block_hash: FieldElement::ZERO,
+                block_number: 0,
+                messages_sent: vec![],
+                events: vec![],
+            },
+        ))
+    }
+
+    fn block(block_number: u64, transactions: Vec<Transaction>) -> BlockWithTxs {
+        BlockWithTxs {
+            status: BlockStatus::AcceptedOnL2,
+            block_hash: FieldElement::from(block_number + 100),
+            parent_hash: FieldElement::from(block_number + 99),
+            block_number,
+            new_root: FieldElement::from(block_number),
+            timestamp: block_number,
+            sequencer_address: FieldElement::ZERO,
+            transactions,
+        }
+    }
+
+    /// Return an empty directory unique to the calling test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "beerus-block-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn retention(max_blocks: usize) -> BlockRetention {
+        BlockRetention {
+            max_blocks,
+            max_bytes: None,
+        }
+    }
+
+    /// Test that the oldest blocks are evicted once the cache holds more than `max_blocks`.
+    #[test]
+    fn given_max_blocks_when_inserting_more_blocks_then_oldest_are_evicted() {
+        let mut cache = BlockCache::new(retention(2));
+        for block_number in 1..=3 {
+            cache.insert(block(block_number, vec![])).unwrap();
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(1).is_none());
+        assert!(cache.get_by_hash(&FieldElement::from(101u64)).is_none());
+        assert_eq!(cache.latest().unwrap().block_number, 3);
+        assert_eq!(
+            cache
+                .values()
+                .map(|block| block.block_number)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    /// Test that the oldest blocks are evicted once the cache holds more than `max_bytes`.
+    #[test]
+    fn given_max_bytes_when_inserting_more_blocks_then_oldest_are_evicted() {
+        let block_size = serde_json::to_vec(&block(1, vec![])).unwrap().len();
+        let mut cache = BlockCache::new(BlockRetention {
+            max_blocks: 10,
+            max_bytes: Some(2 * block_size + 1),
+        });
+        for block_number in 1..=3 {
+            cache.insert(block(block_number, vec![])).unwrap();
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(1).is_none());
+        assert!(cache.total_bytes() <= 2 * block_size + 1);
+    }
+
+    /// Test that re-inserting a block number replaces the cached block and its hash.
+    #[test]
+    fn given_cached_block_when_inserting_same_number_then_block_is_replaced() {
+        let mut cache = BlockCache::new(retention(10));
+        cache.insert(block(1, vec![])).unwrap();
+        let bytes = cache.total_bytes();
+
+        let mut replacement = block(1, vec![]);
+        replacement.block_hash = FieldElement::from(42u64);
+        cache.insert(replacement).unwrap();
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.total_bytes(), bytes);
+        assert!(cache.get_by_hash(&FieldElement::from(101u64)).is_none());
+        assert_eq!(
+            cache
+                .get_by_hash(&FieldElement::from(42u64))
+                .unwrap()
+                .block_number,
+            1
+        );
+    }
+
+    /// Test that a transaction is found by its hash in the cached blocks.
+    #[test]
+    fn given_cached_transactions_when_find_transaction_then_transaction_is_returned() {
+        let mut cache = BlockCache::new(retention(10));
+        cache
+            .insert(block(1, vec![invoke_transaction(1), invoke_transaction(2)]))
+            .unwrap();
+        cache.insert(block(2, vec![invoke_transaction(3)])).unwrap();
+
+        assert!(cache.find_transaction(&FieldElement::from(2u64)).is_some());
+        assert!(cache.find_transaction(&FieldElement::from(3u64)).is_some());
+        assert!(cache.find_transaction(&FieldElement::from(4u64)).is_none());
+    }
+
+    /// Test that the transactions of evicted and replaced blocks are no longer found.
+    #[test]
+    fn given_evicted_or_replaced_block_when_find_transaction_then_transactions_are_dropped() {
+        let mut cache = BlockCache::new(retention(1));
+        cache.insert(block(1, vec![invoke_transaction(1)])).unwrap();
+        cache.insert(block(2, vec![invoke_transaction(2)])).unwrap();
+
+        assert!(cache.find_transaction(&FieldElement::from(1u64)).is_none());
+        assert!(cache.find_transaction(&FieldElement::from(2u64)).is_some());
+
+        cache.insert(block(2, vec![invoke_transaction(3)])).unwrap();
+
+        assert!(cache.find_transaction(&FieldElement::from(2u64)).is_none());
+        assert!(cache.find_transaction(&FieldElement::from(3u64)).is_some());
+    }
+
+    /// Test that receipts are only cached along with their block, and dropped with it.
+    #[test]
+    fn given_cached_block_when_insert_receipts_then_receipts_are_kept_with_the_block() {
+        let mut cache = BlockCache::new(retention(10));
+        cache.insert(block(1, vec![invoke_transaction(1)])).unwrap();
+        let bytes = cache.total_bytes();
+        let block_hash = FieldElement::from(101u64);
+
+        // Receipts of a block that is not cached are dropped.
+        cache
+            .insert_receipts(&FieldElement::from(102u64), vec![invoke_receipt(2)])
+            .unwrap();
+        assert!(cache.receipts(&FieldElement::from(102u64)).is_none());
+        assert!(cache.receipts(&block_hash).is_none());
+
+        cache
+            .insert_receipts(&block_hash, vec![invoke_receipt(1)])
+            .unwrap();
+        assert_eq!(cache.receipts(&block_hash).map(<[_]>::len), Some(1));
+        assert!(cache.total_bytes() > bytes);
+
+        cache.insert(block(1, vec![invoke_transaction(1)])).unwrap();
+        assert!(cache.receipts(&block_hash).is_none());
+        assert_eq!(cache.total_bytes(), bytes);
+    }
+
+    /// Test that blocks persisted by a cache are loaded when the directory is reopened.
+    #[test]
+    fn given_persisted_blocks_when_open_then_blocks_are_loaded() {
+        let dir = temp_dir("reopen");
+        {
+            let mut cache = BlockCache::open(&dir, retention(2)).unwrap();
+            for block_number in 1..=3 {
+                cache
+                    .insert(block(block_number, vec![invoke_transaction(block_number)]))
+                    .unwrap();
+            }
+        }
+        // The evicted block is removed from disk as well.
+        assert!(!dir.join("1.json").exists());
+
+        let cache = BlockCache::open(&dir, retention(2)).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.latest().unwrap().block_number, 3);
+        assert!(cache.find_transaction(&FieldElement::from(2u64)).is_some());
+
+        // A smaller retention window evicts the oldest persisted blocks.
+        let cache = BlockCache::open(&dir, retention(1)).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(!dir.join("2.json").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test that truncating the cache removes the blocks from memory and disk.
+    #[test]
+    fn given_persisted_blocks_when_truncate_then_blocks_above_are_removed() {
+        let dir = temp_dir("truncate");
+        let mut cache = BlockCache::open(&dir, retention(10)).unwrap();
+        for block_number in 1..=4 {
+            cache
+                .insert(block(block_number, vec![invoke_transaction(block_number)]))
+                .unwrap();
+        }
+
+        let removed = cache.truncate(3);
+
+        assert_eq!(
+            removed
+                .iter()
+                .map(|block| block.block_number)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(cache.latest().unwrap().block_number, 2);
+        assert!(cache.get_by_hash(&FieldElement::from(103u64)).is_none());
+        assert!(cache.find_transaction(&FieldElement::from(4u64)).is_none());
+        assert!(!dir.join("3.json").exists());
+        assert_eq!(BlockCache::open(&dir, retention(10)).unwrap().len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test that unreadable block files are discarded when the cache is opened.
+    #[test]
+    fn given_corrupt_block_file_when_open_then_file_is_discarded() {
+        let dir = temp_dir("corrupt");
+        {
+            let mut cache = BlockCache::open(&dir, retention(10)).unwrap();
+            cache.insert(block(1, vec![])).unwrap();
+        }
+        fs::write(dir.join("2.json"), b"not a block").unwrap();
+
+        let cache = BlockCache::open(&dir, retention(10)).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(1).is_some());
+        assert!(!dir.join("2.json").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
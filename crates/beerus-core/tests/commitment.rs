@@ -0,0 +1,194 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+pub mod common;
+use common::{mock_block_with_txs, mock_invoke_tx_v1};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beerus_core::lightclient::starknet::commitment::{
+        calculate_block_hash, calculate_event_commitment, calculate_event_hash,
+        calculate_patricia_root, calculate_transaction_commitment, has_legacy_block_hash,
+        GOERLI_FIRST_0_7_BLOCK, MAINNET_FIRST_0_7_BLOCK,
+    };
+    use beerus_core::lightclient::starknet::transaction_hash::{
+        calculate_transaction_hash, may_have_legacy_hash,
+    };
+    use starknet::core::{
+        chain_id,
+        crypto::{compute_hash_on_elements, pedersen_hash},
+        types::{
+            BlockStatus, Event, FieldElement, InvokeTransaction, InvokeTransactionV0, Transaction,
+        },
+    };
+
+    fn edge_hash(child: FieldElement, path: u64, len: u64) -> FieldElement {
+        pedersen_hash(&child, &FieldElement::from(path)) + FieldElement::from(len)
+    }
+
+    fn felt(value: u64) -> FieldElement {
+        FieldElement::from(value)
+    }
+
+    #[test]
+    fn given_no_leaves_when_calculate_patricia_root_should_return_zero() {
+        assert_eq!(calculate_patricia_root(&[]), FieldElement::ZERO);
+    }
+
+    #[test]
+    fn given_single_leaf_when_calculate_patricia_root_should_return_full_edge() {
+        let root = calculate_patricia_root(&[felt(42)]);
+
+        assert_eq!(root, edge_hash(felt(42), 0, 64));
+    }
+
+    #[test]
+    fn given_two_leaves_when_calculate_patricia_root_should_return_edge_to_binary_node() {
+        let root = calculate_patricia_root(&[felt(1), felt(2)]);
+
+        let binary = pedersen_hash(&felt(1), &felt(2));
+        assert_eq!(root, edge_hash(binary, 0, 63));
+    }
+
+    #[test]
+    fn given_three_leaves_when_calculate_patricia_root_should_hash_missing_sibling_as_edge() {
+        let root = calculate_patricia_root(&[felt(1), felt(2), felt(3)]);
+
+        // Leaf 2 (0b10) has no sibling at index 3, it is reached through a 1-bit edge.
+        let left = pedersen_hash(&felt(1), &felt(2));
+        let right = edge_hash(felt(3), 0, 1);
+        let binary = pedersen_hash(&left, &right);
+        assert_eq!(root, edge_hash(binary, 0, 62));
+    }
+
+    #[test]
+    fn given_invoke_transaction_when_calculate_transaction_commitment_should_hash_signature() {
+        let mut tx = mock_invoke_tx_v1(String::from("0x1234"));
+        tx.signature = vec![felt(5), felt(6)];
+        let transactions = vec![Transaction::Invoke(InvokeTransaction::V1(tx))];
+
+        let commitment = calculate_transaction_commitment(&transactions);
+
+        let leaf = pedersen_hash(
+            &FieldElement::from_hex_be("0x1234").unwrap(),
+            &compute_hash_on_elements(&[felt(5), felt(6)]),
+        );
+        assert_eq!(commitment, edge_hash(leaf, 0, 64));
+    }
+
+    #[test]
+    fn given_events_when_calculate_event_commitment_should_hash_events_in_order() {
+        let first = Event {
+            from_address: felt(1),
+            keys: vec![felt(2)],
+            data: vec![felt(3), felt(4)],
+        };
+        let second = Event {
+            from_address: felt(5),
+            keys: vec![],
+            data: vec![],
+        };
+
+        let first_hash = calculate_event_hash(&first);
+        let commitment = calculate_event_commitment(&[first, second.clone()]);
+
+        assert_eq!(
+            first_hash,
+            compute_hash_on_elements(&[
+                felt(1),
+                compute_hash_on_elements(&[felt(2)]),
+                compute_hash_on_elements(&[felt(3), felt(4)]),
+            ])
+        );
+        let binary = pedersen_hash(&first_hash, &calculate_event_hash(&second));
+        assert_eq!(commitment, edge_hash(binary, 0, 63));
+    }
+
+    #[test]
+    fn given_empty_block_when_calculate_block_hash_should_hash_header_fields() {
+        let block = mock_block_with_txs(vec![], 10, BlockStatus::AcceptedOnL1, FieldElement::ZERO);
+
+        let block_hash = calculate_block_hash(&block, &[]);
+
+        let expected_result = compute_hash_on_elements(&[
+            felt(10),
+            block.new_root,
+            block.sequencer_address,
+            felt(block.timestamp),
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            block.parent_hash,
+        ]);
+        assert_eq!(block_hash, expected_result);
+    }
+
+    #[test]
+    fn given_block_before_v0_7_when_has_legacy_block_hash_should_return_true() {
+        assert!(has_legacy_block_hash(
+            chain_id::MAINNET,
+            MAINNET_FIRST_0_7_BLOCK - 1
+        ));
+        assert!(!has_legacy_block_hash(
+            chain_id::MAINNET,
+            MAINNET_FIRST_0_7_BLOCK
+        ));
+        assert!(has_legacy_block_hash(
+            chain_id::TESTNET,
+            GOERLI_FIRST_0_7_BLOCK - 1
+        ));
+        assert!(!has_legacy_block_hash(
+            chain_id::TESTNET,
+            GOERLI_FIRST_0_7_BLOCK
+        ));
+        assert!(!has_legacy_block_hash(chain_id::TESTNET2, 0));
+    }
+
+    #[test]
+    fn given_invoke_v1_transaction_when_calculate_transaction_hash_should_hash_fields() {
+        let mut tx = mock_invoke_tx_v1(String::from("0x0"));
+        tx.sender_address = felt(7);
+        tx.calldata = vec![felt(1), felt(2)];
+        tx.max_fee = felt(100);
+        tx.nonce = felt(3);
+        let transaction = Transaction::Invoke(InvokeTransaction::V1(tx));
+
+        let tx_hash = calculate_transaction_hash(&transaction, chain_id::TESTNET);
+
+        let expected_result = compute_hash_on_elements(&[
+            FieldElement::from_byte_slice_be(b"invoke").unwrap(),
+            FieldElement::ONE,
+            felt(7),
+            FieldElement::ZERO,
+            compute_hash_on_elements(&[felt(1), felt(2)]),
+            felt(100),
+            chain_id::TESTNET,
+            felt(3),
+        ]);
+        assert_eq!(tx_hash, expected_result);
+    }
+
+    #[test]
+    fn given_transaction_types_when_may_have_legacy_hash_should_only_match_pre_v0_8_types() {
+        let tx = mock_invoke_tx_v1(String::from("0x0"));
+        let legacy_tx = InvokeTransactionV0 {
+            transaction_hash: tx.transaction_hash,
+            max_fee: tx.max_fee,
+            signature: vec![],
+            nonce: tx.nonce,
+            contract_address: tx.sender_address,
+            entry_point_selector: FieldElement::ONE,
+            calldata: vec![],
+        };
+
+        assert!(may_have_legacy_hash(&Transaction::Invoke(
+            InvokeTransaction::V0(legacy_tx)
+        )));
+        assert!(!may_have_legacy_hash(&Transaction::Invoke(
+            InvokeTransaction::V1(tx)
+        )));
+    }
+}
@@ -0,0 +1,76 @@
+#!/bin/bash
+
+# This script captures a `pathfinder_getProof` fixture for `tests/proofs.rs`.
+#
+# It will in order:
+# - fetch the proof of the given contract and storage keys at the given block,
+# - fetch the state root of the block and the value of every storage key,
+# - store the response verbatim in `responses/<name>.json`,
+# - store the fixture describing it in `<name>.json`.
+#
+# The memberships are derived from the values, an unset key being a non-member. The state root
+# must be checked against the `LogStateUpdate` event of the block on L1 before committing.
+#
+# Usage: capture.sh <rpc url> <name> <block number> <contract address> [storage key...]
+
+set -euo pipefail
+
+if [ $# -lt 4 ]; then
+  echo "Usage: $0 <rpc url> <name> <block number> <contract address> [storage key...]" >&2
+  exit 1
+fi
+
+rpc_url=$1
+name=$2
+block_number=$3
+contract_address=$4
+shift 4
+storage_keys=("$@")
+
+dir=$(dirname "$0")
+
+# Sends a JSON-RPC request and prints its response.
+#
+# @param $1 - the method name
+# @param $2 - the params as a JSON array
+rpc () {
+  curl -sf -X POST "$rpc_url" \
+    -H 'content-type: application/json' \
+    -d "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"$1\",\"params\":$2}"
+}
+
+keys_json=$(printf '%s\n' "${storage_keys[@]}" | jq -R . | jq -sc 'map(select(. != ""))')
+block_id="{\"block_number\":$block_number}"
+
+mkdir -p "$dir/responses"
+rpc pathfinder_getProof "[$block_id,\"$contract_address\",$keys_json]" > "$dir/responses/$name.json"
+state_root=$(rpc starknet_getStateUpdate "[$block_id]" | jq -r .result.new_root)
+contract=$(jq -r 'if .result.contract_data == null then "non_member" else "member" end' \
+  "$dir/responses/$name.json")
+
+storage_values=()
+for key in "${storage_keys[@]}"; do
+  storage_values+=("$(rpc starknet_getStorageAt "[\"$contract_address\",\"$key\",$block_id]" \
+    | jq -r .result)")
+done
+values_json=$(printf '%s\n' "${storage_values[@]}" | jq -R . | jq -sc 'map(select(. != ""))')
+
+jq -n \
+  --arg response "responses/$name.json" \
+  --argjson block_number "$block_number" \
+  --arg state_root "$state_root" \
+  --arg contract_address "$contract_address" \
+  --argjson storage_keys "$keys_json" \
+  --argjson storage_values "$values_json" \
+  --arg contract "$contract" \
+  '{
+    description: "pathfinder_getProof response captured at block \($block_number)",
+    response: $response,
+    block_number: $block_number,
+    state_root: $state_root,
+    contract_address: $contract_address,
+    storage_keys: $storage_keys,
+    storage_values: $storage_values,
+    contract: $contract,
+    storage: $storage_values | map(if test("^0x0*$") then "non_member" else "member" end)
+  }' > "$dir/$name.json"
@@ -2,23 +2,33 @@
 use beerus_core::{
     config::{Config, DEFAULT_BEERUS_RPC_ADDR, DEFAULT_HELIOS_RPC_ADDR},
     lightclient::{
+        beerus::ProvenState,
         ethereum::MockEthereumLightClient,
-        starknet::{storage_proof::GetProofOutput, MockStarkNetLightClient},
+        starknet::{
+            storage_proof::{
+                class_leaf_hash, ContractData, GetClassProofOutput, GetProofOutput, TrieHash,
+            },
+            MockStarkNetLightClient,
+        },
     },
 };
 use ethers::types::Address;
 use httpmock::{prelude::*, Mock};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use starknet::core::crypto::pedersen_hash;
 use starknet::core::types::{
     BlockStatus, BlockWithTxs, BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1,
     BroadcastedTransaction, FieldElement, InvokeTransactionV1, Transaction,
 };
+use starknet_crypto::poseidon_hash;
 use std::fs;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+pub mod trie;
+
 pub fn mock_clients() -> (Config, MockEthereumLightClient, MockStarkNetLightClient) {
     (
         Config::default(),
@@ -69,6 +79,129 @@ pub fn mock_block_with_txs(
     }
 }
 
+/// StarkNet state root the proof in `tests/common/data/data.json` is anchored to.
+pub const MOCK_PROOF_STATE_ROOT: &str =
+    "0x47f25798a804800b657d4e1508776e3c3c70f0d7587d125a558208f88570aa7";
+/// Contract address the proof in `tests/common/data/data.json` was requested for.
+pub const MOCK_PROOF_CONTRACT_ADDRESS: &str =
+    "0x4d4e07157aeb54abeb64f5792145f2e8db1c83bda01a8f06e050be18cfb8153";
+
+/// L1 proven state matching the proof in `tests/common/data/data.json`, at StarkNet block 1.
+pub fn mock_proven_state() -> ProvenState {
+    ProvenState {
+        block_number: 1,
+        state_root: FieldElement::from_hex_be(MOCK_PROOF_STATE_ROOT).unwrap(),
+        block_hash: FieldElement::ONE,
+        l1_block: 1,
+    }
+}
+
+pub fn mock_proof_output() -> GetProofOutput {
+    let path = "tests/common/data/data.json";
+    let s = fs::read_to_string(path).unwrap();
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct JsonOutput {
+        result: GetProofOutput,
+    }
+    let output: JsonOutput = serde_json::from_str(&s).unwrap();
+    output.result
+}
+
+/// Address of a contract that is not deployed in the state of `mock_absent_contract_proof`.
+pub const MOCK_ABSENT_CONTRACT_ADDRESS: &str = "0x1234";
+
+/// Proven state of a contract tree holding a single leaf, and the non-membership proof of
+/// `MOCK_ABSENT_CONTRACT_ADDRESS` against it.
+pub fn mock_absent_contract_proof() -> (ProvenState, GetProofOutput) {
+    let leaf_path = FieldElement::ONE;
+    let leaf_hash = FieldElement::from_hex_be("0x42").unwrap();
+    let state_root = pedersen_hash(&leaf_hash, &leaf_path) + FieldElement::from(251_u64);
+
+    let proof: GetProofOutput = serde_json::from_value(json!({
+        "contract_proof": [
+            {
+                "edge": {
+                    "path": { "value": format!("0x{leaf_path:x}"), "len": 251 },
+                    "child": format!("0x{leaf_hash:x}"),
+                }
+            }
+        ],
+        "contract_data": null,
+    }))
+    .unwrap();
+
+    let proven_state = ProvenState {
+        state_root,
+        ..mock_proven_state()
+    };
+    (proven_state, proof)
+}
+
+/// Proven state of a contract tree holding a single contract with the given storage, and the
+/// proof of `storage_keys` of that contract against it.
+pub fn mock_storage_proof(
+    contract_address: FieldElement,
+    storage: &[(FieldElement, FieldElement)],
+    storage_keys: &[FieldElement],
+) -> (ProvenState, GetProofOutput) {
+    let storage_trie = trie::merkle_tree(storage, TrieHash::Pedersen);
+    let class_hash = FieldElement::from_hex_be("0x1234").unwrap();
+    let nonce = FieldElement::ONE;
+    let contract_state_hash = pedersen_hash(
+        &pedersen_hash(&pedersen_hash(&class_hash, &storage_trie.root()), &nonce),
+        &FieldElement::ZERO,
+    );
+    let contract_trie = trie::merkle_tree(
+        &[(contract_address, contract_state_hash)],
+        TrieHash::Pedersen,
+    );
+
+    let proof = GetProofOutput {
+        contracts_tree_root: None,
+        classes_tree_root: None,
+        contract_proof: contract_trie.get_proof(contract_address),
+        contract_data: Some(ContractData {
+            class_hash,
+            nonce,
+            root: storage_trie.root(),
+            contract_state_hash_version: FieldElement::ZERO,
+            storage_proofs: storage_keys
+                .iter()
+                .map(|key| storage_trie.get_proof(*key))
+                .collect(),
+        }),
+    };
+
+    let proven_state = ProvenState {
+        state_root: contract_trie.root(),
+        ..mock_proven_state()
+    };
+    (proven_state, proof)
+}
+
+/// Class tree holding a single declared class, and the membership proof of that class.
+pub fn mock_class_proof(
+    class_hash: FieldElement,
+    compiled_class_hash: FieldElement,
+) -> (FieldElement, GetClassProofOutput) {
+    let leaf_hash = class_leaf_hash(compiled_class_hash);
+    let classes_tree_root = poseidon_hash(leaf_hash, class_hash) + FieldElement::from(251_u64);
+
+    let proof: GetClassProofOutput = serde_json::from_value(json!({
+        "class_proof": [
+            {
+                "edge": {
+                    "path": { "value": format!("0x{class_hash:x}"), "len": 251 },
+                    "child": format!("0x{leaf_hash:x}"),
+                }
+            }
+        ],
+    }))
+    .unwrap();
+    (classes_tree_root, proof)
+}
+
 pub fn mock_get_contract_storage_proof(server: &MockServer) -> (Mock, GetProofOutput) {
     let path = "tests/common/data/data.json";
     let s = fs::read_to_string(path).unwrap();
@@ -123,6 +256,30 @@ pub fn mock_get_storage_at(server: &MockServer) -> Mock {
     })
 }
 
+pub fn mock_get_storage_at_proven_contract(server: &MockServer) -> Mock {
+    server.mock(|when, then| {
+        when.method(POST).path("/").json_body(json!({
+            "id":1,
+            "jsonrpc":"2.0",
+            "method":"starknet_getStorageAt",
+            "params":[
+                MOCK_PROOF_CONTRACT_ADDRESS,
+                "0x1",
+                {
+                    "block_number": 1
+                }
+            ]
+        }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "0x0"
+            }));
+    })
+}
+
 pub fn mock_get_nonce(server: &MockServer) -> Mock {
     server.mock(|when, then| {
         when.method(POST).path("/").json_body(json!({
@@ -190,5 +347,9 @@ pub fn mock_server_config(server: &MockServer) -> Config {
         beerus_rpc_address: Some(SocketAddr::from_str(DEFAULT_BEERUS_RPC_ADDR).unwrap()),
         helios_rpc_address: Some(DEFAULT_HELIOS_RPC_ADDR),
         ethereum_checkpoint: None,
+        state_diff_sample_size: None,
+        block_cache_max_blocks: None,
+        block_cache_max_bytes: None,
+        backfill_concurrency: None,
     }
 }
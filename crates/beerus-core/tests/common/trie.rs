@@ -0,0 +1,71 @@
+//! Random StarkNet Patricia-Merkle trees and proof mutations, used to check `storage_proof`
+//! against arbitrary tree shapes.
+
+use beerus_core::lightclient::starknet::{
+    merkle::MerkleTree,
+    storage_proof::{ProofNode, TrieHash},
+};
+use rand::Rng;
+use serde_json::{json, Value};
+use starknet::core::types::FieldElement;
+
+/// Return a random field element lower than `2**251`, valid both as a key and as a value.
+pub fn random_felt<R: Rng>(rng: &mut R) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    rng.fill(&mut bytes);
+    bytes[0] &= 0x07;
+    FieldElement::from_bytes_be(&bytes).unwrap()
+}
+
+/// Return `count` random leaves. Random keys rarely share long prefixes, so the tree is made of
+/// a few binary nodes at the top and long edges down to the leaves.
+pub fn sparse_leaves<R: Rng>(rng: &mut R, count: usize) -> Vec<(FieldElement, FieldElement)> {
+    (0..count)
+        .map(|_| (random_felt(rng), random_felt(rng)))
+        .collect()
+}
+
+/// Return `count` leaves at consecutive keys from a random offset. Consecutive keys share all
+/// but their last bits, so the tree is a long edge down to a dense subtree of binary nodes.
+pub fn dense_leaves<R: Rng>(rng: &mut R, count: usize) -> Vec<(FieldElement, FieldElement)> {
+    let offset = random_felt(rng).to_bytes_be()[31] as u64;
+    (0..count as u64)
+        .map(|i| (FieldElement::from(offset + i), random_felt(rng)))
+        .collect()
+}
+
+/// Return the tree holding the given leaves.
+pub fn merkle_tree(leaves: &[(FieldElement, FieldElement)], trie_hash: TrieHash) -> MerkleTree {
+    let mut tree = MerkleTree::new(trie_hash);
+    for (key, value) in leaves {
+        tree.insert(*key, *value).unwrap();
+    }
+    tree
+}
+
+/// Return every variant of a proof node with a single field changed.
+pub fn node_mutations(node: &ProofNode) -> Vec<ProofNode> {
+    let node = serde_json::to_value(node).unwrap();
+    let pointers: &[&str] = if node.get("binary").is_some() {
+        &["/binary/left", "/binary/right"]
+    } else {
+        &["/edge/child", "/edge/path/value", "/edge/path/len"]
+    };
+
+    pointers
+        .iter()
+        .map(|pointer| {
+            let mut mutated = node.clone();
+            let field = mutated.pointer_mut(pointer).unwrap();
+            *field = match field {
+                Value::Number(len) => json!(len.as_u64().unwrap() ^ 1),
+                Value::String(felt) => {
+                    let felt = FieldElement::from_hex_be(felt).unwrap();
+                    json!(format!("0x{:x}", felt + FieldElement::ONE))
+                }
+                _ => unreachable!(),
+            };
+            serde_json::from_value(mutated).unwrap()
+        })
+        .collect()
+}
@@ -6,6 +6,10 @@ mod tests {
         Config, DEFAULT_DATA_DIR, DEFAULT_ETHEREUM_NETWORK, DEFAULT_POLL_INTERVAL_SECS,
         STARKNET_GOERLI_CC_ADDRESS,
     };
+    use beerus_core::lightclient::{
+        backfill::DEFAULT_BACKFILL_CONCURRENCY,
+        block_cache::{BlockRetention, DEFAULT_BLOCK_CACHE_MAX_BLOCKS},
+    };
     use ethers::types::Address;
     use helios::config::networks::Network;
     use serial_test::serial;
@@ -130,6 +134,68 @@ mod tests {
         assert_eq!(cfg.get_poll_interval(), DEFAULT_POLL_INTERVAL_SECS);
     }
 
+    #[test]
+    #[serial]
+    fn none_from_block_cache_limits_returns_default_retention() {
+        let mut cfg = Config::default();
+
+        cfg.block_cache_max_blocks = None;
+        cfg.block_cache_max_bytes = None;
+        assert_eq!(
+            cfg.get_block_retention(),
+            BlockRetention {
+                max_blocks: DEFAULT_BLOCK_CACHE_MAX_BLOCKS,
+                max_bytes: None,
+            }
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn none_from_backfill_concurrency_returns_default_val() {
+        let mut cfg = Config::default();
+
+        cfg.backfill_concurrency = None;
+        assert_eq!(cfg.get_backfill_concurrency(), DEFAULT_BACKFILL_CONCURRENCY);
+    }
+
+    /// Test `from_env` function with the backfill concurrency set.
+    #[test]
+    #[serial]
+    fn backfill_concurrency_env_set_returns_concurrency() {
+        Config::clean_env();
+        env::set_var("ETHEREUM_CONSENSUS_RPC_URL", "http://localhost:8545");
+        env::set_var("ETHEREUM_EXECUTION_RPC_URL", "http://localhost:8545");
+        env::set_var("STARKNET_RPC_URL", "http://localhost:8545");
+        env::set_var("BACKFILL_CONCURRENCY", "16");
+
+        let cfg = Config::from_env();
+        Config::clean_env();
+        assert_eq!(cfg.get_backfill_concurrency(), 16);
+    }
+
+    /// Test `from_env` function with the block cache limits set.
+    #[test]
+    #[serial]
+    fn block_cache_envs_set_returns_retention() {
+        Config::clean_env();
+        env::set_var("ETHEREUM_CONSENSUS_RPC_URL", "http://localhost:8545");
+        env::set_var("ETHEREUM_EXECUTION_RPC_URL", "http://localhost:8545");
+        env::set_var("STARKNET_RPC_URL", "http://localhost:8545");
+        env::set_var("BLOCK_CACHE_MAX_BLOCKS", "64");
+        env::set_var("BLOCK_CACHE_MAX_BYTES", "1048576");
+
+        let cfg = Config::from_env();
+        Config::clean_env();
+        assert_eq!(
+            cfg.get_block_retention(),
+            BlockRetention {
+                max_blocks: 64,
+                max_bytes: Some(1048576),
+            }
+        );
+    }
+
     /// Test `from_env` function.
     #[test]
     #[serial]
@@ -0,0 +1,87 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod tests {
+    use beerus_core::lightclient::starknet::events::{
+        event_matches_filter, is_complete_block_slice,
+    };
+    use starknet::core::types::{EmittedEvent, EventFilter, FieldElement};
+
+    fn felt(value: u64) -> FieldElement {
+        FieldElement::from(value)
+    }
+
+    fn filter(address: Option<u64>, keys: Option<Vec<Vec<FieldElement>>>) -> EventFilter {
+        EventFilter {
+            from_block: None,
+            to_block: None,
+            address: address.map(felt),
+            keys,
+        }
+    }
+
+    fn emitted_event(data: u64) -> EmittedEvent {
+        EmittedEvent {
+            from_address: felt(1),
+            keys: vec![felt(2)],
+            data: vec![felt(data)],
+            block_hash: felt(3),
+            block_number: 10,
+            transaction_hash: felt(4),
+        }
+    }
+
+    #[test]
+    fn given_matching_address_and_keys_when_event_matches_filter_should_return_true() {
+        let filter = filter(Some(1), Some(vec![vec![felt(2), felt(3)], vec![]]));
+
+        assert!(event_matches_filter(felt(1), &[felt(3), felt(9)], &filter));
+    }
+
+    #[test]
+    fn given_other_address_or_key_when_event_matches_filter_should_return_false() {
+        let filter = filter(Some(1), Some(vec![vec![felt(2)]]));
+
+        assert!(!event_matches_filter(felt(5), &[felt(2)], &filter));
+        assert!(!event_matches_filter(felt(1), &[felt(3)], &filter));
+        assert!(!event_matches_filter(felt(1), &[], &filter));
+    }
+
+    #[test]
+    fn given_contiguous_events_when_is_complete_block_slice_should_check_bounds() {
+        let expected = vec![emitted_event(1), emitted_event(2), emitted_event(3)];
+
+        assert!(is_complete_block_slice(&expected, &expected, true, true));
+        assert!(is_complete_block_slice(
+            &expected,
+            &expected[1..],
+            false,
+            true
+        ));
+        assert!(is_complete_block_slice(
+            &expected,
+            &expected[..2],
+            true,
+            false
+        ));
+        assert!(!is_complete_block_slice(
+            &expected,
+            &expected[1..],
+            true,
+            true
+        ));
+        assert!(!is_complete_block_slice(
+            &expected,
+            &expected[..2],
+            true,
+            true
+        ));
+    }
+
+    #[test]
+    fn given_omitted_event_when_is_complete_block_slice_should_return_false() {
+        let expected = vec![emitted_event(1), emitted_event(2), emitted_event(3)];
+        let page = vec![emitted_event(1), emitted_event(3)];
+
+        assert!(!is_complete_block_slice(&expected, &page, false, false));
+    }
+}
@@ -3,7 +3,8 @@
 pub mod common;
 use common::{
     mock_call, mock_get_contract_storage_proof, mock_get_nonce, mock_get_storage_at,
-    mock_server_config,
+    mock_get_storage_at_proven_contract, mock_proven_state, mock_server_config,
+    MOCK_PROOF_CONTRACT_ADDRESS,
 };
 
 #[cfg(test)]
@@ -26,33 +27,32 @@ mod test {
     async fn given_normal_conditions_when_starknet_get_storage_at_should_work() {
         // Start a lightweight mock server.
         let server = MockServer::start();
-        let mock_request = mock_get_storage_at(&server);
+        let mock_request = mock_get_storage_at_proven_contract(&server);
+        let (mock_proof_request, _) = mock_get_contract_storage_proof(&server);
         let config = mock_server_config(&server);
 
         let starknet_lightclient = Box::new(StarkNetLightClientImpl::new(&config).unwrap());
-        let mut helios_lightclient = MockEthereumLightClient::new();
-
-        helios_lightclient
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(1)));
+        let helios_lightclient = MockEthereumLightClient::new();
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(helios_lightclient),
             starknet_lightclient,
         );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
 
         let block_id = BlockId::Number(1);
         let storage_var = beerus
             .starknet_get_storage_at(
-                FieldElement::from_str("0x00").unwrap(),
-                FieldElement::from_str("0x00").unwrap(),
+                FieldElement::from_hex_be(MOCK_PROOF_CONTRACT_ADDRESS).unwrap(),
+                FieldElement::ONE,
                 &block_id,
             )
             .await
             .unwrap();
 
         mock_request.assert();
-        assert_eq!(storage_var, FieldElement::from_str("0x01").unwrap());
+        mock_proof_request.assert();
+        assert_eq!(storage_var, FieldElement::ZERO);
     }
 
     #[tokio::test]
@@ -63,9 +63,9 @@ mod test {
 
         let starknet_lightclient = Box::new(StarkNetLightClientImpl::new(&config).unwrap());
         let mut helios_lightclient = MockEthereumLightClient::new();
-        // Mock the `start` method of the Ethereum light client.
+        // Mock the `get_block_number` method of the Ethereum light client.
         helios_lightclient
-            .expect_starknet_last_proven_block()
+            .expect_get_block_number()
             .return_once(move || {
                 Err(JsonRpcError {
                     code: UNKNOWN_ERROR_CODE,
@@ -136,14 +136,12 @@ mod test {
         helios_lightclient
             .expect_call()
             .return_once(move |_req, _block_nb| Ok(vec![1]));
-        helios_lightclient
-            .expect_starknet_last_proven_block()
-            .return_once(move || Ok(U256::from(1)));
         let beerus = BeerusLightClient::new_from_clients(
             config,
             Box::new(helios_lightclient),
             starknet_lightclient,
         );
+        beerus.node.write().await.proven_state = Some(mock_proven_state());
         let storage_var = beerus
             .starknet_call_contract(
                 FieldElement::from_str("0x00").unwrap(),
@@ -166,9 +164,9 @@ mod test {
         let starknet_lightclient = Box::new(StarkNetLightClientImpl::new(&config).unwrap());
         let mut helios_lightclient = MockEthereumLightClient::new();
 
-        // Mock the `start` method of the Ethereum light client.
+        // Mock the `get_block_number` method of the Ethereum light client.
         helios_lightclient
-            .expect_starknet_last_proven_block()
+            .expect_get_block_number()
             .return_once(move || {
                 Err(JsonRpcError {
                     code: UNKNOWN_ERROR_CODE,
@@ -0,0 +1,119 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+#[cfg(test)]
+mod tests {
+    use beerus_core::lightclient::starknet::{
+        merkle::MerkleTree,
+        storage_proof::{class_leaf_hash, GetClassProofOutput, Membership, TrieHash},
+    };
+    use starknet::core::{crypto::pedersen_hash, types::FieldElement};
+    use starknet_crypto::poseidon_hash;
+
+    fn felt(value: &str) -> FieldElement {
+        FieldElement::from_hex_be(value).unwrap()
+    }
+
+    /// Test that an empty tree has a zero root and empty proofs.
+    #[test]
+    fn given_empty_tree_when_root_should_return_zero() {
+        let tree = MerkleTree::new(TrieHash::Pedersen);
+
+        assert_eq!(tree.root(), FieldElement::ZERO);
+        assert!(tree.get_proof(FieldElement::ONE).is_empty());
+    }
+
+    /// Test that the root of a single leaf is the edge from the root down to the leaf.
+    #[test]
+    fn given_single_leaf_when_root_should_hash_edge_to_leaf() {
+        let mut tree = MerkleTree::new(TrieHash::Pedersen);
+        tree.insert(felt("0x5"), felt("0x42")).unwrap();
+
+        let expected = pedersen_hash(&felt("0x42"), &felt("0x5")) + FieldElement::from(251_u64);
+        assert_eq!(tree.root(), expected);
+    }
+
+    /// Test that sibling leaves are hashed in a binary node below a shared edge.
+    #[test]
+    fn given_sibling_leaves_when_root_should_hash_binary_node_below_edge() {
+        let mut tree = MerkleTree::new(TrieHash::Poseidon);
+        tree.insert(felt("0x3"), felt("0x20")).unwrap();
+        tree.insert(felt("0x2"), felt("0x10")).unwrap();
+
+        let binary = poseidon_hash(felt("0x10"), felt("0x20"));
+        let expected = poseidon_hash(binary, FieldElement::ONE) + FieldElement::from(250_u64);
+        assert_eq!(tree.root(), expected);
+        assert_eq!(tree.get_proof(felt("0x2")).len(), 2);
+    }
+
+    /// Test that the root does not depend on the insertion order, and that setting a leaf to
+    /// zero removes it.
+    #[test]
+    fn given_leaves_in_any_order_when_root_should_match() {
+        let leaves = [("0x1", "0x11"), ("0x7", "0x77"), ("0x100", "0x1000")];
+        let mut tree = MerkleTree::new(TrieHash::Pedersen);
+        for (key, value) in leaves.iter() {
+            tree.insert(felt(key), felt(value)).unwrap();
+        }
+        let mut reversed = MerkleTree::new(TrieHash::Pedersen);
+        for (key, value) in leaves.iter().rev() {
+            reversed.insert(felt(key), felt(value)).unwrap();
+        }
+        assert_eq!(tree.root(), reversed.root());
+
+        let mut removed = tree.clone();
+        removed.insert(felt("0x7"), FieldElement::ZERO).unwrap();
+        assert_eq!(removed.get(felt("0x7")), FieldElement::ZERO);
+        assert_ne!(removed.root(), tree.root());
+        removed.insert(felt("0x7"), felt("0x77")).unwrap();
+        assert_eq!(removed.root(), tree.root());
+    }
+
+    /// Test that keys that do not fit in 251 bits are rejected.
+    #[test]
+    fn given_key_larger_than_251_bits_when_insert_should_fail() {
+        let mut tree = MerkleTree::new(TrieHash::Pedersen);
+
+        let res = tree.insert(
+            felt("0x800000000000000000000000000000000000000000000000000000000000000"),
+            FieldElement::ONE,
+        );
+
+        assert!(res.is_err());
+    }
+
+    /// Test that the proofs of the tree verify with `storage_proof`.
+    #[test]
+    fn given_class_tree_when_get_proof_should_verify() {
+        let mut tree = MerkleTree::new(TrieHash::Poseidon);
+        for i in 0..20_u64 {
+            let class_hash = FieldElement::from(i * 7 + 1);
+            let compiled_class_hash = FieldElement::from(i + 1000);
+            tree.insert(class_hash, class_leaf_hash(compiled_class_hash))
+                .unwrap();
+        }
+
+        let member = GetClassProofOutput {
+            class_proof: tree.get_proof(FieldElement::from(8_u64)),
+        };
+        assert_eq!(
+            member.verify(
+                tree.root(),
+                FieldElement::from(8_u64),
+                FieldElement::from(1001_u64)
+            ),
+            Ok(Membership::Member)
+        );
+
+        let non_member = GetClassProofOutput {
+            class_proof: tree.get_proof(FieldElement::from(9_u64)),
+        };
+        assert_eq!(
+            non_member.verify(
+                tree.root(),
+                FieldElement::from(9_u64),
+                FieldElement::from(1001_u64)
+            ),
+            Ok(Membership::NonMember)
+        );
+    }
+}
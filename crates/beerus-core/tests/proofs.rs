@@ -1,34 +1,639 @@
 #![cfg(not(target_arch = "wasm32"))]
 
-use beerus_core::lightclient::starknet::storage_proof::{GetProofOutput, Membership};
+pub mod common;
+use beerus_core::lightclient::starknet::merkle::MerkleTree;
+use beerus_core::lightclient::starknet::storage_proof::{
+    class_leaf_hash, global_state_root, Binary, ContractData, GetClassProofOutput, GetProofOutput,
+    Membership, ProofError, ProofNode, TrieHash, VerificationReport,
+};
+use common::trie::{dense_leaves, merkle_tree, node_mutations, random_felt, sparse_leaves};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::Deserialize;
-use starknet::core::types::FieldElement;
+use serde_json::json;
+use starknet::core::{crypto::pedersen_hash, types::FieldElement};
+use starknet_crypto::poseidon_hash;
 use std::fs;
+use std::path::Path;
+
+/// Number of random tries each property is checked against.
+const CASES: u64 = 32;
 
 #[derive(Debug, Deserialize)]
 struct JsonOutput {
     result: GetProofOutput,
 }
 
-#[test]
-fn non_membership() {
+fn state_root() -> FieldElement {
+    FieldElement::from_hex_be("0x47f25798a804800b657d4e1508776e3c3c70f0d7587d125a558208f88570aa7")
+        .unwrap()
+}
+
+fn contract_address() -> FieldElement {
+    FieldElement::from_hex_be("0x4d4e07157aeb54abeb64f5792145f2e8db1c83bda01a8f06e050be18cfb8153")
+        .unwrap()
+}
+
+fn proof_output() -> GetProofOutput {
     let path = "tests/common/data/data.json";
     let s = fs::read_to_string(path).unwrap();
-
-    let state_root = FieldElement::from_hex_be(
-        "0x47f25798a804800b657d4e1508776e3c3c70f0d7587d125a558208f88570aa7",
-    )
-    .unwrap();
     let j: JsonOutput = serde_json::from_str(&s).unwrap();
-    let output = j.result;
+    j.result
+}
+
+#[test]
+fn non_membership() {
+    let output = proof_output();
 
     let storage_keys = [FieldElement::ONE];
     let storage_values = [FieldElement::TWO];
-    let contract_address = FieldElement::from_hex_be(
-        "0x4d4e07157aeb54abeb64f5792145f2e8db1c83bda01a8f06e050be18cfb8153",
-    )
+    let report = output.verify(
+        state_root(),
+        contract_address(),
+        &storage_keys,
+        &storage_values,
+    );
+
+    assert_eq!(
+        report,
+        Ok(VerificationReport {
+            contract: Membership::Member,
+            storage: vec![Ok(Membership::NonMember)],
+        })
+    );
+}
+
+#[test]
+fn wrong_state_root_reports_hash_mismatch_at_root_node() {
+    let output = proof_output();
+
+    let report = output.verify(
+        FieldElement::ONE,
+        contract_address(),
+        &[FieldElement::ONE],
+        &[FieldElement::ZERO],
+    );
+
+    match report {
+        Err(ProofError::HashMismatch { node, expected, .. }) => {
+            assert_eq!(node, 0);
+            assert_eq!(expected, FieldElement::ONE);
+        }
+        other => panic!("Expected a hash mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn mismatching_keys_and_values_report_length_mismatch() {
+    let output = proof_output();
+
+    let report = output.verify(state_root(), contract_address(), &[FieldElement::ONE], &[]);
+
+    assert_eq!(
+        report,
+        Err(ProofError::LengthMismatch {
+            storage_keys: 1,
+            storage_values: 0,
+            storage_proofs: 1,
+        })
+    );
+}
+
+#[test]
+fn missing_contract_data_is_reported() {
+    let mut output = proof_output();
+    output.contract_data = None;
+
+    let report = output.verify(
+        state_root(),
+        contract_address(),
+        &[FieldElement::ONE],
+        &[FieldElement::ZERO],
+    );
+
+    assert_eq!(report, Err(ProofError::MissingContractData));
+}
+
+#[test]
+fn undeployed_contract_non_membership() {
+    let leaf_path = FieldElement::ONE;
+    let leaf_hash = FieldElement::from_hex_be("0x42").unwrap();
+    let state_root = pedersen_hash(&leaf_hash, &leaf_path) + FieldElement::from(251_u64);
+    let output: GetProofOutput = serde_json::from_value(json!({
+        "contract_proof": [
+            {
+                "edge": {
+                    "path": { "value": "0x1", "len": 251 },
+                    "child": "0x42",
+                }
+            }
+        ],
+        "contract_data": null,
+    }))
     .unwrap();
-    let memberships = output.verify(state_root, contract_address, &storage_keys, &storage_values);
 
-    assert_eq!(memberships, Some(vec![Some(Membership::NonMember)]));
+    let report = output.verify(
+        state_root,
+        FieldElement::from_hex_be("0x1234").unwrap(),
+        &[FieldElement::ONE],
+        &[FieldElement::ZERO],
+    );
+
+    assert_eq!(
+        report,
+        Ok(VerificationReport {
+            contract: Membership::NonMember,
+            storage: vec![Ok(Membership::NonMember)],
+        })
+    );
+
+    // The same proof cannot show that the contract of the leaf is not deployed.
+    let report = output.verify(state_root, leaf_path, &[], &[]);
+    assert_eq!(report, Err(ProofError::MissingContractData));
+}
+
+#[test]
+fn poseidon_global_root() {
+    let mut output = proof_output();
+    let classes_tree_root = FieldElement::from_hex_be("0x1234").unwrap();
+    output.classes_tree_root = Some(classes_tree_root);
+    let global_root = global_state_root(state_root(), classes_tree_root);
+
+    assert_eq!(output.verify_global_root(global_root), Ok(state_root()));
+    let report = output.verify(
+        global_root,
+        contract_address(),
+        &[FieldElement::ONE],
+        &[FieldElement::ZERO],
+    );
+    assert_eq!(report.unwrap().storage, vec![Ok(Membership::NonMember)]);
+
+    // The contract tree root alone is not the global root anymore.
+    assert_eq!(
+        output.verify_global_root(state_root()),
+        Err(ProofError::GlobalRootMismatch {
+            expected: state_root(),
+            actual: global_root,
+        })
+    );
+}
+
+#[test]
+fn class_proof_membership() {
+    let class_hash = FieldElement::from_hex_be("0x5").unwrap();
+    let compiled_class_hash = FieldElement::from_hex_be("0x6").unwrap();
+    let leaf_hash = class_leaf_hash(compiled_class_hash);
+    let classes_tree_root = poseidon_hash(leaf_hash, class_hash) + FieldElement::from(251_u64);
+    let output: GetClassProofOutput = serde_json::from_value(json!({
+        "class_commitment": format!("0x{classes_tree_root:x}"),
+        "class_proof": [
+            {
+                "edge": {
+                    "path": { "value": "0x5", "len": 251 },
+                    "child": format!("0x{leaf_hash:x}"),
+                }
+            }
+        ],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        output.verify(classes_tree_root, class_hash, compiled_class_hash),
+        Ok(Membership::Member)
+    );
+    assert_eq!(
+        output.verify(classes_tree_root, FieldElement::TWO, compiled_class_hash),
+        Ok(Membership::NonMember)
+    );
+    assert_eq!(
+        output.verify(classes_tree_root, class_hash, FieldElement::ONE),
+        Err(ProofError::LeafMismatch {
+            expected: leaf_hash,
+            actual: class_leaf_hash(FieldElement::ONE),
+        })
+    );
+}
+
+/// A `pathfinder_getProof` response captured from a full node, with the memberships it proves.
+///
+/// Fixtures are captured with `tests/common/data/proofs/capture.sh` at a block committed on L1,
+/// the `state_root` being the root of the matching `LogStateUpdate` event. Responses are stored
+/// verbatim under `responses/` and must never be edited by hand, every node is checked by the
+/// mutation test below.
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    response: String,
+    /// StarkNet block the proof was captured at, unknown for `data.json`.
+    block_number: Option<u64>,
+    state_root: String,
+    contract_address: String,
+    storage_keys: Vec<String>,
+    storage_values: Vec<String>,
+    contract: Membership,
+    storage: Vec<Membership>,
+}
+
+fn felts(values: &[String]) -> Vec<FieldElement> {
+    values
+        .iter()
+        .map(|value| FieldElement::from_hex_be(value).unwrap())
+        .collect()
+}
+
+#[test]
+fn fixture_corpus_verifies_and_rejects_every_node_mutation() {
+    let dir = Path::new("tests/common/data/proofs");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+            continue;
+        }
+        let fixture: Fixture = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let response = fs::read_to_string(dir.join(&fixture.response)).unwrap();
+        let output = serde_json::from_str::<JsonOutput>(&response)
+            .unwrap()
+            .result;
+
+        let state_root = FieldElement::from_hex_be(&fixture.state_root).unwrap();
+        let contract_address = FieldElement::from_hex_be(&fixture.contract_address).unwrap();
+        let keys = felts(&fixture.storage_keys);
+        let values = felts(&fixture.storage_values);
+
+        let report = output
+            .verify(state_root, contract_address, &keys, &values)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "{} at block {:?}: {e}",
+                    path.display(),
+                    fixture.block_number
+                )
+            });
+        assert_eq!(report.contract, fixture.contract, "{}", path.display());
+        assert_eq!(
+            report.storage_memberships(),
+            Ok(fixture.storage.clone()),
+            "{}",
+            path.display()
+        );
+
+        for (index, node) in output.contract_proof.iter().enumerate() {
+            for mutated_node in node_mutations(node) {
+                let mut mutated = output.clone();
+                mutated.contract_proof[index] = mutated_node;
+                assert!(matches!(
+                    mutated.verify(state_root, contract_address, &keys, &values),
+                    Err(ProofError::HashMismatch { node, .. }) if node == index
+                ));
+            }
+        }
+        if let Some(contract_data) = &output.contract_data {
+            for (key, proof) in contract_data.storage_proofs.iter().enumerate() {
+                for (index, node) in proof.iter().enumerate() {
+                    for mutated_node in node_mutations(node) {
+                        let mut mutated = output.clone();
+                        mutated.contract_data.as_mut().unwrap().storage_proofs[key][index] =
+                            mutated_node;
+                        let report = mutated
+                            .verify(state_root, contract_address, &keys, &values)
+                            .unwrap();
+                        assert!(matches!(
+                            report.storage[key],
+                            Err(ProofError::HashMismatch { node, .. }) if node == index
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A contract deployed in a random contract tree, with a random storage tree.
+struct RandomState {
+    state_root: FieldElement,
+    contract_address: FieldElement,
+    storage: Vec<(FieldElement, FieldElement)>,
+    storage_trie: MerkleTree,
+    contract_trie: MerkleTree,
+    class_hash: FieldElement,
+    nonce: FieldElement,
+}
+
+impl RandomState {
+    fn new(rng: &mut StdRng, storage: Vec<(FieldElement, FieldElement)>) -> Self {
+        let storage_trie = merkle_tree(&storage, TrieHash::Pedersen);
+        let class_hash = random_felt(rng);
+        let nonce = FieldElement::from(rng.gen::<u64>());
+        let contract_state_hash = pedersen_hash(
+            &pedersen_hash(&pedersen_hash(&class_hash, &storage_trie.root()), &nonce),
+            &FieldElement::ZERO,
+        );
+
+        let contract_address = random_felt(rng);
+        let count = rng.gen_range(0..16);
+        let mut contracts = sparse_leaves(rng, count);
+        contracts.push((contract_address, contract_state_hash));
+        let contract_trie = merkle_tree(&contracts, TrieHash::Pedersen);
+
+        Self {
+            state_root: contract_trie.root(),
+            contract_address,
+            storage,
+            storage_trie,
+            contract_trie,
+            class_hash,
+            nonce,
+        }
+    }
+
+    fn get_proof(&self, keys: &[FieldElement]) -> GetProofOutput {
+        GetProofOutput {
+            contracts_tree_root: None,
+            classes_tree_root: None,
+            contract_proof: self.contract_trie.get_proof(self.contract_address),
+            contract_data: Some(ContractData {
+                class_hash: self.class_hash,
+                nonce: self.nonce,
+                root: self.storage_trie.root(),
+                contract_state_hash_version: FieldElement::ZERO,
+                storage_proofs: keys
+                    .iter()
+                    .map(|key| self.storage_trie.get_proof(*key))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Generate storage leaves, alternating sparse tries (edge-heavy) and dense tries
+/// (binary-heavy).
+fn random_storage(rng: &mut StdRng, case: u64) -> Vec<(FieldElement, FieldElement)> {
+    let count = rng.gen_range(1..64);
+    if case % 2 == 0 {
+        sparse_leaves(rng, count)
+    } else {
+        dense_leaves(rng, count)
+    }
+}
+
+#[test]
+fn random_tries_storage_proofs_verify() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let storage = random_storage(&mut rng, case);
+        let state = RandomState::new(&mut rng, storage);
+
+        // Every set key is a member, random keys are almost surely not.
+        let mut keys: Vec<FieldElement> = state.storage.iter().map(|(key, _)| *key).collect();
+        let mut values: Vec<FieldElement> = state.storage.iter().map(|(_, value)| *value).collect();
+        let absent_key = random_felt(&mut rng);
+        keys.push(absent_key);
+        values.push(FieldElement::ZERO);
+
+        let report = state
+            .get_proof(&keys)
+            .verify(state.state_root, state.contract_address, &keys, &values)
+            .unwrap();
+
+        assert_eq!(report.contract, Membership::Member, "case {case}");
+        let mut expected = vec![Ok(Membership::Member); state.storage.len()];
+        expected.push(Ok(Membership::NonMember));
+        assert_eq!(report.storage, expected, "case {case}");
+    }
+}
+
+#[test]
+fn random_tries_storage_values_are_read_from_the_leaves() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let storage = random_storage(&mut rng, case);
+        let state = RandomState::new(&mut rng, storage);
+
+        let mut keys: Vec<FieldElement> = state.storage.iter().map(|(key, _)| *key).collect();
+        let mut values: Vec<FieldElement> = state.storage.iter().map(|(_, value)| *value).collect();
+        keys.push(random_felt(&mut rng));
+        values.push(FieldElement::ZERO);
+
+        assert_eq!(
+            state.get_proof(&keys).storage_values(&keys),
+            values,
+            "case {case}"
+        );
+    }
+}
+
+#[test]
+fn random_tries_storage_proofs_reject_wrong_values() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let storage = random_storage(&mut rng, case);
+        let state = RandomState::new(&mut rng, storage);
+
+        let (key, value) = state.storage[0];
+        let report = state
+            .get_proof(&[key])
+            .verify(
+                state.state_root,
+                state.contract_address,
+                &[key],
+                &[value + FieldElement::ONE],
+            )
+            .unwrap();
+
+        assert!(
+            matches!(report.storage[0], Err(ProofError::LeafMismatch { .. })),
+            "case {case}"
+        );
+    }
+}
+
+#[test]
+fn random_tries_reject_every_single_node_mutation() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let storage = random_storage(&mut rng, case);
+        let state = RandomState::new(&mut rng, storage);
+
+        let (key, value) = state.storage[rng.gen_range(0..state.storage.len())];
+        let output = state.get_proof(&[key]);
+
+        for (index, node) in output.contract_proof.iter().enumerate() {
+            for mutated_node in node_mutations(node) {
+                let mut mutated = output.clone();
+                mutated.contract_proof[index] = mutated_node;
+                assert!(
+                    matches!(
+                        mutated.verify(state.state_root, state.contract_address, &[key], &[value]),
+                        Err(ProofError::HashMismatch { node, .. }) if node == index
+                    ),
+                    "case {case}, contract proof node {index}"
+                );
+            }
+        }
+
+        let storage_proof = &output.contract_data.as_ref().unwrap().storage_proofs[0];
+        for (index, node) in storage_proof.iter().enumerate() {
+            for mutated_node in node_mutations(node) {
+                let mut mutated = output.clone();
+                mutated.contract_data.as_mut().unwrap().storage_proofs[0][index] = mutated_node;
+                let report = mutated
+                    .verify(state.state_root, state.contract_address, &[key], &[value])
+                    .unwrap();
+                assert!(
+                    matches!(
+                        report.storage[0],
+                        Err(ProofError::HashMismatch { node, .. }) if node == index
+                    ),
+                    "case {case}, storage proof node {index}"
+                );
+            }
+        }
+
+        // Dropping the last node stops the proof above the leaf.
+        let mut truncated = output.clone();
+        truncated.contract_data.as_mut().unwrap().storage_proofs[0].pop();
+        let report = truncated
+            .verify(state.state_root, state.contract_address, &[key], &[value])
+            .unwrap();
+        assert!(report.storage[0].is_err(), "case {case}");
+    }
+}
+
+/// Return the hash the proof points to below its last node when walking down to `key`.
+fn child_hash(proof: &[ProofNode], key: FieldElement) -> FieldElement {
+    let mut bits = key.to_bits_le();
+    bits.reverse();
+    let mut depth = 256 - 251;
+    let mut hash = FieldElement::ZERO;
+    for node in proof {
+        match node {
+            ProofNode::Binary(bin) => {
+                hash = if bits[depth] { bin.right } else { bin.left };
+                depth += 1;
+            }
+            ProofNode::Edge(edge) => {
+                hash = edge.child;
+                depth += serde_json::to_value(&edge.path).unwrap()["len"]
+                    .as_u64()
+                    .unwrap() as usize;
+            }
+        }
+    }
+    hash
+}
+
+#[test]
+fn truncated_proofs_reject_internal_node_hash_as_value() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let storage = random_storage(&mut rng, case);
+        let state = RandomState::new(&mut rng, storage);
+
+        let (key, _) = state.storage[rng.gen_range(0..state.storage.len())];
+        let output = state.get_proof(&[key]);
+        let storage_proof = &output.contract_data.as_ref().unwrap().storage_proofs[0];
+
+        // Stopping the proof at any internal node and claiming its hash as the value must fail.
+        for len in 1..storage_proof.len() {
+            let mut truncated = output.clone();
+            truncated.contract_data.as_mut().unwrap().storage_proofs[0].truncate(len);
+            let forged_value = child_hash(&storage_proof[..len], key);
+            let report = truncated
+                .verify(
+                    state.state_root,
+                    state.contract_address,
+                    &[key],
+                    &[forged_value],
+                )
+                .unwrap();
+            assert!(
+                matches!(report.storage[0], Err(ProofError::PathIncomplete { .. })),
+                "case {case}, proof truncated to {len} nodes"
+            );
+        }
+    }
+}
+
+#[test]
+fn proofs_running_past_the_key_are_rejected() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let (left, right) = (random_felt(&mut rng), random_felt(&mut rng));
+    let extra_node = ProofNode::Binary(Binary { left, right });
+
+    // A leaf whose value is the hash of a binary node, followed by that node in the proof.
+    let key = random_felt(&mut rng);
+    let state = RandomState::new(&mut rng, vec![(key, pedersen_hash(&left, &right))]);
+    let mut output = state.get_proof(&[key]);
+    output.contract_data.as_mut().unwrap().storage_proofs[0].push(extra_node);
+    let node = output.contract_data.as_ref().unwrap().storage_proofs[0].len() - 1;
+
+    let report = output
+        .verify(state.state_root, state.contract_address, &[key], &[left])
+        .unwrap();
+    assert_eq!(report.storage[0], Err(ProofError::PathOverflow { node }));
+}
+
+#[test]
+fn empty_tree_proves_non_membership() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let state = RandomState::new(&mut rng, vec![]);
+    let key = random_felt(&mut rng);
+
+    let report = state
+        .get_proof(&[key])
+        .verify(
+            state.state_root,
+            state.contract_address,
+            &[key],
+            &[FieldElement::ZERO],
+        )
+        .unwrap();
+    assert_eq!(report.storage, vec![Ok(Membership::NonMember)]);
+}
+
+#[test]
+fn random_class_tries_verify_and_reject_every_single_node_mutation() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let classes = random_storage(&mut rng, case);
+        let leaves: Vec<(FieldElement, FieldElement)> = classes
+            .iter()
+            .map(|(class_hash, compiled_class_hash)| {
+                (*class_hash, class_leaf_hash(*compiled_class_hash))
+            })
+            .collect();
+        let trie = merkle_tree(&leaves, TrieHash::Poseidon);
+
+        let (class_hash, compiled_class_hash) = classes[0];
+        let output = GetClassProofOutput {
+            class_proof: trie.get_proof(class_hash),
+        };
+        assert_eq!(
+            output.verify(trie.root(), class_hash, compiled_class_hash),
+            Ok(Membership::Member),
+            "case {case}"
+        );
+
+        let absent_class_hash = random_felt(&mut rng);
+        let absent_output = GetClassProofOutput {
+            class_proof: trie.get_proof(absent_class_hash),
+        };
+        assert_eq!(
+            absent_output.verify(trie.root(), absent_class_hash, compiled_class_hash),
+            Ok(Membership::NonMember),
+            "case {case}"
+        );
+
+        for (index, node) in output.class_proof.iter().enumerate() {
+            for mutated_node in node_mutations(node) {
+                let mut mutated = output.clone();
+                mutated.class_proof[index] = mutated_node;
+                assert!(
+                    matches!(
+                        mutated.verify(trie.root(), class_hash, compiled_class_hash),
+                        Err(ProofError::HashMismatch { node, .. }) if node == index
+                    ),
+                    "case {case}, class proof node {index}"
+                );
+            }
+        }
+    }
 }
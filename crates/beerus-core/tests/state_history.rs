@@ -0,0 +1,160 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod tests {
+    use beerus_core::lightclient::{
+        beerus::ProvenState,
+        ethereum::{EthereumLightClient, MockEthereumLightClient},
+        state_history::{
+            decode_log_state_update, fetch_state_updates, StateHistory,
+            LOG_STATE_UPDATE_BLOCK_RANGE, MAX_STATE_HISTORY_ENTRIES,
+            STATE_HISTORY_LOOKBACK_L1_BLOCKS,
+        },
+    };
+    use ethers::{
+        abi::{encode, Abi, Token},
+        types::{Log, H160, U256, U64},
+    };
+    use futures::TryStreamExt;
+    use starknet::core::types::FieldElement;
+    use tokio::sync::Mutex;
+
+    fn starknet_core_abi() -> Abi {
+        serde_json::from_str(include_str!("../src/resources/starknet_core_abi.json")).unwrap()
+    }
+
+    fn proven_state(block_number: u64) -> ProvenState {
+        ProvenState {
+            block_number,
+            state_root: FieldElement::from(block_number),
+            block_hash: FieldElement::from(block_number + 1),
+            l1_block: block_number * 10,
+        }
+    }
+
+    fn log_state_update(abi: &Abi, proven_state: &ProvenState) -> Log {
+        let data = encode(&[
+            Token::Uint(U256::from_big_endian(
+                &proven_state.state_root.to_bytes_be(),
+            )),
+            Token::Int(U256::from(proven_state.block_number)),
+            Token::Uint(U256::from_big_endian(
+                &proven_state.block_hash.to_bytes_be(),
+            )),
+        ]);
+        Log {
+            topics: vec![abi.event("LogStateUpdate").unwrap().signature()],
+            data: data.into(),
+            block_number: Some(U64::from(proven_state.l1_block)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn given_log_state_update_when_decode_log_state_update_should_work() {
+        let abi = starknet_core_abi();
+        let expected_result = proven_state(42);
+        let log = log_state_update(&abi, &expected_result);
+
+        let res = decode_log_state_update(&abi, &log).unwrap();
+
+        assert_eq!(res, expected_result);
+    }
+
+    #[test]
+    fn given_log_without_l1_block_when_decode_log_state_update_should_fail() {
+        let abi = starknet_core_abi();
+        let mut log = log_state_update(&abi, &proven_state(42));
+        log.block_number = None;
+
+        let res = decode_log_state_update(&abi, &log);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn given_other_event_when_decode_log_state_update_should_fail() {
+        let abi = starknet_core_abi();
+        let mut log = log_state_update(&abi, &proven_state(42));
+        log.topics = vec![abi.event("LogStateTransitionFact").unwrap().signature()];
+
+        let res = decode_log_state_update(&abi, &log);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn given_full_history_when_insert_should_evict_oldest_state() {
+        let mut history = StateHistory::new();
+        for block_number in 0..=MAX_STATE_HISTORY_ENTRIES as u64 {
+            history.insert(proven_state(block_number));
+        }
+
+        assert_eq!(history.len(), MAX_STATE_HISTORY_ENTRIES);
+        assert!(history.get(0).is_none());
+        assert_eq!(history.get(1), Some(&proven_state(1)));
+        assert_eq!(
+            history.latest(),
+            Some(&proven_state(MAX_STATE_HISTORY_ENTRIES as u64))
+        );
+    }
+
+    #[test]
+    fn given_sparse_history_when_previous_should_return_closest_earlier_state() {
+        let mut history = StateHistory::new();
+        history.insert(proven_state(2));
+        history.insert(proven_state(5));
+
+        assert_eq!(history.previous(2), None);
+        assert_eq!(history.previous(5), Some(&proven_state(2)));
+        assert_eq!(history.previous(9), Some(&proven_state(5)));
+    }
+
+    #[test]
+    fn given_indexed_history_when_next_l1_block_should_resume_after_last_indexed_block() {
+        let mut history = StateHistory::new();
+        assert_eq!(
+            history.next_l1_block(10_000),
+            10_000 - STATE_HISTORY_LOOKBACK_L1_BLOCKS
+        );
+
+        history.extend(vec![proven_state(1), proven_state(2)], 10_000);
+
+        assert_eq!(history.last_indexed_l1_block(), Some(10_000));
+        assert_eq!(history.next_l1_block(10_100), 10_001);
+        assert_eq!(history.get(2), Some(&proven_state(2)));
+    }
+
+    #[tokio::test]
+    async fn given_normal_conditions_when_fetch_state_updates_should_query_block_windows() {
+        let abi = starknet_core_abi();
+        let log = log_state_update(&abi, &proven_state(5));
+
+        let mut ethereum_lightclient_mock = MockEthereumLightClient::new();
+        ethereum_lightclient_mock
+            .expect_get_logs()
+            .times(2)
+            .returning(move |_filter| Ok(vec![log.clone()]));
+
+        let ethereum_lightclient: Mutex<Box<dyn EthereumLightClient>> =
+            Mutex::new(Box::new(ethereum_lightclient_mock));
+
+        let res: Vec<(Vec<ProvenState>, u64)> = fetch_state_updates(
+            &ethereum_lightclient,
+            H160::zero(),
+            &abi,
+            0,
+            LOG_STATE_UPDATE_BLOCK_RANGE + 1,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                (vec![proven_state(5)], LOG_STATE_UPDATE_BLOCK_RANGE - 1),
+                (vec![proven_state(5)], LOG_STATE_UPDATE_BLOCK_RANGE + 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod tests {
+    use beerus_core::lightclient::starknet::storage_key::{
+        storage_slots, storage_var_address, u256_to_felts, StorageValue,
+    };
+    use ethabi::Uint as U256;
+    use starknet::core::{crypto::pedersen_hash, types::FieldElement, utils::starknet_keccak};
+
+    fn felt(value: u64) -> FieldElement {
+        FieldElement::from(value)
+    }
+
+    #[test]
+    fn given_plain_variable_when_storage_var_address_should_return_sn_keccak_of_name() {
+        let address = storage_var_address("ERC20_total_supply", &[]);
+
+        assert_eq!(address, starknet_keccak(b"ERC20_total_supply"));
+    }
+
+    #[test]
+    fn given_nested_map_when_storage_var_address_should_fold_keys_with_pedersen() {
+        let address = storage_var_address("ERC20_allowances", &[felt(1), felt(2)]);
+
+        let expected_result = pedersen_hash(
+            &pedersen_hash(&starknet_keccak(b"ERC20_allowances"), &felt(1)),
+            &felt(2),
+        );
+        assert_eq!(address, expected_result);
+    }
+
+    #[test]
+    fn given_u256_when_u256_to_felts_should_split_low_and_high_parts() {
+        let value = (U256::from(7) << 128) + U256::from(5);
+
+        assert_eq!(u256_to_felts(value), [felt(5), felt(7)]);
+    }
+
+    #[test]
+    fn given_two_slots_when_u256_from_felts_should_join_low_and_high_parts() {
+        let value = U256::from_felts(&[felt(5), felt(7)]).unwrap();
+
+        assert_eq!(value, (U256::from(7) << 128) + U256::from(5));
+        assert_eq!(storage_slots::<U256>(felt(10)), vec![felt(10), felt(11)]);
+    }
+
+    #[test]
+    fn given_slot_above_128_bits_when_u256_from_felts_should_fail() {
+        let too_big = FieldElement::from_hex_be("0x100000000000000000000000000000000").unwrap();
+
+        assert!(U256::from_felts(&[too_big, felt(0)]).is_err());
+    }
+}
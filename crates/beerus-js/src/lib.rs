@@ -5,9 +5,7 @@ use wasm_bindgen::prelude::*;
 
 use beerus_core::{
     config::Config,
-    lightclient::{
-        beerus::BeerusLightClient, beerus::SyncStatus,
-    },
+    lightclient::{beerus::BeerusLightClient, beerus::SyncStatus},
 };
 
 #[allow(unused_macros)]
@@ -43,11 +41,11 @@ impl BeerusClient {
     }
 
     #[wasm_bindgen]
-    pub fn get_sync_status(&self) -> String {
-        match self.beerus.sync_status() {
-            &SyncStatus::NotSynced => "not synced".to_string(),
-            &SyncStatus::Syncing => "syncing".to_string(),
-            &SyncStatus::Synced => "sync successful".to_string(),
+    pub async fn get_sync_status(&self) -> String {
+        match self.beerus.sync_status().await {
+            SyncStatus::NotSynced => "not synced".to_string(),
+            SyncStatus::Syncing => "syncing".to_string(),
+            SyncStatus::Synced => "sync successful".to_string(),
         }
     }
 
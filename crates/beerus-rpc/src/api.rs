@@ -1,6 +1,9 @@
-use crate::models::EventFilterWithPage;
+use crate::models::{EventFilterWithPage, VerifiedEventsPage};
 
-use beerus_core::lightclient::starknet::storage_proof::GetProofOutput;
+use beerus_core::lightclient::{
+    beerus::{SyncState, VerifiedStorage},
+    starknet::storage_proof::GetProofOutput,
+};
 use helios::types::{BlockTag, CallOpts, ExecutionBlock};
 use jsonrpsee::{core::Error, proc_macros::rpc};
 
@@ -262,4 +265,47 @@ pub trait BeerusRpc {
         key: String,
         block_id: BlockId,
     ) -> Result<FieldElement, Error>;
+
+    // Beerus endpoints
+    #[method(name = "beerus_syncStatus")]
+    async fn beerus_sync_status(&self) -> Result<SyncState, Error>;
+
+    #[method(name = "beerus_getVerifiedEvents")]
+    async fn beerus_get_verified_events(
+        &self,
+        custom_filter: EventFilterWithPage,
+    ) -> Result<VerifiedEventsPage, Error>;
+
+    #[method(name = "beerus_getVerifiedStorage")]
+    async fn beerus_get_verified_storage(
+        &self,
+        contract_address: String,
+        keys: Vec<String>,
+        block_id: BlockId,
+    ) -> Result<VerifiedStorage, Error>;
+
+    #[method(name = "beerus_getStorageMany")]
+    async fn beerus_get_storage_many(
+        &self,
+        contract_address: String,
+        keys: Vec<String>,
+        block_id: BlockId,
+    ) -> Result<Vec<FieldElement>, Error>;
+
+    #[method(name = "beerus_erc20BalanceOf")]
+    async fn beerus_erc20_balance_of(
+        &self,
+        token: String,
+        owner: String,
+        block_id: BlockId,
+    ) -> Result<U256, Error>;
+
+    #[method(name = "beerus_erc20Allowance")]
+    async fn beerus_erc20_allowance(
+        &self,
+        token: String,
+        owner: String,
+        spender: String,
+        block_id: BlockId,
+    ) -> Result<U256, Error>;
 }
@@ -1,3 +1,4 @@
+use beerus_core::lightclient::starknet::storage_proof::ProofError;
 use jsonrpsee::{
     core::Error,
     types::error::{CallError, ErrorObject},
@@ -21,6 +22,9 @@ pub const CONTRACT_ERROR: i64 = 40;
 pub const INVALID_CONTRACT_CLASS: i64 = 50;
 pub const INTERNAL_SERVER_ERROR: i64 = 500;
 pub const PROOF_LIMIT_EXCEEDED: i64 = 10000;
+pub const PROOF_VERIFICATION_FAILED: i64 = 10001;
+pub const CLASS_HASH_MISMATCH: i64 = 10002;
+pub const EVENTS_LIMIT_EXCEEDED: i64 = 10003;
 pub const UNKNOWN_ERROR: i64 = 520;
 pub const INVALID_PARAMS: i64 = 400;
 
@@ -59,6 +63,12 @@ pub enum BeerusApiError {
     InternalServerError(i64, String),
     #[error("Too many storage keys requested")]
     ProofLimitExceeded(i64, String),
+    #[error("Proof verification failed")]
+    ProofVerificationFailed(i64, String),
+    #[error("Class hash mismatch")]
+    ClassHashMismatch(i64, String),
+    #[error("Too many blocks to verify for a page of events")]
+    EventsLimitExceeded(i64, String),
     #[error("Too many keys provided in a filter")]
     TooManyKeysInFilter(i64, String),
     #[error("Unknown error")]
@@ -121,11 +131,26 @@ impl From<JsonRpcError> for BeerusApiError {
             PROOF_LIMIT_EXCEEDED => {
                 BeerusApiError::ProofLimitExceeded(PROOF_LIMIT_EXCEEDED, err.message)
             }
+            PROOF_VERIFICATION_FAILED => {
+                BeerusApiError::ProofVerificationFailed(PROOF_VERIFICATION_FAILED, err.message)
+            }
+            CLASS_HASH_MISMATCH => {
+                BeerusApiError::ClassHashMismatch(CLASS_HASH_MISMATCH, err.message)
+            }
+            EVENTS_LIMIT_EXCEEDED => {
+                BeerusApiError::EventsLimitExceeded(EVENTS_LIMIT_EXCEEDED, err.message)
+            }
             _ => BeerusApiError::UnknownError(UNKNOWN_ERROR, err.message),
         }
     }
 }
 
+impl From<ProofError> for BeerusApiError {
+    fn from(err: ProofError) -> Self {
+        BeerusApiError::from(JsonRpcError::from(err))
+    }
+}
+
 impl From<BeerusApiError> for Error {
     fn from(err: BeerusApiError) -> Self {
         // Todo :: consider this conversion seems to be not convenient, cause
@@ -206,6 +231,17 @@ impl From<i64> for BeerusApiError {
                 PROOF_LIMIT_EXCEEDED,
                 "Too many storage keys requested".into(),
             ),
+            PROOF_VERIFICATION_FAILED => BeerusApiError::ProofVerificationFailed(
+                PROOF_VERIFICATION_FAILED,
+                "Proof verification failed".into(),
+            ),
+            CLASS_HASH_MISMATCH => {
+                BeerusApiError::ClassHashMismatch(CLASS_HASH_MISMATCH, "Class hash mismatch".into())
+            }
+            EVENTS_LIMIT_EXCEEDED => BeerusApiError::EventsLimitExceeded(
+                EVENTS_LIMIT_EXCEEDED,
+                "Too many blocks to verify for a page of events".into(),
+            ),
             _ => BeerusApiError::UnknownError(UNKNOWN_ERROR, "Unknown error".into()),
         }
     }
@@ -231,6 +267,9 @@ impl From<BeerusApiError> for (i64, String) {
             BeerusApiError::InvalidContractClass(code, msg) => (code, msg),
             BeerusApiError::InternalServerError(code, msg) => (code, msg),
             BeerusApiError::ProofLimitExceeded(code, msg) => (code, msg),
+            BeerusApiError::ProofVerificationFailed(code, msg) => (code, msg),
+            BeerusApiError::ClassHashMismatch(code, msg) => (code, msg),
+            BeerusApiError::EventsLimitExceeded(code, msg) => (code, msg),
             BeerusApiError::UnknownError(code, msg) => (code, msg),
             _ => (520, String::from("Unknown")), // Unknown error
         }
@@ -4,10 +4,13 @@ pub mod models;
 pub mod utils;
 
 use crate::api::BeerusRpcServer;
-use crate::models::EventFilterWithPage;
+use crate::models::{EventFilterWithPage, VerifiedEventsPage};
 use beerus_core::{
     ethers_helper::{parse_eth_address, parse_eth_hash},
-    lightclient::starknet::storage_proof::GetProofOutput,
+    lightclient::{
+        beerus::{SyncState, VerifiedStorage},
+        starknet::storage_proof::GetProofOutput,
+    },
 };
 
 use helios::types::{BlockTag, CallOpts, ExecutionBlock};
@@ -318,10 +321,7 @@ impl BeerusRpcServer for BeerusRpc {
 
     async fn eth_syncing(&self) -> Result<SyncingStatus, Error> {
         self.beerus
-            .ethereum_lightclient
-            .lock()
-            .await
-            .syncing()
+            .eth_syncing()
             .await
             .map_err(|e| Error::from(anyhow::anyhow!(e.to_string())))
     }
@@ -392,7 +392,7 @@ impl BeerusRpcServer for BeerusRpc {
 
         let nonce = self
             .beerus
-            .starknet_get_nonce(contract_address, &block_id)
+            .starknet_get_verified_nonce(contract_address, &block_id)
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))?;
 
@@ -407,25 +407,25 @@ impl BeerusRpcServer for BeerusRpc {
             FieldElement::from_hex_be(tx_hash).map_err(|_| invalid_call_data("tx_hash_felt"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_transaction_by_hash(tx_hash_felt)
+            .get_verified_transaction_by_hash(format!("0x{tx_hash_felt:x}"))
             .await
+            .map(|verified_transaction| verified_transaction.transaction)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
     async fn starknet_get_block_transaction_count(&self, block_id: BlockId) -> Result<u64, Error> {
         self.beerus
-            .starknet_lightclient
             .get_block_transaction_count(&block_id)
             .await
+            .map(|count| count as u64)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
     async fn starknet_block_hash_and_number(&self) -> Result<BlockHashAndNumber, Error> {
         self.beerus
-            .starknet_lightclient
-            .block_hash_and_number()
+            .get_verified_block_hash_and_number()
             .await
+            .map(|verified_block| verified_block.block)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
@@ -461,8 +461,7 @@ impl BeerusRpcServer for BeerusRpc {
             .map_err(|_| invalid_call_data("contract_address"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_class_at(&block_id, contract_address)
+            .get_verified_class_at(&block_id, contract_address)
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
@@ -483,9 +482,9 @@ impl BeerusRpcServer for BeerusRpc {
         block_id: BlockId,
     ) -> Result<MaybePendingBlockWithTxHashes, Error> {
         self.beerus
-            .starknet_lightclient
-            .get_block_with_tx_hashes(&block_id)
+            .get_verified_block_with_tx_hashes(&block_id)
             .await
+            .map(|verified_block| verified_block.block)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
@@ -497,8 +496,7 @@ impl BeerusRpcServer for BeerusRpc {
         let index = u64::from_str(index).map_err(|_| invalid_call_data("index"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_transaction_by_block_id_and_index(&block_id, index)
+            .get_transaction_by_block_and_index(&block_id, index)
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
@@ -508,9 +506,9 @@ impl BeerusRpcServer for BeerusRpc {
         block_id: BlockId,
     ) -> Result<MaybePendingBlockWithTxs, Error> {
         self.beerus
-            .starknet_lightclient
-            .get_block_with_txs(&block_id)
+            .get_verified_block_with_txs(&block_id)
             .await
+            .map(|verified_block| verified_block.block)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
@@ -519,16 +517,15 @@ impl BeerusRpcServer for BeerusRpc {
         block_id: BlockId,
     ) -> Result<MaybePendingStateUpdate, Error> {
         self.beerus
-            .starknet_lightclient
-            .get_state_update(&block_id)
+            .get_verified_state_update(&block_id)
             .await
+            .map(|verified_state_update| verified_state_update.state_update)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
     async fn starknet_syncing(&self) -> Result<SyncStatusType, Error> {
         self.beerus
-            .starknet_lightclient
-            .syncing()
+            .starknet_syncing()
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
@@ -562,9 +559,9 @@ impl BeerusRpcServer for BeerusRpc {
             FieldElement::from_hex_be(&tx_hash).map_err(|_| invalid_call_data("tx_hash_felt"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_transaction_receipt(tx_hash_felt)
+            .get_verified_transaction_receipt(format!("0x{tx_hash_felt:x}"))
             .await
+            .map(|verified_receipt| verified_receipt.transaction)
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
 
@@ -577,8 +574,7 @@ impl BeerusRpcServer for BeerusRpc {
             .map_err(|_| invalid_call_data("contract_address"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_class_hash_at(&block_id, contract_address)
+            .starknet_get_verified_class_hash_at(contract_address, &block_id)
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
@@ -592,8 +588,7 @@ impl BeerusRpcServer for BeerusRpc {
             FieldElement::from_str(&class_hash).map_err(|_| invalid_call_data("class_hash"))?;
 
         self.beerus
-            .starknet_lightclient
-            .get_class(&block_id, class_hash)
+            .get_verified_class(&block_id, class_hash)
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
@@ -683,4 +678,100 @@ impl BeerusRpcServer for BeerusRpc {
             .await
             .map_err(|e| Error::from(BeerusApiError::from(e)))
     }
+
+    // Beerus methods
+    async fn beerus_sync_status(&self) -> Result<SyncState, Error> {
+        Ok(self.beerus.sync_state().await)
+    }
+
+    async fn beerus_get_verified_events(
+        &self,
+        custom_filter: EventFilterWithPage,
+    ) -> Result<VerifiedEventsPage, Error> {
+        let verified_events = self
+            .beerus
+            .get_verified_events(
+                custom_filter.filter,
+                custom_filter.page.continuation_token,
+                custom_filter.page.chunk_size,
+            )
+            .await
+            .map_err(|e| Error::from(BeerusApiError::from(e)))?;
+
+        Ok(VerifiedEventsPage {
+            page: verified_events.page,
+            verified: verified_events.verified,
+        })
+    }
+
+    async fn beerus_get_verified_storage(
+        &self,
+        contract_address: String,
+        keys: Vec<String>,
+        block_id: BlockId,
+    ) -> Result<VerifiedStorage, Error> {
+        let contract_address = FieldElement::from_hex_be(&contract_address)
+            .map_err(|_| invalid_call_data("contract_address"))?;
+
+        let keys: Result<Vec<FieldElement>, _> =
+            keys.iter().map(|k| FieldElement::from_hex_be(k)).collect();
+        let keys = keys.map_err(|_| invalid_call_data("keys"))?;
+
+        self.beerus
+            .get_verified_storage(contract_address, keys, &block_id)
+            .await
+            .map_err(|e| Error::from(BeerusApiError::from(e)))
+    }
+
+    async fn beerus_get_storage_many(
+        &self,
+        contract_address: String,
+        keys: Vec<String>,
+        block_id: BlockId,
+    ) -> Result<Vec<FieldElement>, Error> {
+        let contract_address = FieldElement::from_hex_be(&contract_address)
+            .map_err(|_| invalid_call_data("contract_address"))?;
+
+        let keys: Result<Vec<FieldElement>, _> =
+            keys.iter().map(|k| FieldElement::from_hex_be(k)).collect();
+        let keys = keys.map_err(|_| invalid_call_data("keys"))?;
+
+        self.beerus
+            .get_storage_many(contract_address, keys, &block_id)
+            .await
+            .map_err(|e| Error::from(BeerusApiError::from(e)))
+    }
+
+    async fn beerus_erc20_balance_of(
+        &self,
+        token: String,
+        owner: String,
+        block_id: BlockId,
+    ) -> Result<U256, Error> {
+        let token = FieldElement::from_hex_be(&token).map_err(|_| invalid_call_data("token"))?;
+        let owner = FieldElement::from_hex_be(&owner).map_err(|_| invalid_call_data("owner"))?;
+
+        self.beerus
+            .erc20_balance_of(token, owner, &block_id)
+            .await
+            .map_err(|e| Error::from(BeerusApiError::from(e)))
+    }
+
+    async fn beerus_erc20_allowance(
+        &self,
+        token: String,
+        owner: String,
+        spender: String,
+        block_id: BlockId,
+    ) -> Result<U256, Error> {
+        let token = FieldElement::from_hex_be(&token).map_err(|_| invalid_call_data("token"))?;
+        let owner = FieldElement::from_hex_be(&owner).map_err(|_| invalid_call_data("owner"))?;
+        let spender =
+            FieldElement::from_hex_be(&spender).map_err(|_| invalid_call_data("spender"))?;
+
+        self.beerus
+            .allowance(token, owner, spender, &block_id)
+            .await
+            .map_err(|e| Error::from(BeerusApiError::from(e)))
+    }
 }
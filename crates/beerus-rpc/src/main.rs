@@ -3,6 +3,7 @@ use beerus_rpc::BeerusRpc;
 use env_logger::Env;
 use log::{error, info};
 use std::process::exit;
+use tokio::signal;
 
 #[tokio::main]
 async fn main() {
@@ -25,6 +26,9 @@ async fn main() {
         exit(1);
     };
 
+    // Keep a handle on the sync loop, the light client itself is moved into the server.
+    let sync_handle = beerus.take_sync_handle();
+
     info!("starting beerus rpc server...");
     match BeerusRpc::new(beerus).run().await {
         Ok((addr, server_handle)) => {
@@ -32,11 +36,56 @@ async fn main() {
             info!("Beerus JSON-RPC server started 🚀: http://{addr}");
             info!("===================================================");
 
-            server_handle.stopped().await;
+            shutdown_signal().await;
+
+            info!("stopping beerus rpc server...");
+            // Stop accepting new requests and wait for the in-flight ones to complete.
+            if server_handle.stop().is_ok() {
+                server_handle.stopped().await;
+            }
         }
         Err(err) => {
             error! {"{}", err};
             exit(1);
         }
     };
+
+    info!("stopping the Beerus light client...");
+    if let Some(sync_handle) = sync_handle {
+        if let Err(err) = sync_handle.stop().await {
+            error!("{}", err);
+            exit(1);
+        }
+    }
+    info!("Beerus stopped");
+}
+
+/// Wait for SIGINT (Ctrl-C) or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = signal::ctrl_c().await {
+            error!("failed to listen for SIGINT: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                error!("failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("received SIGINT"),
+        _ = terminate => info!("received SIGTERM"),
+    }
 }
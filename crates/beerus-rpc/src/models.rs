@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use starknet::core::types::EventFilter;
+use starknet::core::types::{EventFilter, EventsPage};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde_as]
@@ -19,3 +19,11 @@ pub struct ResultPageRequest {
     pub continuation_token: Option<String>,
     pub chunk_size: u64,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedEventsPage {
+    #[serde(flatten)]
+    pub page: EventsPage,
+    /// Whether every block of the page matches the block hash committed on L1
+    pub verified: bool,
+}
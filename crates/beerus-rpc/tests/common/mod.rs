@@ -1,8 +1,9 @@
 use beerus_core::{
     config::Config,
     lightclient::{
-        beerus::BeerusLightClient, ethereum::MockEthereumLightClient,
-        starknet::StarkNetLightClientImpl,
+        beerus::{BeerusLightClient, ProvenState, SyncState},
+        ethereum::MockEthereumLightClient,
+        starknet::{commitment::calculate_block_hash, StarkNetLightClientImpl},
     },
     starknet_helper::create_mock_broadcasted_transaction,
 };
@@ -10,7 +11,9 @@ use beerus_rpc::BeerusRpc;
 use reqwest::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use starknet::core::types::{BlockId, BlockTag, EventFilter, FieldElement, FunctionCall};
+use starknet::core::types::{
+    BlockId, BlockStatus, BlockTag, BlockWithTxs, EventFilter, FieldElement, FunctionCall,
+};
 use std::path::PathBuf;
 use std::str::FromStr;
 use wiremock::{
@@ -79,15 +82,6 @@ impl<'a, StarknetParams> StarknetRpcBaseData<'a, StarknetParams> {
         }
     }
 
-    pub const fn starknet_block_hash_and_number(params: StarknetParams) -> Self {
-        Self {
-            id: 1,
-            jsonrpc: "2.0",
-            method: "starknet_blockHashAndNumber",
-            params,
-        }
-    }
-
     pub const fn starknet_get_transaction_by_block_id_and_index(params: StarknetParams) -> Self {
         Self {
             id: 1,
@@ -97,15 +91,6 @@ impl<'a, StarknetParams> StarknetRpcBaseData<'a, StarknetParams> {
         }
     }
 
-    pub const fn starknet_get_block_with_tx_hashes(params: StarknetParams) -> Self {
-        Self {
-            id: 1,
-            jsonrpc: "2.0",
-            method: "starknet_getBlockWithTxHashes",
-            params,
-        }
-    }
-
     pub const fn starknet_call(params: StarknetParams) -> Self {
         Self {
             id: 1,
@@ -123,32 +108,69 @@ pub async fn setup_wiremock() -> String {
     mock_get_events().mount(&mock_server).await;
     mock_estimate_fee().mount(&mock_server).await;
     mock_starknet_syncing().mount(&mock_server).await;
-    mock_starknet_block_hash_and_number()
-        .mount(&mock_server)
-        .await;
     mock_starknet_get_transaction_by_block_id_and_index()
         .mount(&mock_server)
         .await;
-    mock_starknet_get_block_with_tx_hashes()
-        .mount(&mock_server)
-        .await;
     mock_starknet_call().mount(&mock_server).await;
 
     mock_server.uri()
 }
 
 pub async fn setup_beerus_rpc() -> BeerusRpc {
+    setup_beerus_rpc_with_sync_state(SyncState::default()).await
+}
+
+pub async fn setup_beerus_rpc_with_sync_state(sync_state: SyncState) -> BeerusRpc {
+    let beerus_client = setup_beerus_client().await;
+    beerus_client.node.write().await.sync = sync_state;
+    BeerusRpc::new(beerus_client)
+}
+
+/// Return a Beerus RPC whose sync loop tracked `block`, `tracked_block()` being proven on L1.
+pub async fn setup_beerus_rpc_with_tracked_block(block: BlockWithTxs) -> BeerusRpc {
+    let beerus_client = setup_beerus_client().await;
+    {
+        let mut node = beerus_client.node.write().await;
+        let proven_block = tracked_block();
+        node.proven_state = Some(ProvenState {
+            block_number: proven_block.block_number,
+            state_root: proven_block.new_root,
+            block_hash: proven_block.block_hash,
+            l1_block: 1,
+        });
+        node.block_number = block.block_number;
+        node.payload.insert(block).unwrap();
+    }
+    BeerusRpc::new(beerus_client)
+}
+
+/// Return a block without transactions whose block hash matches its header.
+pub fn tracked_block() -> BlockWithTxs {
+    let mut block = BlockWithTxs {
+        status: BlockStatus::AcceptedOnL2,
+        block_hash: FieldElement::ZERO,
+        parent_hash: FieldElement::ONE,
+        block_number: 100_000,
+        new_root: FieldElement::TWO,
+        timestamp: 10,
+        sequencer_address: FieldElement::from(3u64),
+        transactions: vec![],
+    };
+    block.block_hash = calculate_block_hash(&block, &[]);
+    block
+}
+
+async fn setup_beerus_client() -> BeerusLightClient {
     let mut config = Config::from_file(&PathBuf::from("tests/common/data/test.toml"));
     config.starknet_rpc = setup_wiremock().await;
     let ethereum_lightclient = MockEthereumLightClient::new();
     let starknet_lightclient = StarkNetLightClientImpl::new(&config).unwrap();
 
-    let beerus_client = BeerusLightClient::new_from_clients(
+    BeerusLightClient::new_from_clients(
         config,
         Box::new(ethereum_lightclient),
         Box::new(starknet_lightclient),
-    );
-    BeerusRpc::new(beerus_client)
+    )
 }
 
 fn mock_block_number() -> Mock {
@@ -229,18 +251,6 @@ fn mock_starknet_syncing() -> Mock {
         ))
 }
 
-fn mock_starknet_block_hash_and_number() -> Mock {
-    let stub: Vec<u8> = vec![];
-    Mock::given(method("POST"))
-        .and(body_json(
-            StarknetRpcBaseData::starknet_block_hash_and_number(stub),
-        ))
-        .respond_with(response_template_with_status(StatusCode::OK).set_body_raw(
-            include_str!("data/starknet_blockHashAndNumber.json"),
-            "application/json",
-        ))
-}
-
 fn mock_starknet_get_transaction_by_block_id_and_index() -> Mock {
     let latest_block = BlockId::Tag(BlockTag::Latest);
     let index: u64 = 5;
@@ -257,18 +267,6 @@ fn mock_starknet_get_transaction_by_block_id_and_index() -> Mock {
         ))
 }
 
-fn mock_starknet_get_block_with_tx_hashes() -> Mock {
-    let latest_block = BlockId::Tag(BlockTag::Latest);
-    Mock::given(method("POST"))
-        .and(body_json(
-            StarknetRpcBaseData::starknet_get_block_with_tx_hashes([&latest_block]),
-        ))
-        .respond_with(response_template_with_status(StatusCode::OK).set_body_raw(
-            include_str!("data/starknet_getBlockWithTxHashes.json"),
-            "application/json",
-        ))
-}
-
 fn mock_starknet_call() -> Mock {
     let request = FunctionCall {
         contract_address: FieldElement::from_hex_be(
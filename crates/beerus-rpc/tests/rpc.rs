@@ -3,16 +3,20 @@ mod common;
 #[cfg(test)]
 mod tests {
 
-    use crate::common::setup_beerus_rpc;
-    use beerus_core::starknet_helper::{
-        create_mock_broadcasted_transaction, create_mock_get_events,
+    use crate::common::{
+        setup_beerus_rpc, setup_beerus_rpc_with_sync_state, setup_beerus_rpc_with_tracked_block,
+        tracked_block,
+    };
+    use beerus_core::{
+        lightclient::beerus::{ProvenState, SyncState, SyncStatus},
+        starknet_helper::{create_mock_broadcasted_transaction, create_mock_get_events},
     };
     use beerus_rpc::api::BeerusRpcServer;
     use beerus_rpc::models::{EventFilterWithPage, ResultPageRequest};
     use starknet::core::types::{
-        BlockId, BlockStatus, BlockTag, BlockWithTxHashes, EventFilter, FeeEstimate, FieldElement,
-        FunctionCall, InvokeTransaction, InvokeTransactionV1, MaybePendingBlockWithTxHashes,
-        SyncStatusType, Transaction,
+        BlockId, BlockTag, BlockWithTxHashes, EventFilter, FeeEstimate, FieldElement, FunctionCall,
+        InvokeTransaction, InvokeTransactionV1, MaybePendingBlockWithTxHashes,
+        MaybePendingBlockWithTxs, SyncStatusType, Transaction,
     };
 
     #[tokio::test]
@@ -121,9 +125,23 @@ mod tests {
         assert_eq!(expected.overall_fee, actual.overall_fee);
     }
 
+    fn synced_state() -> SyncState {
+        let mut sync_state = SyncState::default();
+        sync_state.start();
+        sync_state.record_l1_synced(true);
+        sync_state.record_proven_state(&ProvenState {
+            block_number: 27000,
+            state_root: FieldElement::ONE,
+            block_hash: FieldElement::ONE,
+            l1_block: 100,
+        });
+        sync_state.record_l2_tip(27468, FieldElement::TWO);
+        sync_state
+    }
+
     #[tokio::test]
     async fn starknet_syncing_ok() {
-        let beerus_rpc = setup_beerus_rpc().await;
+        let beerus_rpc = setup_beerus_rpc_with_sync_state(synced_state()).await;
 
         let sync_status = beerus_rpc.starknet_syncing().await.unwrap();
 
@@ -150,17 +168,38 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn starknet_starknet_block_hash_and_number_ok() {
+    async fn starknet_syncing_not_synced_returns_light_client_progress() {
         let beerus_rpc = setup_beerus_rpc().await;
+
+        let sync_status = beerus_rpc.starknet_syncing().await.unwrap();
+
+        match sync_status {
+            SyncStatusType::Syncing(result) => {
+                assert_eq!(result.current_block_num, 0);
+                assert_eq!(result.highest_block_num, 0);
+                assert_eq!(result.starting_block_num, 0);
+            }
+            SyncStatusType::NotSyncing => panic!("Syncing status should be true"),
+        }
+    }
+
+    #[tokio::test]
+    async fn beerus_sync_status_ok() {
+        let beerus_rpc = setup_beerus_rpc_with_sync_state(synced_state()).await;
+
+        let sync_state = beerus_rpc.beerus_sync_status().await.unwrap();
+
+        assert_eq!(sync_state.status, SyncStatus::Synced);
+        assert_eq!(sync_state.l2_lag, Some(468));
+        assert_eq!(sync_state, synced_state());
+    }
+
+    #[tokio::test]
+    async fn starknet_starknet_block_hash_and_number_ok() {
+        let beerus_rpc = setup_beerus_rpc_with_tracked_block(tracked_block()).await;
         let result = beerus_rpc.starknet_block_hash_and_number().await.unwrap();
-        assert_eq!(result.block_number, 27461);
-        assert_eq!(
-            result.block_hash,
-            FieldElement::from_hex_be(
-                "0x63813d0cd71bf351dfe3217f9d2dcd8871cf4d56c0ffe3563980b3d02b6898d"
-            )
-            .unwrap()
-        );
+        assert_eq!(result.block_number, 100_000);
+        assert_eq!(result.block_hash, tracked_block().block_hash);
     }
 
     #[tokio::test]
@@ -190,24 +229,24 @@ mod tests {
 
     #[tokio::test]
     async fn starknet_get_block_with_tx_hashes_ok() {
-        let beerus_rpc = setup_beerus_rpc().await;
+        let beerus_rpc = setup_beerus_rpc_with_tracked_block(tracked_block()).await;
         let block_with_tx_hashes = beerus_rpc
             .starknet_get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
             .await
             .unwrap();
 
-        let felt = FieldElement::from_hex_be("0x1").unwrap();
-        let block = BlockWithTxHashes {
-            status: BlockStatus::AcceptedOnL2,
-            block_hash: felt.clone(),
-            parent_hash: felt.clone(),
-            block_number: 1,
-            new_root: felt.clone(),
-            timestamp: 10,
-            sequencer_address: felt.clone(),
-            transactions: vec![felt.clone()],
-        };
-        let expected_block_with_tx_hashes = MaybePendingBlockWithTxHashes::Block(block);
+        let block = tracked_block();
+        let expected_block_with_tx_hashes =
+            MaybePendingBlockWithTxHashes::Block(BlockWithTxHashes {
+                status: block.status,
+                block_hash: block.block_hash,
+                parent_hash: block.parent_hash,
+                block_number: block.block_number,
+                new_root: block.new_root,
+                timestamp: block.timestamp,
+                sequencer_address: block.sequencer_address,
+                transactions: vec![],
+            });
 
         assert_eq!(
             serde_json::to_string(&block_with_tx_hashes).unwrap(),
@@ -215,6 +254,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn starknet_get_block_with_txs_ok() {
+        let beerus_rpc = setup_beerus_rpc_with_tracked_block(tracked_block()).await;
+        let block_with_txs = beerus_rpc
+            .starknet_get_block_with_txs(BlockId::Number(100_000))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&block_with_txs).unwrap(),
+            serde_json::to_string(&MaybePendingBlockWithTxs::Block(tracked_block())).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn starknet_get_block_with_txs_tampered_block_err() {
+        let mut block = tracked_block();
+        block.timestamp += 1;
+        let beerus_rpc = setup_beerus_rpc_with_tracked_block(block).await;
+
+        let result = beerus_rpc
+            .starknet_get_block_with_txs(BlockId::Number(100_000))
+            .await;
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn starknet_call() {
         let beerus_rpc = setup_beerus_rpc().await;
@@ -0,0 +1,164 @@
+use beerus_core::lightclient::starknet::storage_proof::{GetProofOutput, Membership};
+use clap::Parser;
+use eyre::{eyre, Result};
+use serde::Deserialize;
+use starknet::core::types::FieldElement;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// Verify a `pathfinder_getProof` output against a StarkNet state root, without any network
+/// access.
+#[derive(Debug, Parser)]
+#[command(name = "beerus-verify", version, about)]
+pub struct Args {
+    /// JSON file holding the `GetProofOutput`, either bare or as a JSON-RPC response.
+    #[arg(long)]
+    pub proof: PathBuf,
+    /// StarkNet state root the proof is checked against.
+    #[arg(long, value_parser = parse_felt)]
+    pub state_root: FieldElement,
+    /// Address of the contract.
+    #[arg(long, value_parser = parse_felt)]
+    pub contract_address: FieldElement,
+    /// Storage key, repeated for every key of the proof.
+    #[arg(long = "key", value_parser = parse_felt)]
+    pub keys: Vec<FieldElement>,
+    /// Storage value, repeated in the order of the keys.
+    #[arg(long = "value", value_parser = parse_felt)]
+    pub values: Vec<FieldElement>,
+}
+
+/// Result of the verification of a single storage key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyOutcome {
+    pub key: FieldElement,
+    pub value: FieldElement,
+    /// Membership of the key, or the reason its proof failed.
+    pub result: Result<Membership, String>,
+}
+
+/// Result of the verification of a proof whose contract proof is valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub contract: Membership,
+    pub keys: Vec<KeyOutcome>,
+}
+
+impl Outcome {
+    /// Return `true` if every storage key was verified.
+    pub fn is_valid(&self) -> bool {
+        self.keys.iter().all(|key| key.result.is_ok())
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "contract: {}", membership_str(&self.contract))?;
+        for key in self.keys.iter() {
+            match &key.result {
+                Ok(membership) => writeln!(
+                    f,
+                    "0x{:x} = 0x{:x}: {}",
+                    key.key,
+                    key.value,
+                    membership_str(membership)
+                )?,
+                Err(reason) => {
+                    writeln!(f, "0x{:x} = 0x{:x}: invalid ({reason})", key.key, key.value)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the proof file and verify it against the arguments.
+///
+/// # Errors
+///
+/// This function returns an error if the proof file cannot be read or parsed, or if the
+/// contract proof is invalid. Invalid storage proofs are reported in the `Outcome`.
+pub fn run(args: &Args) -> Result<Outcome> {
+    let proof = read_proof(&args.proof)?;
+    verify(
+        &proof,
+        args.state_root,
+        args.contract_address,
+        &args.keys,
+        &args.values,
+    )
+}
+
+/// Verify a proof against a state root, reporting the result of every storage key.
+///
+/// A non-membership proof is only valid for a zero value.
+///
+/// # Errors
+///
+/// This function returns an error if the contract proof is invalid.
+pub fn verify(
+    proof: &GetProofOutput,
+    state_root: FieldElement,
+    contract_address: FieldElement,
+    keys: &[FieldElement],
+    values: &[FieldElement],
+) -> Result<Outcome> {
+    let report = proof
+        .verify(state_root, contract_address, keys, values)
+        .map_err(|e| eyre!("Invalid proof for contract 0x{contract_address:x}: {e}"))?;
+
+    let keys = keys
+        .iter()
+        .zip(values.iter())
+        .zip(report.storage)
+        .map(|((key, value), result)| KeyOutcome {
+            key: *key,
+            value: *value,
+            result: match result {
+                Ok(Membership::NonMember) if *value != FieldElement::ZERO => {
+                    Err("non-membership proof for a non-zero value".to_string())
+                }
+                result => result.map_err(|e| e.to_string()),
+            },
+        })
+        .collect();
+    Ok(Outcome {
+        contract: report.contract,
+        keys,
+    })
+}
+
+/// Read a `GetProofOutput` from a JSON file, either bare or wrapped in a JSON-RPC response.
+///
+/// # Errors
+///
+/// This function returns an error if the file cannot be read or does not hold a proof.
+pub fn read_proof(path: &PathBuf) -> Result<GetProofOutput> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ProofFile {
+        Response { result: GetProofOutput },
+        Output(GetProofOutput),
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| eyre!("Cannot read proof file {}: {e}", path.display()))?;
+    match serde_json::from_str(&content)
+        .map_err(|e| eyre!("Cannot parse proof file {}: {e}", path.display()))?
+    {
+        ProofFile::Response { result } => Ok(result),
+        ProofFile::Output(output) => Ok(output),
+    }
+}
+
+fn parse_felt(value: &str) -> Result<FieldElement, String> {
+    FieldElement::from_hex_be(value).map_err(|_| format!("invalid field element: {value}"))
+}
+
+fn membership_str(membership: &Membership) -> &'static str {
+    match membership {
+        Membership::Member => "member",
+        Membership::NonMember => "non_member",
+    }
+}
@@ -0,0 +1,20 @@
+use beerus_verify::{run, Args};
+use clap::Parser;
+use std::process::exit;
+
+fn main() {
+    let args = Args::parse();
+
+    match run(&args) {
+        Ok(outcome) => {
+            print!("{outcome}");
+            if !outcome.is_valid() {
+                exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("Proof verification failed: {err}");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use beerus_core::lightclient::starknet::storage_proof::Membership;
+use beerus_verify::{run, Args};
+use clap::Parser;
+use starknet::core::types::FieldElement;
+
+const PROOF_PATH: &str = "../beerus-core/tests/common/data/data.json";
+const STATE_ROOT: &str = "0x47f25798a804800b657d4e1508776e3c3c70f0d7587d125a558208f88570aa7";
+const CONTRACT_ADDRESS: &str = "0x4d4e07157aeb54abeb64f5792145f2e8db1c83bda01a8f06e050be18cfb8153";
+
+fn args(state_root: &str, value: &str) -> Args {
+    Args::try_parse_from([
+        "beerus-verify",
+        "--proof",
+        PROOF_PATH,
+        "--state-root",
+        state_root,
+        "--contract-address",
+        CONTRACT_ADDRESS,
+        "--key",
+        "0x1",
+        "--value",
+        value,
+    ])
+    .unwrap()
+}
+
+#[test]
+fn given_empty_slot_when_run_should_report_non_membership() {
+    let outcome = run(&args(STATE_ROOT, "0x0")).unwrap();
+
+    assert!(outcome.is_valid());
+    assert_eq!(outcome.contract, Membership::Member);
+    assert_eq!(outcome.keys[0].result, Ok(Membership::NonMember));
+    assert_eq!(
+        outcome.to_string(),
+        "contract: member\n0x1 = 0x0: non_member\n"
+    );
+}
+
+#[test]
+fn given_non_zero_value_for_empty_slot_when_run_should_report_failure_reason() {
+    let outcome = run(&args(STATE_ROOT, "0x2")).unwrap();
+
+    assert!(!outcome.is_valid());
+    assert_eq!(outcome.keys[0].value, FieldElement::TWO);
+    assert!(outcome.keys[0].result.is_err());
+}
+
+#[test]
+fn given_wrong_state_root_when_run_should_fail() {
+    let err = run(&args("0x1", "0x0")).unwrap_err();
+
+    assert!(err.to_string().contains("Invalid proof for contract"));
+}
+
+#[test]
+fn given_invalid_field_element_when_parse_args_should_fail() {
+    let res = Args::try_parse_from([
+        "beerus-verify",
+        "--proof",
+        PROOF_PATH,
+        "--state-root",
+        "not a felt",
+        "--contract-address",
+        CONTRACT_ADDRESS,
+    ]);
+
+    assert!(res.is_err());
+}